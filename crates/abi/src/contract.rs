@@ -1,19 +1,28 @@
-use super::{event::AbiEvent, function::AbiFunction};
+use super::{
+    error::AbiError,
+    event::AbiEvent,
+    function::{AbiFunction, AbiFunctionType},
+};
 
-use serde::{ser::SerializeSeq, Serialize, Serializer};
+use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AbiContract {
     /// Public functions in the contract.
-    funcs: Vec<AbiFunction>,
+    pub funcs: Vec<AbiFunction>,
 
     /// Events emitted from the contract.
-    events: Vec<AbiEvent>,
+    pub events: Vec<AbiEvent>,
+
+    /// Errors the contract reverts with.
+    pub errors: Vec<AbiError>,
 }
 
 impl Serialize for AbiContract {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        let mut seq = s.serialize_seq(Some(self.funcs.len() + self.events.len()))?;
+        let mut seq = s.serialize_seq(Some(
+            self.funcs.len() + self.events.len() + self.errors.len(),
+        ))?;
         for func in &self.funcs {
             seq.serialize_element(func)?;
         }
@@ -22,12 +31,140 @@ impl Serialize for AbiContract {
             seq.serialize_element(event)?;
         }
 
+        for error in &self.errors {
+            seq.serialize_element(error)?;
+        }
+
         seq.end()
     }
 }
 
+impl<'de> Deserialize<'de> for AbiContract {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(tag = "type", rename_all = "lowercase")]
+        enum Entry {
+            Function(AbiFunction),
+            Constructor(AbiFunction),
+            Receive(AbiFunction),
+            Fallback(AbiFunction),
+            Event(AbiEvent),
+            Error(AbiError),
+        }
+
+        let mut contract = Self::new(vec![], vec![], vec![]);
+        for entry in Vec::<Entry>::deserialize(d)? {
+            match entry {
+                Entry::Function(func) => contract.funcs.push(func),
+                Entry::Constructor(func) => contract.funcs.push(AbiFunction {
+                    func_type: AbiFunctionType::Constructor,
+                    ..func
+                }),
+                Entry::Receive(func) => contract.funcs.push(AbiFunction {
+                    func_type: AbiFunctionType::Receive,
+                    ..func
+                }),
+                Entry::Fallback(func) => contract.funcs.push(AbiFunction {
+                    func_type: AbiFunctionType::Fallback,
+                    ..func
+                }),
+                Entry::Event(event) => contract.events.push(event),
+                Entry::Error(error) => contract.errors.push(error),
+            }
+        }
+
+        Ok(contract)
+    }
+}
+
 impl AbiContract {
-    pub fn new(funcs: Vec<AbiFunction>, events: Vec<AbiEvent>) -> Self {
-        Self { funcs, events }
+    pub fn new(funcs: Vec<AbiFunction>, events: Vec<AbiEvent>, errors: Vec<AbiError>) -> Self {
+        Self {
+            funcs,
+            events,
+            errors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        error::AbiErrorField,
+        event::AbiEventField,
+        function::StateMutability,
+        types::{AbiTupleField, AbiType},
+    };
+
+    #[test]
+    fn deserialize_contract() {
+        let json = r#"[
+            {"type": "constructor", "inputs": [], "stateMutability": "nonpayable"},
+            {
+                "type": "function",
+                "name": "transfer",
+                "inputs": [
+                    {"name": "to", "type": "address", "internalType": "address"},
+                    {
+                        "name": "memo",
+                        "type": "tuple[]",
+                        "components": [{"name": "text", "type": "string"}]
+                    }
+                ],
+                "outputs": [{"name": "", "type": "bool"}],
+                "stateMutability": "nonpayable"
+            },
+            {
+                "type": "event",
+                "name": "Transfer",
+                "inputs": [{"name": "to", "type": "address", "indexed": true}],
+                "anonymous": false
+            },
+            {"type": "error", "name": "Denied", "inputs": [{"name": "code", "type": "uint8"}]}
+        ]"#;
+
+        let contract: AbiContract = serde_json::from_str(json).unwrap();
+        assert_eq!(contract.funcs.len(), 2);
+        assert_eq!(contract.funcs[0].func_type, AbiFunctionType::Constructor);
+
+        let transfer = &contract.funcs[1];
+        assert_eq!(transfer.func_type, AbiFunctionType::Function);
+        assert_eq!(transfer.state_mutability, StateMutability::Nonpayable);
+        assert_eq!(
+            transfer.selector().selector_signature(),
+            "transfer(address,(string)[])"
+        );
+        assert_eq!(
+            transfer.inputs[1].ty,
+            AbiType::DynArray {
+                elem_ty: AbiType::Tuple(vec![AbiTupleField::new("text".into(), AbiType::String)])
+                    .into()
+            }
+        );
+
+        assert_eq!(
+            contract.events,
+            vec![AbiEvent::new(
+                "Transfer".into(),
+                vec![AbiEventField::new("to".into(), AbiType::Address, true)],
+                false
+            )]
+        );
+        assert_eq!(
+            contract.errors,
+            vec![AbiError::new(
+                "Denied".into(),
+                vec![AbiErrorField::new("code".into(), AbiType::UInt(8))]
+            )]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = r#"[{"type":"function","name":"f","inputs":[{"name":"x","type":"int8[3]"}],"outputs":[],"stateMutability":"view"},{"type":"event","name":"E","inputs":[],"anonymous":true},{"type":"error","name":"Err","inputs":[]}]"#;
+        let contract: AbiContract = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&contract).unwrap(), json);
     }
 }
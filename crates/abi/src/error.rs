@@ -0,0 +1,146 @@
+use super::types::AbiType;
+
+use fe_common::utils::keccak;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiError {
+    #[serde(rename = "type", skip_deserializing, default = "error_ty")]
+    pub ty: &'static str,
+    pub name: String,
+    pub inputs: Vec<AbiErrorField>,
+}
+
+fn error_ty() -> &'static str {
+    "error"
+}
+
+impl AbiError {
+    pub fn new(name: String, fields: Vec<AbiErrorField>) -> Self {
+        Self {
+            ty: "error",
+            name,
+            inputs: fields,
+        }
+    }
+
+    pub fn selector(&self) -> AbiErrorSelector {
+        AbiErrorSelector::new(self)
+    }
+}
+
+pub struct AbiErrorSelector {
+    selector_sig: String,
+}
+
+impl AbiErrorSelector {
+    fn new(error: &AbiError) -> Self {
+        let selector_sig = format!(
+            "{}({})",
+            error.name,
+            error
+                .inputs
+                .iter()
+                .map(|input| input.ty.selector_type_name())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+
+        Self { selector_sig }
+    }
+
+    pub fn selector_signature(&self) -> &str {
+        &self.selector_sig
+    }
+
+    pub fn selector_raw(&self) -> [u8; 4] {
+        keccak::full_as_bytes(self.selector_sig.as_bytes())[..4]
+            .try_into()
+            .unwrap()
+    }
+
+    /// Returns first 4 bytes of signature hash in hex.
+    pub fn hex(&self) -> String {
+        keccak::partial(self.selector_sig.as_bytes(), 4)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiErrorField {
+    pub name: String,
+    #[serde(flatten)]
+    pub ty: AbiType,
+}
+
+impl AbiErrorField {
+    pub fn new(name: String, ty: impl Into<AbiType>) -> Self {
+        Self {
+            name,
+            ty: ty.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_test::{assert_ser_tokens, Token};
+
+    fn test_error() -> AbiError {
+        let u256_ty = AbiType::UInt(256);
+        let bool_ty = AbiType::Bool;
+        let field1 = AbiErrorField::new("code".into(), u256_ty);
+        let field2 = AbiErrorField::new("fatal".into(), bool_ty);
+
+        AbiError::new("MyError".into(), vec![field1, field2])
+    }
+
+    #[test]
+    fn serialize_error() {
+        let error = test_error();
+
+        assert_ser_tokens(
+            &error,
+            &[
+                Token::Struct {
+                    name: "AbiError",
+                    len: 3,
+                },
+                Token::Str("type"),
+                Token::Str("error"),
+                Token::String("name"),
+                Token::String("MyError"),
+                Token::Str("inputs"),
+                Token::Seq { len: Some(2) },
+                Token::Map { len: None },
+                Token::String("name"),
+                Token::String("code"),
+                Token::String("type"),
+                Token::String("uint256"),
+                Token::MapEnd,
+                Token::Map { len: None },
+                Token::String("name"),
+                Token::String("fatal"),
+                Token::String("type"),
+                Token::String("bool"),
+                Token::MapEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        )
+    }
+
+    #[test]
+    fn error_selector() {
+        let error = test_error();
+        let selector = error.selector();
+        debug_assert_eq!(selector.selector_signature(), "MyError(uint256,bool)");
+
+        let panic = AbiError::new(
+            "Panic".into(),
+            vec![AbiErrorField::new("code".into(), AbiType::UInt(256))],
+        );
+        debug_assert_eq!(panic.selector().hex(), "4e487b71");
+    }
+}
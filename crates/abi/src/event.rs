@@ -1,17 +1,22 @@
 use super::types::AbiType;
 
 use fe_common::utils::keccak;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AbiEvent {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", skip_deserializing, default = "event_ty")]
     pub ty: &'static str,
     pub name: String,
     pub inputs: Vec<AbiEventField>,
+    #[serde(default)]
     pub anonymous: bool,
 }
 
+fn event_ty() -> &'static str {
+    "event"
+}
+
 impl AbiEvent {
     pub fn new(name: String, fields: Vec<AbiEventField>, anonymous: bool) -> Self {
         Self {
@@ -60,7 +65,7 @@ impl AbiEventSignature {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AbiEventField {
     pub name: String,
     #[serde(flatten)]
@@ -1,11 +1,11 @@
 use fe_common::utils::keccak;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use super::types::AbiType;
 
 /// The mutability of a public function.
-#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum StateMutability {
     Pure,
@@ -14,49 +14,20 @@ pub enum StateMutability {
     Payable,
 }
 
-pub enum SelfParam {
-    None,
-    Imm,
-    Mut,
-}
-pub enum CtxParam {
-    None,
-    Imm,
-    Mut,
-}
-
-impl StateMutability {
-    pub fn from_self_and_ctx_params(self_: SelfParam, ctx: CtxParam) -> Self {
-        // Check ABI conformity
-        // See https://github.com/ethereum/fe/issues/558
-        //
-        //              no self   |   self   |  mut self  |
-        //           ......................................
-        // no ctx    :    pure    |   view    |  payable  |
-        // ctx       :    view    |   view    |  payable  |
-        // mut ctx   :   payable  |  payable  |  payable  |
-
-        match (self_, ctx) {
-            (SelfParam::None, CtxParam::None) => StateMutability::Pure,
-            (SelfParam::None, CtxParam::Imm) => StateMutability::View,
-            (SelfParam::None, CtxParam::Mut) => StateMutability::Payable,
-            (SelfParam::Imm, CtxParam::None) => StateMutability::View,
-            (SelfParam::Imm, CtxParam::Imm) => StateMutability::View,
-            (SelfParam::Imm, CtxParam::Mut) => StateMutability::Payable,
-            (SelfParam::Mut, _) => StateMutability::Payable,
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AbiFunction {
-    #[serde(rename = "type")]
-    func_type: AbiFunctionType,
-    name: String,
-    inputs: Vec<AbiFunctionParamInner>,
-    outputs: Vec<AbiFunctionParamInner>,
+    /// Taken from the `type` of the entry when deserializing an `AbiContract`.
+    #[serde(rename = "type", skip_deserializing)]
+    pub func_type: AbiFunctionType,
+    /// Empty for the constructor, `receive` and `fallback` functions.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<AbiFunctionParamInner>,
+    #[serde(default)]
+    pub outputs: Vec<AbiFunctionParamInner>,
     #[serde(rename = "stateMutability")]
-    state_mutability: StateMutability,
+    pub state_mutability: StateMutability,
 }
 
 impl AbiFunction {
@@ -89,9 +60,10 @@ impl AbiFunction {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AbiFunctionType {
+    #[default]
     Function,
     Constructor,
     Receive,
@@ -135,11 +107,11 @@ impl AbiFunctionSelector {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
-struct AbiFunctionParamInner {
-    name: String,
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiFunctionParamInner {
+    pub name: String,
     #[serde(flatten)]
-    ty: AbiType,
+    pub ty: AbiType,
 }
 
 impl AbiFunctionParamInner {
@@ -247,45 +219,6 @@ mod tests {
 
     #[test]
     fn test_state_mutability() {
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::None, CtxParam::None),
-            StateMutability::Pure
-        );
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::None, CtxParam::Imm),
-            StateMutability::View
-        );
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::None, CtxParam::Mut),
-            StateMutability::Payable
-        );
-
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::Imm, CtxParam::None),
-            StateMutability::View
-        );
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::Imm, CtxParam::Imm),
-            StateMutability::View
-        );
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::Imm, CtxParam::Mut),
-            StateMutability::Payable
-        );
-
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::Mut, CtxParam::None),
-            StateMutability::Payable
-        );
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::Mut, CtxParam::Imm),
-            StateMutability::Payable
-        );
-        assert_eq!(
-            StateMutability::from_self_and_ctx_params(SelfParam::Mut, CtxParam::Mut),
-            StateMutability::Payable
-        );
-
         let pure_func = test_func(StateMutability::Pure);
         assert_eq!(pure_func.state_mutability, StateMutability::Pure);
 
@@ -1,4 +1,4 @@
-use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AbiType {
@@ -7,7 +7,24 @@ pub enum AbiType {
     Address,
     Bool,
     Function,
-    Array { elem_ty: Box<AbiType>, len: usize },
+    /// `bytes1` to `bytes32`. Only found in imported ABIs, Fe has no
+    /// equivalent.
+    FixedBytes(usize),
+    /// `fixed<M>x<N>` and `ufixed<M>x<N>`. Only found in imported ABIs, Fe has
+    /// no equivalent.
+    Fixed {
+        signed: bool,
+        bits: usize,
+        decimals: usize,
+    },
+    Array {
+        elem_ty: Box<AbiType>,
+        len: usize,
+    },
+    /// A dynamically sized array, i.e. `T[]`.
+    DynArray {
+        elem_ty: Box<AbiType>,
+    },
     Tuple(Vec<AbiTupleField>),
     Bytes,
     String,
@@ -21,6 +38,12 @@ impl AbiType {
             Self::Address => "address".to_string(),
             Self::Bool => "bool".to_string(),
             Self::Function => "function".to_string(),
+            Self::FixedBytes(len) => format!("bytes{len}"),
+            Self::Fixed {
+                signed,
+                bits,
+                decimals,
+            } => format!("{}fixed{bits}x{decimals}", if *signed { "" } else { "u" }),
             Self::Array { elem_ty, len } => {
                 if elem_ty.as_ref() == &AbiType::UInt(8) {
                     "bytes".to_string()
@@ -28,6 +51,7 @@ impl AbiType {
                     format!("{}[{}]", elem_ty.selector_type_name(), len)
                 }
             }
+            Self::DynArray { elem_ty } => format!("{}[]", elem_ty.selector_type_name()),
             Self::Tuple(elems) => format!(
                 "({})",
                 elems
@@ -52,16 +76,23 @@ impl AbiType {
                     format!("{}[{}]", elem_ty.abi_type_name(), len)
                 }
             }
+            Self::DynArray { elem_ty } => format!("{}[]", elem_ty.abi_type_name()),
             _ => self.selector_type_name(),
         }
     }
 
     pub fn header_size(&self) -> usize {
         match self {
-            Self::UInt(_) | Self::Int(_) | Self::Address | Self::Bool | Self::Function => 32,
+            Self::UInt(_)
+            | Self::Int(_)
+            | Self::Address
+            | Self::Bool
+            | Self::Function
+            | Self::FixedBytes(_)
+            | Self::Fixed { .. } => 32,
 
             Self::Array { elem_ty, len } if elem_ty.is_static() => elem_ty.header_size() * len,
-            Self::Array { .. } => 32,
+            Self::Array { .. } | Self::DynArray { .. } => 32,
 
             Self::Tuple(fields) if self.is_static() => fields
                 .iter()
@@ -95,17 +126,28 @@ impl AbiType {
 
     pub fn is_static(&self) -> bool {
         match self {
-            Self::UInt(_) | Self::Int(_) | Self::Address | Self::Bool | Self::Function => true,
+            Self::UInt(_)
+            | Self::Int(_)
+            | Self::Address
+            | Self::Bool
+            | Self::Function
+            | Self::FixedBytes(_)
+            | Self::Fixed { .. } => true,
             Self::Array { elem_ty, .. } => elem_ty.is_static(),
             Self::Tuple(fields) => fields.iter().all(|field| field.ty.is_static()),
-            Self::Bytes | Self::String => false,
+            Self::DynArray { .. } | Self::Bytes | Self::String => false,
         }
     }
 
     /// Returns bytes size of the encoded type if the type is static.
     pub fn size(&self) -> Option<usize> {
         match self {
-            Self::UInt(_) | Self::Int(_) | Self::Address | Self::Bool => Some(32),
+            Self::UInt(_)
+            | Self::Int(_)
+            | Self::Address
+            | Self::Bool
+            | Self::FixedBytes(_)
+            | Self::Fixed { .. } => Some(32),
             Self::Function => Some(24),
             Self::Array { elem_ty, len } => Some(elem_ty.size()? * len),
             Self::Tuple(fields) => {
@@ -116,14 +158,90 @@ impl AbiType {
                 Some(size)
             }
 
-            Self::Bytes | Self::String => None,
+            Self::DynArray { .. } | Self::Bytes | Self::String => None,
         }
     }
 
+    /// Parses a type name of the ABI JSON, e.g. `uint256[]` or `tuple[2]`.
+    /// `components` are the fields of the innermost tuple type.
+    pub fn parse(name: &str, components: Vec<AbiTupleField>) -> Result<Self, String> {
+        if let Some(prefix) = name.strip_suffix(']') {
+            let (elem, len) = prefix
+                .rsplit_once('[')
+                .ok_or_else(|| format!("invalid ABI type `{name}`"))?;
+            let elem_ty = Box::new(Self::parse(elem, components)?);
+            return if len.is_empty() {
+                Ok(Self::DynArray { elem_ty })
+            } else {
+                let len = len
+                    .parse()
+                    .map_err(|_| format!("invalid array length in ABI type `{name}`"))?;
+                Ok(Self::Array { elem_ty, len })
+            };
+        }
+
+        let bits = |digits: &str| match digits {
+            "" => Some(256),
+            _ => digits
+                .parse()
+                .ok()
+                .filter(|bits| bits % 8 == 0 && (8..=256).contains(bits)),
+        };
+        let ty = match name {
+            "address" => Self::Address,
+            "bool" => Self::Bool,
+            "function" => Self::Function,
+            "bytes" => Self::Bytes,
+            "string" => Self::String,
+            "tuple" => Self::Tuple(components),
+            _ => if let Some(digits) = name.strip_prefix("uint") {
+                bits(digits).map(Self::UInt)
+            } else if let Some(digits) = name.strip_prefix("int") {
+                bits(digits).map(Self::Int)
+            } else if let Some(digits) = name.strip_prefix("bytes") {
+                digits
+                    .parse()
+                    .ok()
+                    .filter(|len| (1..=32).contains(len))
+                    .map(Self::FixedBytes)
+            } else if let Some(digits) = name.strip_prefix("ufixed") {
+                Self::fixed(false, digits, bits)
+            } else if let Some(digits) = name.strip_prefix("fixed") {
+                Self::fixed(true, digits, bits)
+            } else {
+                None
+            }
+            .ok_or_else(|| format!("unsupported ABI type `{name}`"))?,
+        };
+        Ok(ty)
+    }
+
+    /// Parses the `<M>x<N>` of a fixed point type, which defaults to
+    /// `128x18`.
+    fn fixed(signed: bool, digits: &str, bits: impl Fn(&str) -> Option<usize>) -> Option<Self> {
+        let (bits, decimals) = match digits {
+            "" => (128, 18),
+            _ => {
+                let (bits_digits, decimals) = digits.split_once('x')?;
+                (
+                    bits(bits_digits).filter(|_| !bits_digits.is_empty())?,
+                    decimals.parse().ok().filter(|decimals| *decimals <= 80)?,
+                )
+            }
+        };
+        Some(Self::Fixed {
+            signed,
+            bits,
+            decimals,
+        })
+    }
+
     fn serialize_component<S: SerializeMap>(&self, s: &mut S) -> Result<(), S::Error> {
         match self {
             Self::Tuple(entry) => s.serialize_entry("components", entry),
-            Self::Array { elem_ty, .. } => elem_ty.serialize_component(s),
+            Self::Array { elem_ty, .. } | Self::DynArray { elem_ty } => {
+                elem_ty.serialize_component(s)
+            }
             _ => Ok(()),
         }
     }
@@ -141,11 +259,26 @@ impl Serialize for AbiType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+impl<'de> Deserialize<'de> for AbiType {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Repr {
+            #[serde(rename = "type")]
+            name: String,
+            #[serde(default)]
+            components: Vec<AbiTupleField>,
+        }
+
+        let repr = Repr::deserialize(d)?;
+        Self::parse(&repr.name, repr.components).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AbiTupleField {
-    name: String,
+    pub name: String,
     #[serde(flatten)]
-    ty: AbiType,
+    pub ty: AbiType,
 }
 
 impl AbiTupleField {
@@ -161,7 +294,97 @@ impl AbiTupleField {
 mod tests {
     use super::*;
 
-    use serde_test::{assert_ser_tokens, Token};
+    use serde_test::{assert_de_tokens_error, assert_ser_tokens, assert_tokens, Token};
+
+    #[test]
+    fn parse() {
+        let u256_ty = AbiType::UInt(256);
+        assert_eq!(AbiType::parse("uint", vec![]).unwrap(), u256_ty);
+        assert_eq!(AbiType::parse("int16", vec![]).unwrap(), AbiType::Int(16));
+        assert_eq!(
+            AbiType::parse("uint256[2][]", vec![]).unwrap(),
+            AbiType::DynArray {
+                elem_ty: AbiType::Array {
+                    elem_ty: u256_ty.into(),
+                    len: 2,
+                }
+                .into(),
+            }
+        );
+
+        assert_eq!(
+            AbiType::parse("bytes32", vec![]).unwrap(),
+            AbiType::FixedBytes(32)
+        );
+        assert_eq!(
+            AbiType::parse("ufixed", vec![])
+                .unwrap()
+                .selector_type_name(),
+            "ufixed128x18"
+        );
+        assert_eq!(
+            AbiType::parse("fixed64x10", vec![]).unwrap(),
+            AbiType::Fixed {
+                signed: true,
+                bits: 64,
+                decimals: 10,
+            }
+        );
+
+        for name in [
+            "bytes0",
+            "bytes33",
+            "uint7",
+            "fixed7x1",
+            "fixedx18",
+            "fixed128x81",
+            "uint256[x]",
+        ] {
+            assert!(AbiType::parse(name, vec![]).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn deserialize_tuple() {
+        let field1 = AbiTupleField::new("field1".into(), AbiType::Address);
+        let field2 = AbiTupleField::new("field2".into(), AbiType::String);
+        let tuple_ty = AbiType::Tuple(vec![field1, field2]);
+
+        assert_tokens(
+            &tuple_ty,
+            &[
+                Token::Map { len: None },
+                Token::String("type"),
+                Token::String("tuple"),
+                Token::String("components"),
+                Token::Seq { len: Some(2) },
+                Token::Map { len: None },
+                Token::String("name"),
+                Token::String("field1"),
+                Token::String("type"),
+                Token::String("address"),
+                Token::MapEnd,
+                Token::Map { len: None },
+                Token::String("name"),
+                Token::String("field2"),
+                Token::String("type"),
+                Token::String("string"),
+                Token::MapEnd,
+                Token::SeqEnd,
+                Token::MapEnd,
+            ],
+        );
+
+        assert_de_tokens_error::<AbiType>(
+            &[
+                Token::Map { len: None },
+                Token::String("type"),
+                Token::String("bytes33"),
+                Token::MapEnd,
+            ],
+            "unsupported ABI type `bytes33`",
+        );
+    }
 
     #[test]
     fn primitive() {
@@ -196,6 +419,55 @@ mod tests {
         )
     }
 
+    #[test]
+    fn primitive_dyn_array() {
+        let u256_ty = AbiType::UInt(256);
+        let dyn_array_u256 = AbiType::DynArray {
+            elem_ty: u256_ty.into(),
+        };
+        assert_eq!(dyn_array_u256.selector_type_name(), "uint256[]");
+        assert!(!dyn_array_u256.is_static());
+
+        assert_ser_tokens(
+            &dyn_array_u256,
+            &[
+                Token::Map { len: None },
+                Token::String("type"),
+                Token::String("uint256[]"),
+                Token::MapEnd,
+            ],
+        )
+    }
+
+    #[test]
+    fn tuple_dyn_array() {
+        let field1 = AbiTupleField::new("field1".into(), AbiType::Address);
+        let tuple_ty = AbiType::Tuple(vec![field1]);
+        let tuple_dyn_array_ty = AbiType::DynArray {
+            elem_ty: tuple_ty.into(),
+        };
+        assert_eq!(tuple_dyn_array_ty.selector_type_name(), "(address)[]");
+
+        assert_ser_tokens(
+            &tuple_dyn_array_ty,
+            &[
+                Token::Map { len: None },
+                Token::String("type"),
+                Token::String("tuple[]"),
+                Token::String("components"),
+                Token::Seq { len: Some(1) },
+                Token::Map { len: None },
+                Token::String("name"),
+                Token::String("field1"),
+                Token::String("type"),
+                Token::String("address"),
+                Token::MapEnd,
+                Token::SeqEnd,
+                Token::MapEnd,
+            ],
+        )
+    }
+
     #[test]
     fn tuple_array() {
         let u16_ty = AbiType::UInt(16);
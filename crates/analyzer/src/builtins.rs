@@ -1,4 +1,4 @@
-use crate::namespace::types::Base;
+use crate::namespace::types::{Base, StateMutability};
 use strum::{AsRefStr, EnumIter, EnumString};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumString, AsRefStr)]
@@ -16,6 +16,29 @@ pub enum GlobalFunction {
     Keccak256,
 }
 
+/// The methods of the builtin `Vec` type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum VecMethod {
+    Push,
+    Pop,
+    Len,
+}
+
+impl VecMethod {
+    pub fn arg_count(&self) -> usize {
+        match self {
+            VecMethod::Push => 1,
+            VecMethod::Pop | VecMethod::Len => 0,
+        }
+    }
+
+    /// Returns `true` if the method modifies the vector.
+    pub fn is_mut(&self) -> bool {
+        matches!(self, VecMethod::Push | VecMethod::Pop)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, EnumString, AsRefStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum ContractTypeMethod {
@@ -117,6 +140,27 @@ pub enum Intrinsic {
 }
 
 impl Intrinsic {
+    /// The least permissive state mutability of a function that uses the
+    /// intrinsic.
+    pub fn state_mutability(&self) -> StateMutability {
+        use Intrinsic::*;
+        match self {
+            __sload | __gas | __address | __balance | __selfbalance | __caller | __extcodesize
+            | __extcodecopy | __extcodehash | __staticcall | __chainid | __basefee | __origin
+            | __gasprice | __blockhash | __coinbase | __timestamp | __number | __prevrandao
+            | __gaslimit => StateMutability::View,
+
+            __sstore | __create | __create2 | __call | __callcode | __delegatecall
+            | __selfdestruct | __log0 | __log1 | __log2 | __log3 | __log4 => {
+                StateMutability::Nonpayable
+            }
+
+            __callvalue => StateMutability::Payable,
+
+            _ => StateMutability::Pure,
+        }
+    }
+
     pub fn arg_count(&self) -> usize {
         use Intrinsic::*;
         match self {
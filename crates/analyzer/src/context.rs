@@ -11,7 +11,7 @@ use crate::namespace::items::{
 use crate::namespace::types::{Generic, SelfDecl, Type, TypeId};
 use crate::AnalyzerDb;
 use crate::{
-    builtins::{ContractTypeMethod, GlobalFunction, Intrinsic, ValueMethod},
+    builtins::{ContractTypeMethod, GlobalFunction, Intrinsic, ValueMethod, VecMethod},
     namespace::scopes::BlockScopeType,
 };
 use crate::{
@@ -58,6 +58,11 @@ pub trait AnalyzerContext {
     fn resolve_visible_path(&self, path: &ast::Path) -> Option<NamedThing>;
     /// Resolves the given path. Does not register any errors
     fn resolve_any_path(&self, path: &ast::Path) -> Option<NamedThing>;
+    /// Resolves the name of a generic parameter of the enclosing struct or
+    /// enum to its type.
+    fn resolve_type_param(&self, _name: &str) -> Option<TypeId> {
+        None
+    }
 
     fn add_diagnostic(&self, diag: Diagnostic);
     fn db(&self) -> &dyn AnalyzerDb;
@@ -486,6 +491,11 @@ pub enum CallType {
         method: ValueMethod,
         typ: TypeId,
     },
+    // some_vec.push(x), some_vec.pop(), some_vec.len()
+    BuiltinVecMethod {
+        method: VecMethod,
+        typ: TypeId,
+    },
 
     // create, create2 (will be methods of the context struct soon)
     BuiltinAssociatedFunction {
@@ -529,6 +539,7 @@ impl CallType {
         match self {
             BuiltinFunction(_)
             | BuiltinValueMethod { .. }
+            | BuiltinVecMethod { .. }
             | TypeConstructor(_)
             | EnumConstructor(_)
             | Intrinsic(_)
@@ -546,6 +557,7 @@ impl CallType {
             CallType::BuiltinFunction(f) => f.as_ref().into(),
             CallType::Intrinsic(f) => f.as_ref().into(),
             CallType::BuiltinValueMethod { method, .. } => method.as_ref().into(),
+            CallType::BuiltinVecMethod { method, .. } => method.as_ref().into(),
             CallType::BuiltinAssociatedFunction { function, .. } => function.as_ref().into(),
             CallType::AssociatedFunction { function: id, .. }
             | CallType::ValueMethod { method: id, .. }
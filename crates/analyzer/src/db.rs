@@ -133,6 +133,10 @@ pub trait AnalyzerDb: SourceDb + Upcast<dyn SourceDb> + UpcastMut<dyn SourceDb>
     fn contract_init_function(&self, id: ContractId) -> Analysis<Option<FunctionId>>;
     #[salsa::invoke(queries::contracts::contract_call_function)]
     fn contract_call_function(&self, id: ContractId) -> Analysis<Option<FunctionId>>;
+    #[salsa::invoke(queries::contracts::contract_receive_function)]
+    fn contract_receive_function(&self, id: ContractId) -> Analysis<Option<FunctionId>>;
+    #[salsa::invoke(queries::contracts::contract_fallback_function)]
+    fn contract_fallback_function(&self, id: ContractId) -> Analysis<Option<FunctionId>>;
 
     #[salsa::invoke(queries::contracts::contract_all_fields)]
     fn contract_all_fields(&self, id: ContractId) -> Rc<[ContractFieldId]>;
@@ -143,6 +147,10 @@ pub trait AnalyzerDb: SourceDb + Upcast<dyn SourceDb> + UpcastMut<dyn SourceDb>
     ) -> Analysis<Rc<IndexMap<SmolStr, ContractFieldId>>>;
     #[salsa::invoke(queries::contracts::contract_field_type)]
     fn contract_field_type(&self, field: ContractFieldId) -> Analysis<Result<TypeId, TypeError>>;
+    #[salsa::invoke(queries::contracts::contract_called_functions)]
+    fn contract_called_functions(&self, id: ContractId) -> Analysis<Rc<[FunctionId]>>;
+    #[salsa::invoke(queries::contracts::contract_error_types)]
+    fn contract_error_types(&self, id: ContractId) -> Analysis<Rc<[StructId]>>;
     #[salsa::cycle(queries::contracts::contract_dependency_graph_cycle)]
     #[salsa::invoke(queries::contracts::contract_dependency_graph)]
     fn contract_dependency_graph(&self, id: ContractId) -> DepGraphWrapper;
@@ -155,11 +163,17 @@ pub trait AnalyzerDb: SourceDb + Upcast<dyn SourceDb> + UpcastMut<dyn SourceDb>
     fn function_signature(&self, id: FunctionSigId) -> Analysis<Rc<types::FunctionSignature>>;
     #[salsa::invoke(queries::functions::function_body)]
     fn function_body(&self, id: FunctionId) -> Analysis<Rc<FunctionBody>>;
+    #[salsa::invoke(queries::functions::function_state_mutability)]
+    fn function_state_mutability(&self, id: FunctionId) -> Analysis<types::StateMutability>;
+    #[salsa::invoke(queries::functions::function_expected_revert)]
+    fn function_expected_revert(&self, id: FunctionId) -> Analysis<Option<types::ExpectedRevert>>;
     #[salsa::cycle(queries::functions::function_dependency_graph_cycle)]
     #[salsa::invoke(queries::functions::function_dependency_graph)]
     fn function_dependency_graph(&self, id: FunctionId) -> DepGraphWrapper;
 
     // Struct
+    #[salsa::invoke(queries::structs::struct_type_params)]
+    fn struct_type_params(&self, id: StructId) -> Analysis<Rc<IndexMap<SmolStr, TypeId>>>;
     #[salsa::invoke(queries::structs::struct_all_fields)]
     fn struct_all_fields(&self, id: StructId) -> Rc<[StructFieldId]>;
     #[salsa::invoke(queries::structs::struct_field_map)]
@@ -175,6 +189,8 @@ pub trait AnalyzerDb: SourceDb + Upcast<dyn SourceDb> + UpcastMut<dyn SourceDb>
     fn struct_dependency_graph(&self, id: StructId) -> Analysis<DepGraphWrapper>;
 
     // Enum
+    #[salsa::invoke(queries::enums::enum_type_params)]
+    fn enum_type_params(&self, id: EnumId) -> Analysis<Rc<IndexMap<SmolStr, TypeId>>>;
     #[salsa::invoke(queries::enums::enum_all_variants)]
     fn enum_all_variants(&self, id: EnumId) -> Rc<[EnumVariantId]>;
     #[salsa::invoke(queries::enums::enum_variant_map)]
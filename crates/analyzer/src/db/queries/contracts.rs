@@ -1,16 +1,19 @@
-use crate::context::AnalyzerContext;
+use crate::context::{AnalyzerContext, CallType, FunctionBody};
 use crate::db::{Analysis, AnalyzerDb};
 use crate::errors;
 use crate::namespace::items::{
     self, ContractFieldId, ContractId, DepGraph, DepGraphWrapper, DepLocality, FunctionId, Item,
-    TypeDef,
+    StructId, TypeDef,
 };
 use crate::namespace::scopes::ItemScope;
 use crate::namespace::types::{self, Type};
+use crate::natspec::{self, Documented, NatSpec};
 use crate::traversal::types::type_desc;
 use fe_common::diagnostics::Label;
 use fe_parser::ast;
+use fe_parser::node::Node;
 use indexmap::map::{Entry, IndexMap};
+use indexmap::IndexSet;
 use smol_str::SmolStr;
 use std::rc::Rc;
 
@@ -41,7 +44,38 @@ pub fn contract_function_map(
     for func in db.contract_all_functions(contract).iter() {
         let def = &func.data(db).ast;
         let def_name = def.name();
-        if def_name == "__init__" || def_name == "__call__" {
+        if contract.is_interface(db) {
+            if matches!(def_name, "__init__" | "__call__" | "receive" | "fallback") {
+                scope.fancy_error(
+                    &format!(
+                        "`fn {def_name}()` can't be declared in `interface {}`",
+                        contract.name(db)
+                    ),
+                    vec![Label::primary(func.name_span(db), "not allowed here")],
+                    vec![
+                        "Note: an interface only declares the public functions of a deployed contract."
+                            .into(),
+                    ],
+                );
+                continue;
+            }
+            if !func.takes_self(db) {
+                scope.fancy_error(
+                    &format!(
+                        "functions of `interface {}` must take `self`",
+                        contract.name(db)
+                    ),
+                    vec![Label::primary(
+                        func.name_span(db),
+                        format!("`{def_name}` doesn't take `self`"),
+                    )],
+                    vec![format!(
+                        "Hint: add `self` as the first parameter, like `fn {def_name}(self, ...)`."
+                    )],
+                );
+            }
+        }
+        if matches!(def_name, "__init__" | "__call__" | "receive" | "fallback") {
             continue;
         }
 
@@ -131,6 +165,11 @@ pub fn contract_init_function(
     db: &dyn AnalyzerDb,
     contract: ContractId,
 ) -> Analysis<Option<FunctionId>> {
+    if contract.is_interface(db) {
+        // Reported in `contract_function_map`.
+        return Analysis::new(None, vec![].into());
+    }
+
     let all_fns = db.contract_all_functions(contract);
     let mut init_fns = all_fns.iter().filter_map(|func| {
         let def = &func.data(db).ast;
@@ -183,6 +222,11 @@ pub fn contract_call_function(
     db: &dyn AnalyzerDb,
     contract: ContractId,
 ) -> Analysis<Option<FunctionId>> {
+    if contract.is_interface(db) {
+        // Reported in `contract_function_map`.
+        return Analysis::new(None, vec![].into());
+    }
+
     let all_fns = db.contract_all_functions(contract);
     let mut call_fns = all_fns.iter().filter_map(|func| {
         let def = &func.data(db).ast;
@@ -228,7 +272,12 @@ pub fn contract_call_function(
     if let Some((_id, init_span)) = first_def {
         for func in all_fns.iter() {
             let name = func.name(db);
-            if func.is_public(db) && name != "__init__" && name != "__call__" {
+            if func.is_public(db)
+                && !matches!(
+                    name.as_str(),
+                    "__init__" | "__call__" | "receive" | "fallback"
+                )
+            {
                 diagnostics.push(errors::fancy_error(
                     "`pub` not allowed if `__call__` is defined",
                     vec![
@@ -250,6 +299,120 @@ pub fn contract_call_function(
     }
 }
 
+pub fn contract_receive_function(
+    db: &dyn AnalyzerDb,
+    contract: ContractId,
+) -> Analysis<Option<FunctionId>> {
+    dispatcher_function(db, contract, "receive")
+}
+
+pub fn contract_fallback_function(
+    db: &dyn AnalyzerDb,
+    contract: ContractId,
+) -> Analysis<Option<FunctionId>> {
+    dispatcher_function(db, contract, "fallback")
+}
+
+/// Finds the `receive` or `fallback` function, which the function dispatcher
+/// calls when the calldata is empty or doesn't match any public function.
+fn dispatcher_function(
+    db: &dyn AnalyzerDb,
+    contract: ContractId,
+    name: &str,
+) -> Analysis<Option<FunctionId>> {
+    if contract.is_interface(db) {
+        // Reported in `contract_function_map`.
+        return Analysis::new(None, vec![].into());
+    }
+
+    let scope = ItemScope::new(db, contract.module(db));
+    let all_fns = db.contract_all_functions(contract);
+    let mut defs = all_fns.iter().filter(|func| func.name(db) == name);
+
+    let first_def = defs.next().copied();
+    if let Some(dupe) = defs.next() {
+        let mut labels = vec![
+            Label::primary(
+                first_def.unwrap().data(db).ast.span,
+                format!("`{name}` first defined here"),
+            ),
+            Label::secondary(dupe.data(db).ast.span, format!("`{name}` redefined here")),
+        ];
+        for dupe in defs {
+            labels.push(Label::secondary(
+                dupe.data(db).ast.span,
+                format!("`{name}` redefined here"),
+            ));
+        }
+        scope.fancy_error(
+            &format!(
+                "`fn {name}()` is defined multiple times in `contract {}`",
+                contract.name(db),
+            ),
+            labels,
+            vec![],
+        );
+    }
+
+    if let Some(id) = first_def {
+        // The return type is checked in `queries::functions::function_signature`.
+        if !id.is_public(db) {
+            scope.fancy_error(
+                &format!("`{name}` function is not public"),
+                vec![Label::primary(
+                    id.name_span(db),
+                    format!("`{name}` function must be public"),
+                )],
+                vec![
+                    "Hint: Add the `pub` modifier.".to_string(),
+                    format!("Example: `pub fn {name}()`"),
+                ],
+            );
+        }
+
+        // Only `self` and `ctx` can be passed, since there are no arguments to decode.
+        let context_type = scope.get_context_type();
+        let sig_data = id.sig(db).data(db);
+        let args = sig_data.ast.kind.args.iter();
+        let args = args.filter(|arg| matches!(arg.kind, ast::FunctionArg::Regular { .. }));
+        for (arg, param) in args.zip(id.signature(db).params.iter()) {
+            if let Ok(typ) = param.typ {
+                if Some(typ.deref(db)) != context_type {
+                    scope.fancy_error(
+                        &format!("`{name}` function can't take arguments"),
+                        vec![Label::primary(
+                            arg.span,
+                            format!("`{name}` can only take `self` and `ctx`"),
+                        )],
+                        vec![format!(
+                            "Note: `{name}` is called without any ABI-encoded arguments."
+                        )],
+                    );
+                }
+            }
+        }
+
+        if let Some(call_fn) = all_fns.iter().find(|func| func.name(db) == "__call__") {
+            scope.fancy_error(
+                &format!("`{name}` can't be defined if `__call__` is defined"),
+                vec![
+                    Label::primary(id.name_span(db), format!("`{name}` defined here")),
+                    Label::secondary(call_fn.name_span(db), "`__call__` defined here"),
+                ],
+                vec![
+                    format!("The `__call__` function replaces the default function dispatcher, which is what calls `{name}`."),
+                    format!("Hint: Remove the `{name}` or `__call__` function."),
+                ],
+            );
+        }
+    }
+
+    Analysis {
+        value: first_def,
+        diagnostics: scope.diagnostics.take().into(),
+    }
+}
+
 /// All field ids, including those with duplicate names
 pub fn contract_all_fields(db: &dyn AnalyzerDb, contract: ContractId) -> Rc<[ContractFieldId]> {
     contract
@@ -293,6 +456,18 @@ pub fn contract_field_map(
         }
     }
 
+    let ast = &contract.data(db).ast;
+    if let Some(doc) = &ast.kind.doc {
+        for diag in natspec::check(
+            &NatSpec::parse(doc),
+            Documented::Contract,
+            &contract_name,
+            ast.kind.name.span,
+        ) {
+            scope.add_diagnostic(diag);
+        }
+    }
+
     Analysis {
         value: Rc::new(map),
         diagnostics: scope.diagnostics.take().into(),
@@ -377,7 +552,10 @@ pub fn contract_runtime_dependency_graph(
     let root_fns = if let Some(call_id) = contract.call_function(db) {
         vec![call_id]
     } else {
-        contract.public_functions(db).values().copied().collect()
+        let mut fns: Vec<_> = contract.public_functions(db).values().copied().collect();
+        fns.extend(contract.receive_function(db));
+        fns.extend(contract.fallback_function(db));
+        fns
     }
     .into_iter()
     .map(|fun| (root, Item::Function(fun), DepLocality::Local))
@@ -400,3 +578,139 @@ pub fn contract_runtime_dependency_graph_cycle(
 ) -> DepGraphWrapper {
     DepGraphWrapper(Rc::new(DepGraph::new()))
 }
+
+/// The contract's functions and the functions that they call, directly or
+/// through other functions. Calls to the cheatcodes in `std::test` are
+/// reported, because they only work in tests. Trait methods that are called on
+/// generic types aren't followed.
+pub fn contract_called_functions(
+    db: &dyn AnalyzerDb,
+    contract: ContractId,
+) -> Analysis<Rc<[FunctionId]>> {
+    let mut functions: IndexSet<FunctionId> = contract.all_functions(db).iter().copied().collect();
+    let mut diagnostics = vec![];
+
+    let mut idx = 0;
+    while let Some(&function) = functions.get_index(idx) {
+        idx += 1;
+        let body = function.body(db);
+        for (id, call) in &body.calls {
+            let callee = match call {
+                CallType::Pure(function)
+                | CallType::AssociatedFunction { function, .. }
+                | CallType::ValueMethod {
+                    method: function, ..
+                } => *function,
+                _ => continue,
+            };
+            if callee.is_cheatcode(db) && !function.is_cheatcode(db) {
+                diagnostics.push(errors::fancy_error(
+                    format!("`test::{}` can only be called from a test", callee.name(db)),
+                    vec![Label::primary(
+                        body.spans[id],
+                        format!("called by contract `{}`", contract.name(db)),
+                    )],
+                    vec![
+                        "Note: the functions in `std::test` are cheatcodes that only `fe test` understands."
+                            .into(),
+                    ],
+                ));
+            } else {
+                functions.insert(callee);
+            }
+        }
+    }
+
+    Analysis::new(functions.into_iter().collect(), diagnostics.into())
+}
+
+/// The error structs that the contract's functions, or the functions they
+/// call, revert with. Errors with different signatures that share a selector
+/// are reported, because their revert data couldn't be told apart.
+pub fn contract_error_types(db: &dyn AnalyzerDb, contract: ContractId) -> Analysis<Rc<[StructId]>> {
+    let mut errors = IndexSet::new();
+    for function in contract.called_functions(db).iter() {
+        let body = function.body(db);
+        collect_errors(db, &body, &function.data(db).ast.kind.body, &mut errors);
+    }
+
+    let mut selectors = IndexMap::<[u8; 4], (StructId, String)>::new();
+    let mut diagnostics = vec![];
+    for &error in &errors {
+        // Errors that can't be ABI encoded are reported elsewhere.
+        let selector = match error.abi_error(db) {
+            Some(abi) => abi.selector(),
+            None => continue,
+        };
+        let signature = selector.selector_signature().to_string();
+        match selectors.entry(selector.selector_raw()) {
+            Entry::Vacant(entry) => {
+                entry.insert((error, signature));
+            }
+            // The same signature doesn't make the revert data ambiguous.
+            Entry::Occupied(entry) if entry.get().1 == signature => {}
+            Entry::Occupied(entry) => {
+                let (first, first_signature) = entry.get();
+                diagnostics.push(errors::fancy_error(
+                    format!(
+                        "errors of contract `{}` have the same selector `0x{}`",
+                        contract.name(db),
+                        selector.hex()
+                    ),
+                    vec![
+                        Label::primary(
+                            first.name_span(db),
+                            format!("`{first_signature}` defined here"),
+                        ),
+                        Label::secondary(
+                            error.name_span(db),
+                            format!("`{signature}` defined here"),
+                        ),
+                    ],
+                    vec!["Hint: rename one of the errors or change its fields".into()],
+                ));
+            }
+        }
+    }
+
+    Analysis::new(errors.into_iter().collect(), diagnostics.into())
+}
+
+/// Collects the struct types of the `revert` statements in `stmts`.
+fn collect_errors(
+    db: &dyn AnalyzerDb,
+    body: &FunctionBody,
+    stmts: &[Node<ast::FuncStmt>],
+    errors: &mut IndexSet<StructId>,
+) {
+    for stmt in stmts {
+        match &stmt.kind {
+            ast::FuncStmt::Revert { error: Some(error) } => {
+                if let Some(Type::Struct(id)) = body
+                    .expressions
+                    .get(&error.id)
+                    .map(|attr| attr.typ.deref(db).typ(db))
+                {
+                    errors.insert(id);
+                }
+            }
+            ast::FuncStmt::For { body: stmts, .. }
+            | ast::FuncStmt::While { body: stmts, .. }
+            | ast::FuncStmt::Unsafe(stmts) => collect_errors(db, body, stmts, errors),
+            ast::FuncStmt::If {
+                body: stmts,
+                or_else,
+                ..
+            } => {
+                collect_errors(db, body, stmts, errors);
+                collect_errors(db, body, or_else, errors);
+            }
+            ast::FuncStmt::Match { arms, .. } => {
+                for arm in arms {
+                    collect_errors(db, body, &arm.kind.body, errors);
+                }
+            }
+            _ => {}
+        }
+    }
+}
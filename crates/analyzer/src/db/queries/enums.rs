@@ -15,12 +15,25 @@ use crate::{
             EnumVariantKind, FunctionId, Item, TypeDef,
         },
         scopes::ItemScope,
-        types::Type,
+        types::{Type, TypeId},
     },
-    traversal::types::type_desc,
+    traversal::types::{resolve_type_params, type_desc},
     AnalyzerDb,
 };
 
+pub fn enum_type_params(
+    db: &dyn AnalyzerDb,
+    enum_: EnumId,
+) -> Analysis<Rc<IndexMap<SmolStr, TypeId>>> {
+    let mut scope = ItemScope::new(db, enum_.module(db));
+    let params = resolve_type_params(
+        &mut scope,
+        &enum_.generic_params(db),
+        &enum_.generic_args(db),
+    );
+    Analysis::new(Rc::new(params), scope.diagnostics.take().into())
+}
+
 pub fn enum_all_variants(db: &dyn AnalyzerDb, enum_: EnumId) -> Rc<[EnumVariantId]> {
     enum_
         .data(db)
@@ -73,7 +86,8 @@ pub fn enum_variant_kind(
     variant: EnumVariantId,
 ) -> Analysis<Result<EnumVariantKind, TypeError>> {
     let variant_data = variant.data(db);
-    let mut scope = ItemScope::new(db, variant_data.parent.module(db));
+    let mut scope = ItemScope::new(db, variant_data.parent.module(db))
+        .with_type_params(variant_data.parent.type_params(db));
     let self_ty = Some(variant.parent(db).as_type(db).as_trait_or_type());
     let kind = match &variant_data.ast.kind.kind {
         ast::VariantKind::Unit => Ok(EnumVariantKind::Unit),
@@ -1,20 +1,22 @@
-use crate::context::{AnalyzerContext, CallType, FunctionBody};
+use crate::context::{AdjustmentKind, AnalyzerContext, CallType, FunctionBody, NamedThing};
 use crate::db::{Analysis, AnalyzerDb};
 use crate::display::Displayable;
-use crate::errors::TypeError;
+use crate::errors::{self, TypeError};
 use crate::namespace::items::{
     DepGraph, DepGraphWrapper, DepLocality, FunctionId, FunctionSigId, Item, TypeDef,
 };
 use crate::namespace::scopes::{BlockScope, BlockScopeType, FunctionScope, ItemScope};
-use crate::namespace::types::{self, CtxDecl, Generic, SelfDecl, Type, TypeId};
+use crate::namespace::types::{self, CtxDecl, Generic, SelfDecl, StateMutability, Type, TypeId};
+use crate::natspec::{self, Documented, NatSpec};
 use crate::traversal::functions::traverse_statements;
 use crate::traversal::types::{type_desc, type_desc_to_trait};
 use fe_common::diagnostics::Label;
+use fe_common::{numeric, Span};
 use fe_parser::ast::{self, GenericParameter};
 use fe_parser::node::Node;
 use if_chain::if_chain;
 use smol_str::SmolStr;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 /// Gather context information for a function definition and check for type
@@ -25,8 +27,11 @@ pub fn function_signature(
 ) -> Analysis<Rc<types::FunctionSignature>> {
     let def = &function.data(db).ast;
 
-    let mut scope = ItemScope::new(db, function.module(db));
     let fn_parent = function.parent(db);
+    let mut scope = ItemScope::new(db, function.module(db));
+    if let Item::Type(def) = fn_parent {
+        scope = scope.with_type_params(def.type_params(db));
+    }
 
     let mut self_decl = None;
     let mut ctx_decl = None;
@@ -108,7 +113,8 @@ pub fn function_signature(
             }
             ast::FunctionArg::Regular { mut_, label, name, typ: typedesc } => {
                 let typ = resolve_function_param_type(db, function, &mut scope, typedesc).and_then(|typ| match typ {
-                    typ if typ.has_fixed_size(db) => {
+                    // `Vec` is passed around by reference.
+                    typ if typ.has_fixed_size(db) || typ.is_vec(db) => {
                         if let Some(mut_span) = mut_ {
                             if typ.is_primitive(db) {
                                 Err(TypeError::new(scope.error(
@@ -221,8 +227,13 @@ pub fn function_signature(
         .as_ref()
         .map(|type_node| {
             let fn_name = &function.name(db);
-            if fn_name == "__init__" || fn_name == "__call__" {
-                // `__init__` and `__call__` must not return any type other than `()`.
+            if fn_name == "__init__"
+                || fn_name == "__call__"
+                || function.is_receive(db)
+                || function.is_fallback(db)
+            {
+                // `__init__`, `__call__`, `receive` and `fallback` must not return any
+                // type other than `()`.
                 if type_node.kind != ast::TypeDesc::Unit {
                     scope.fancy_error(
                         &format!("`{fn_name}` function has incorrect return type"),
@@ -241,7 +252,7 @@ pub fn function_signature(
                 };
 
                 match type_desc(&mut scope, type_node, self_ty)? {
-                    typ if typ.has_fixed_size(scope.db()) => Ok(typ),
+                    typ if typ.has_fixed_size(scope.db()) || typ.is_vec(scope.db()) => Ok(typ),
                     _ => Err(TypeError::new(scope.error(
                         "function return type must have a fixed size",
                         type_node.span,
@@ -252,6 +263,31 @@ pub fn function_signature(
         })
         .unwrap_or_else(|| Ok(TypeId::unit(db)));
 
+    check_attributes(db, function, &mut scope);
+
+    if let Some(doc) = &sig_ast.doc {
+        let params = sig_ast
+            .args
+            .iter()
+            .filter_map(|arg| match &arg.kind {
+                ast::FunctionArg::Regular { name, .. } => Some(name.kind.clone()),
+                ast::FunctionArg::Self_ { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        let documented = Documented::Function {
+            params: &params,
+            returns: sig_ast.return_type.is_some(),
+        };
+        for diag in natspec::check(
+            &NatSpec::parse(doc),
+            documented,
+            &sig_ast.name.kind,
+            sig_ast.name.span,
+        ) {
+            scope.add_diagnostic(diag);
+        }
+    }
+
     Analysis {
         value: Rc::new(types::FunctionSignature {
             self_decl,
@@ -263,6 +299,81 @@ pub fn function_signature(
     }
 }
 
+/// Checks the state mutability attributes of a function, like `#view`.
+fn check_attributes(db: &dyn AnalyzerDb, function: FunctionSigId, scope: &mut ItemScope) {
+    let data = function.data(db);
+    let mut declared: Option<&Node<SmolStr>> = None;
+    for attr in &data.ast.kind.attributes {
+        if StateMutability::from_attribute(&attr.kind).is_none() {
+            scope.fancy_error(
+                &format!("unknown function attribute `#{}`", attr.kind),
+                vec![Label::primary(attr.span, "unknown attribute")],
+                vec!["Note: the attributes of a contract function are `#pure`, `#view` and `#payable`".into()],
+            );
+        } else if let Some(previous) = declared {
+            scope.fancy_error(
+                "conflicting state mutability attributes",
+                vec![
+                    Label::primary(attr.span, "conflicting attribute"),
+                    Label::secondary(previous.span, format!("`#{}` declared here", previous.kind)),
+                ],
+                vec![
+                    "Note: a function can only have one of `#pure`, `#view` and `#payable`".into(),
+                ],
+            );
+        } else {
+            declared = Some(attr);
+        }
+    }
+
+    if let Some(attr) = declared {
+        let payable_only = if function.is_constructor(db) {
+            Some((
+                "not allowed on the constructor",
+                "Note: the constructor can only be declared `#payable`",
+            ))
+        } else if function.is_receive(db) {
+            Some((
+                "not allowed on `receive`",
+                "Note: `receive` is always payable",
+            ))
+        } else if function.is_fallback(db) {
+            Some((
+                "not allowed on `fallback`",
+                "Note: `fallback` can only be declared `#payable`",
+            ))
+        } else {
+            None
+        };
+
+        if let Some((label, note)) = payable_only {
+            if StateMutability::from_attribute(&attr.kind) != Some(StateMutability::Payable) {
+                scope.fancy_error(
+                    &format!(
+                        "`#{}` can't be applied to `{}`",
+                        attr.kind,
+                        function.name(db)
+                    ),
+                    vec![Label::primary(attr.span, label)],
+                    vec![note.into()],
+                );
+            }
+        } else if function.pub_span(db).is_none() {
+            scope.fancy_error(
+                &format!("`#{}` can only be applied to public functions", attr.kind),
+                vec![
+                    Label::primary(attr.span, "not allowed here"),
+                    Label::secondary(function.name_span(db), "this function isn't public"),
+                ],
+                vec![
+                    "Hint: add `pub` to make the function callable from outside the contract"
+                        .into(),
+                ],
+            );
+        }
+    }
+}
+
 fn resolve_function_param_type(
     db: &dyn AnalyzerDb,
     function: FunctionSigId,
@@ -299,6 +410,11 @@ fn resolve_function_param_type(
 
 /// Gather context information for a function body and check for type errors.
 pub fn function_body(db: &dyn AnalyzerDb, function: FunctionId) -> Analysis<Rc<FunctionBody>> {
+    // Interface functions are implemented by the contract they are called on.
+    if function.sig(db).is_interface_fn(db) {
+        return Analysis::new(Rc::new(FunctionBody::default()), vec![].into());
+    }
+
     let def = &function.data(db).ast.kind;
     let scope = FunctionScope::new(db, function);
 
@@ -345,6 +461,333 @@ pub fn function_body(db: &dyn AnalyzerDb, function: FunctionId) -> Analysis<Rc<F
     }
 }
 
+/// Infers the state mutability of a function from its body and the functions
+/// it calls, and checks that it doesn't exceed the declared state mutability.
+pub fn function_state_mutability(
+    db: &dyn AnalyzerDb,
+    function: FunctionId,
+) -> Analysis<StateMutability> {
+    let (effects, callees) = state_effects(db, function);
+    let callee_effects = callees.into_iter().map(|(callee, span)| {
+        let mut visited = HashSet::from([function, callee]);
+        (transitive_state_mutability(db, callee, &mut visited), span)
+    });
+    // A function that takes a mutable contract or context promises to modify
+    // the state, even if it doesn't. This comes last, so that an actual
+    // modification is reported as the cause.
+    let mut_decls = mutable_decl_spans(db, function)
+        .into_iter()
+        .map(|span| (StateMutability::Nonpayable, span));
+
+    let mut inferred = StateMutability::Pure;
+    let mut cause = None;
+    for (mutability, span) in effects.into_iter().chain(callee_effects).chain(mut_decls) {
+        if mutability > inferred {
+            inferred = mutability;
+            cause = Some(span);
+        }
+    }
+
+    let mut diagnostics = vec![];
+    let declared = function.sig(db).declared_mutability(db);
+    if let (None, Some(cause)) = (declared, cause) {
+        // A function that's called with ether has to opt in with `#payable`.
+        if inferred == StateMutability::Payable && accepts_payable_attribute(db, function) {
+            diagnostics.push(errors::fancy_error(
+                format!(
+                    "function `{}` reads the value sent with the call, but it isn't `#payable`",
+                    function.name(db),
+                ),
+                vec![Label::primary(
+                    cause,
+                    "this reads the value sent with the call",
+                )],
+                vec!["Hint: add `#payable` on the line before the function to accept ether".into()],
+            ));
+        }
+    }
+    if let (Some((declared, attr_span)), Some(cause)) = (declared, cause) {
+        if inferred > declared {
+            let label = match inferred {
+                StateMutability::Nonpayable
+                    if mutable_decl_spans(db, function).contains(&cause) =>
+                {
+                    "this allows modifying the contract state"
+                }
+                StateMutability::Pure => unreachable!(),
+                StateMutability::View => "this reads the contract state or environment",
+                StateMutability::Nonpayable => "this modifies the contract state",
+                StateMutability::Payable => "this reads the value sent with the call",
+            };
+            diagnostics.push(errors::fancy_error(
+                format!(
+                    "function `{}` is declared `#{}`, but it is `{}`",
+                    function.name(db),
+                    declared.name(),
+                    inferred.name()
+                ),
+                vec![
+                    Label::primary(cause, label),
+                    Label::secondary(attr_span, format!("declared `#{}` here", declared.name())),
+                ],
+                vec![],
+            ));
+        }
+    }
+
+    Analysis::new(inferred, diagnostics.into())
+}
+
+/// `true` if the function is called by a transaction, so it could be
+/// declared `#payable`. `receive` is always payable.
+fn accepts_payable_attribute(db: &dyn AnalyzerDb, function: FunctionId) -> bool {
+    function.is_contract_func(db)
+        && !function.is_receive(db)
+        && (function.is_public(db) || function.is_constructor(db))
+}
+
+fn transitive_state_mutability(
+    db: &dyn AnalyzerDb,
+    function: FunctionId,
+    visited: &mut HashSet<FunctionId>,
+) -> StateMutability {
+    let (effects, callees) = state_effects(db, function);
+    let mut mutability = effects
+        .into_iter()
+        .map(|(mutability, _)| mutability)
+        .max()
+        .unwrap_or(StateMutability::Pure);
+    for (callee, _) in callees {
+        if mutability == StateMutability::Payable {
+            break;
+        }
+        if visited.insert(callee) {
+            mutability = mutability.max(transitive_state_mutability(db, callee, visited));
+        }
+    }
+    mutability
+}
+
+/// The effects of a function body on the chain state, and the functions it
+/// calls, with the spans of the expressions that cause them.
+#[allow(clippy::type_complexity)]
+fn state_effects(
+    db: &dyn AnalyzerDb,
+    function: FunctionId,
+) -> (Vec<(StateMutability, Span)>, Vec<(FunctionId, Span)>) {
+    use StateMutability::*;
+
+    let body = function.body(db);
+    let mut effects = vec![];
+    let mut callees = vec![];
+
+    for (id, attrs) in &body.expressions {
+        if attrs
+            .type_adjustments
+            .iter()
+            .any(|adj| adj.kind == AdjustmentKind::Load)
+        {
+            effects.push((View, body.spans[id]));
+        }
+    }
+
+    for (id, call) in &body.calls {
+        let span = body.spans[id];
+        let mutability = match call {
+            CallType::Intrinsic(intrinsic) => intrinsic.state_mutability(),
+            CallType::BuiltinValueMethod { typ, .. } if typ.is_sptr(db) => View,
+            CallType::BuiltinVecMethod { method, typ } if typ.is_sptr(db) => {
+                if method.is_mut() {
+                    Nonpayable
+                } else {
+                    View
+                }
+            }
+            // Events are emitted through the `Emittable` trait. The
+            // implementation of other trait methods isn't known until the
+            // generic type is, so they're assumed to modify the state too.
+            CallType::External { .. }
+            | CallType::BuiltinAssociatedFunction { .. }
+            | CallType::TraitValueMethod { .. } => Nonpayable,
+            CallType::AssociatedFunction { function, .. }
+            | CallType::ValueMethod {
+                method: function, ..
+            }
+            | CallType::Pure(function) => {
+                callees.push((*function, span));
+                continue;
+            }
+            _ => continue,
+        };
+        effects.push((mutability, span));
+    }
+
+    storage_writes(&body, &function.data(db).ast.kind.body, db, &mut effects);
+    (effects, callees)
+}
+
+/// The spans of the `mut self` parameter of a contract function and of a
+/// `mut ctx` parameter.
+fn mutable_decl_spans(db: &dyn AnalyzerDb, function: FunctionId) -> Vec<Span> {
+    let sig = function.signature(db);
+    let mut_self = sig
+        .self_decl
+        .filter(|_| function.is_contract_func(db))
+        .and_then(|decl| Some(decl.mut_? + decl.span));
+    let mut_ctx = sig
+        .ctx_decl
+        .filter(|decl| decl.mut_.is_some())
+        .map(|decl| decl.span);
+    mut_self.into_iter().chain(mut_ctx).collect()
+}
+
+/// Collects the assignments to contract storage in a block of statements.
+fn storage_writes(
+    body: &FunctionBody,
+    block: &[Node<ast::FuncStmt>],
+    db: &dyn AnalyzerDb,
+    effects: &mut Vec<(StateMutability, Span)>,
+) {
+    for stmt in block {
+        match &stmt.kind {
+            ast::FuncStmt::Assign { target, .. } | ast::FuncStmt::AugAssign { target, .. }
+                if body
+                    .expressions
+                    .get(&target.id)
+                    .is_some_and(|attrs| attrs.typ.is_sptr(db)) =>
+            {
+                effects.push((StateMutability::Nonpayable, target.span));
+            }
+            ast::FuncStmt::For { body: block, .. }
+            | ast::FuncStmt::While { body: block, .. }
+            | ast::FuncStmt::Unsafe(block) => storage_writes(body, block, db, effects),
+            ast::FuncStmt::If {
+                body: block,
+                or_else,
+                ..
+            } => {
+                storage_writes(body, block, db, effects);
+                storage_writes(body, or_else, db, effects);
+            }
+            ast::FuncStmt::Match { arms, .. } => {
+                for arm in arms {
+                    storage_writes(body, &arm.kind.body, db, effects);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The revert expected by a test function, from the arguments of its `#test`
+/// attribute.
+pub fn function_expected_revert(
+    db: &dyn AnalyzerDb,
+    function: FunctionId,
+) -> Analysis<Option<types::ExpectedRevert>> {
+    let scope = ItemScope::new(db, function.module(db));
+    let args = Item::Function(function)
+        .attributes(db)
+        .into_iter()
+        .find(|attr| attr.name(db) == "test")
+        .and_then(|attr| attr.args(db));
+    let value = args.and_then(|args| expected_revert(&scope, &args));
+
+    Analysis {
+        value,
+        diagnostics: scope.diagnostics.take().into(),
+    }
+}
+
+const SHOULD_REVERT_NOTE: &str = "Note: `#test` takes `should_revert`, optionally with an error struct or a panic code, like `#test(should_revert: panic(0x01))`";
+
+fn expected_revert(
+    scope: &ItemScope,
+    args: &Node<Vec<Node<ast::CallArg>>>,
+) -> Option<types::ExpectedRevert> {
+    if args.kind.len() != 1 {
+        scope.fancy_error(
+            "`#test` takes a single argument",
+            vec![Label::primary(args.span, "expected `should_revert`")],
+            vec![SHOULD_REVERT_NOTE.into()],
+        );
+        return None;
+    }
+
+    let arg = &args.kind[0].kind;
+    match (&arg.label, &arg.value.kind) {
+        (None, ast::Expr::Name(name)) if name == "should_revert" => {
+            Some(types::ExpectedRevert::Any)
+        }
+        (Some(label), _) if label.kind == "should_revert" => {
+            expected_revert_payload(scope, &arg.value)
+        }
+        _ => {
+            scope.fancy_error(
+                "unknown `#test` argument",
+                vec![Label::primary(
+                    args.kind[0].span,
+                    "expected `should_revert`",
+                )],
+                vec![SHOULD_REVERT_NOTE.into()],
+            );
+            None
+        }
+    }
+}
+
+fn expected_revert_payload(
+    scope: &ItemScope,
+    payload: &Node<ast::Expr>,
+) -> Option<types::ExpectedRevert> {
+    let named_thing = match &payload.kind {
+        ast::Expr::Name(name) => scope.resolve_name(name, payload.span).ok().flatten(),
+        // An unresolved path is already reported.
+        ast::Expr::Path(path) => Some(scope.resolve_path(path, payload.span).ok()?),
+        ast::Expr::Call { func, args, .. } if func.kind == ast::Expr::Name("panic".into()) => {
+            return expected_panic(scope, args);
+        }
+        _ => None,
+    };
+
+    if let Some(NamedThing::Item(Item::Type(TypeDef::Struct(id)))) = named_thing {
+        Some(types::ExpectedRevert::Error(id))
+    } else {
+        scope.fancy_error(
+            "invalid expected revert",
+            vec![Label::primary(
+                payload.span,
+                "expected an error struct or `panic(code)`",
+            )],
+            vec![SHOULD_REVERT_NOTE.into()],
+        );
+        None
+    }
+}
+
+fn expected_panic(
+    scope: &ItemScope,
+    args: &Node<Vec<Node<ast::CallArg>>>,
+) -> Option<types::ExpectedRevert> {
+    if let [arg] = args.kind.as_slice() {
+        if let (None, ast::Expr::Num(num)) = (&arg.kind.label, &arg.kind.value.kind) {
+            if let Ok(code) = numeric::Literal::new(num).parse::<u64>() {
+                return Some(types::ExpectedRevert::Panic(code));
+            }
+        }
+    }
+
+    scope.fancy_error(
+        "invalid panic code",
+        vec![Label::primary(
+            args.span,
+            "expected a number, like `(0x01)`",
+        )],
+        vec!["Note: the codes are those of the Solidity `Panic(uint256)` error".into()],
+    );
+    None
+}
+
 fn all_paths_return_or_revert(block: &[Node<ast::FuncStmt>]) -> bool {
     for statement in block.iter().rev() {
         match &statement.kind {
@@ -463,7 +906,8 @@ pub fn function_dependency_graph(db: &dyn AnalyzerDb, function: FunctionId) -> D
             // Builtin functions aren't part of the dependency graph yet.
             CallType::BuiltinFunction(_)
             | CallType::Intrinsic(_)
-            | CallType::BuiltinValueMethod { .. } => {}
+            | CallType::BuiltinValueMethod { .. }
+            | CallType::BuiltinVecMethod { .. } => {}
         }
     }
 
@@ -74,18 +74,29 @@ pub fn module_all_items(db: &dyn AnalyzerDb, module: ModuleId) -> Rc<[Item]> {
                     name: node.name().into(),
                     ast: node.clone(),
                     module,
+                    is_interface: false,
+                })),
+            ))),
+            ast::ModuleStmt::Interface(node) => Some(Item::Type(TypeDef::Contract(
+                db.intern_contract(Rc::new(Contract {
+                    name: node.name().into(),
+                    ast: interface_as_contract(node),
+                    module,
+                    is_interface: true,
                 })),
             ))),
             ast::ModuleStmt::Struct(node) => Some(Item::Type(TypeDef::Struct(db.intern_struct(
                 Rc::new(Struct {
                     ast: node.clone(),
                     module,
+                    generic_args: Rc::new([]),
                 }),
             )))),
             ast::ModuleStmt::Enum(node) => {
                 Some(Item::Type(TypeDef::Enum(db.intern_enum(Rc::new(Enum {
                     ast: node.clone(),
                     module,
+                    generic_args: Rc::new([]),
                 })))))
             }
             ast::ModuleStmt::Constant(node) => Some(Item::Constant(db.intern_module_const(
@@ -325,6 +336,40 @@ pub fn module_impl_map(
     )
 }
 
+/// An interface is analyzed as a contract without fields, whose functions
+/// don't have a body.
+fn interface_as_contract(node: &Node<ast::Interface>) -> Node<ast::Contract> {
+    let ast::Interface {
+        name,
+        functions,
+        pub_qual,
+        doc,
+    } = &node.kind;
+    let body = functions
+        .iter()
+        .map(|sig| {
+            ast::ContractStmt::Function(Node::new(
+                ast::Function {
+                    sig: sig.clone(),
+                    body: vec![],
+                },
+                sig.span,
+            ))
+        })
+        .collect();
+
+    Node::new(
+        ast::Contract {
+            name: name.clone(),
+            fields: vec![],
+            body,
+            pub_qual: *pub_qual,
+            doc: doc.clone(),
+        },
+        node.span,
+    )
+}
+
 pub fn module_contracts(db: &dyn AnalyzerDb, module: ModuleId) -> Rc<[ContractId]> {
     module
         .all_items(db)
@@ -540,27 +585,56 @@ pub fn module_used_item_map(
         })
         .collect::<IndexMap<_, _>>();
 
-    // Add `use std::prelude::*` to every module not in std
+    // Add `use std::prelude::*` to every module not in std, along with the items
+    // that the prelude imports itself. Items defined in the module shadow the
+    // prelude.
     if !module.is_in_std(db) {
-        let prelude_items = resolve_use_tree(
-            db,
-            module,
-            &Node::new(
-                ast::UseTree::Glob {
-                    prefix: ast::Path {
-                        segments: vec![
-                            Node::new("std".into(), Span::dummy()),
-                            Node::new("prelude".into(), Span::dummy()),
-                        ],
+        let prelude_path = ast::Path {
+            segments: vec![
+                Node::new("std".into(), Span::dummy()),
+                Node::new("prelude".into(), Span::dummy()),
+            ],
+        };
+        let mut prelude_items = Rc::try_unwrap(
+            resolve_use_tree(
+                db,
+                module,
+                &Node::new(
+                    ast::UseTree::Glob {
+                        prefix: prelude_path.clone(),
                     },
-                },
-                Span::dummy(),
-            ),
-            true,
+                    Span::dummy(),
+                ),
+                true,
+            )
+            .value,
         )
-        .value;
+        .unwrap();
+
+        if let Some(NamedThing::Item(Item::Module(prelude))) = module
+            .resolve_path_non_used_internal(db, &prelude_path)
+            .value
+        {
+            prelude_items.extend(
+                prelude
+                    .used_items(db)
+                    .iter()
+                    .map(|(name, (_, item))| (name.clone(), (Span::dummy(), *item))),
+            );
+        }
 
-        items.extend(Rc::try_unwrap(prelude_items).unwrap().into_iter());
+        // Items defined or explicitly imported in the module shadow the
+        // prelude.
+        let defined_names = module
+            .all_items(db)
+            .iter()
+            .map(|item| item.name(db))
+            .collect::<Vec<_>>();
+        let prelude_items = prelude_items
+            .into_iter()
+            .filter(|(name, _)| !defined_names.contains(name) && !items.contains_key(name))
+            .collect::<Vec<_>>();
+        items.extend(prelude_items);
     }
 
     Analysis::new(Rc::new(items), diagnostics.into())
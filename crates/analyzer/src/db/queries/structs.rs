@@ -9,7 +9,8 @@ use crate::namespace::items::{
 };
 use crate::namespace::scopes::ItemScope;
 use crate::namespace::types::{Type, TypeId};
-use crate::traversal::types::type_desc;
+use crate::natspec::{self, Documented, NatSpec};
+use crate::traversal::types::{resolve_type_params, type_desc};
 use crate::AnalyzerDb;
 use fe_common::utils::humanize::pluralize_conditionally;
 use fe_parser::{ast, Label};
@@ -18,6 +19,19 @@ use smol_str::SmolStr;
 use std::rc::Rc;
 use std::str::FromStr;
 
+pub fn struct_type_params(
+    db: &dyn AnalyzerDb,
+    struct_: StructId,
+) -> Analysis<Rc<IndexMap<SmolStr, TypeId>>> {
+    let mut scope = ItemScope::new(db, struct_.module(db));
+    let params = resolve_type_params(
+        &mut scope,
+        &struct_.generic_params(db),
+        &struct_.generic_args(db),
+    );
+    Analysis::new(Rc::new(params), scope.diagnostics.take().into())
+}
+
 pub fn struct_all_fields(db: &dyn AnalyzerDb, struct_: StructId) -> Rc<[StructFieldId]> {
     struct_
         .data(db)
@@ -103,6 +117,19 @@ pub fn struct_field_map(
         );
     }
 
+    let ast = &struct_.data(db).ast;
+    if let Some(doc) = &ast.kind.doc {
+        let names = fields.keys().cloned().collect::<Vec<_>>();
+        for diag in natspec::check(
+            &NatSpec::parse(doc),
+            Documented::Struct { fields: &names },
+            &struct_name,
+            ast.kind.name.span,
+        ) {
+            scope.add_diagnostic(diag);
+        }
+    }
+
     Analysis::new(Rc::new(fields), scope.diagnostics.take().into())
 }
 
@@ -111,7 +138,8 @@ pub fn struct_field_type(
     field: StructFieldId,
 ) -> Analysis<Result<TypeId, TypeError>> {
     let field_data = field.data(db);
-    let mut scope = ItemScope::new(db, field_data.parent.module(db));
+    let mut scope = ItemScope::new(db, field_data.parent.module(db))
+        .with_type_params(field_data.parent.type_params(db));
 
     let ast::Field {
         attributes: _,
@@ -120,6 +148,7 @@ pub fn struct_field_type(
         name: _,
         typ,
         value,
+        doc: _,
     } = &field_data.ast.kind;
 
     if *is_const {
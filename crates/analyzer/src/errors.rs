@@ -182,6 +182,19 @@ pub fn error(message: impl Into<String>, label_span: Span, label: impl Into<Stri
     fancy_error(message, vec![Label::primary(label_span, label)], vec![])
 }
 
+pub fn warning(
+    message: impl Into<String>,
+    label_span: Span,
+    label: impl Into<String>,
+) -> Diagnostic {
+    Diagnostic {
+        severity: Severity::Warning,
+        message: message.into(),
+        labels: vec![Label::primary(label_span, label)],
+        notes: vec![],
+    }
+}
+
 pub fn fancy_error(
     message: impl Into<String>,
     labels: Vec<Label>,
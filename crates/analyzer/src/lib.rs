@@ -11,6 +11,7 @@ pub mod db;
 pub mod display;
 pub mod errors;
 pub mod namespace;
+pub mod natspec;
 
 mod operations;
 mod traversal;
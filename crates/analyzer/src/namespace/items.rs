@@ -6,6 +6,8 @@ use crate::namespace::types::{self, GenericType, Type, TypeId};
 use crate::traversal::pragma::check_pragma_version;
 use crate::AnalyzerDb;
 use crate::{builtins, errors::ConstEvalError};
+use fe_abi::error::{AbiError, AbiErrorField};
+use fe_abi::types::AbiType;
 use fe_common::diagnostics::Diagnostic;
 use fe_common::diagnostics::Label;
 use fe_common::files::{common_prefix, Utf8Path};
@@ -13,7 +15,7 @@ use fe_common::{impl_intern_key, FileKind, SourceFileId};
 use fe_parser::ast::GenericParameter;
 use fe_parser::node::{Node, Span};
 use fe_parser::{ast, node::NodeId};
-use indexmap::{indexmap, IndexMap};
+use indexmap::{indexmap, IndexMap, IndexSet};
 use smallvec::SmallVec;
 use smol_str::SmolStr;
 use std::rc::Rc;
@@ -61,6 +63,23 @@ impl Item {
         }
     }
 
+    /// The `///` doc comment of the item, if it has one.
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        match self {
+            Item::Type(id) => id.doc(db),
+            Item::Trait(id) => id.doc(db),
+            Item::Impl(id) => id.doc(db),
+            Item::Function(id) => id.doc(db),
+            Item::Constant(id) => id.doc(db),
+            Item::GenericType(_)
+            | Item::BuiltinFunction(_)
+            | Item::Intrinsic(_)
+            | Item::Ingot(_)
+            | Item::Module(_)
+            | Item::Attribute(_) => None,
+        }
+    }
+
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Option<Span> {
         match self {
             Item::Type(id) => id.name_span(db),
@@ -440,13 +459,21 @@ impl IngotId {
         }
     }
 
+    /// Sinks the diagnostics of every ingot this ingot depends on, directly
+    /// or transitively. Each ingot's diagnostics are only reported once.
     pub fn sink_external_ingot_diagnostics(
         &self,
         db: &dyn AnalyzerDb,
         sink: &mut impl DiagnosticSink,
     ) {
-        for ingot in self.external_ingots(db).values() {
-            ingot.sink_diagnostics(db, sink)
+        let mut visited = IndexSet::<IngotId>::default();
+        let mut stack: Vec<_> = self.external_ingots(db).values().rev().copied().collect();
+        while let Some(ingot) = stack.pop() {
+            if ingot == *self || !visited.insert(ingot) {
+                continue;
+            }
+            ingot.sink_diagnostics(db, sink);
+            stack.extend(ingot.external_ingots(db).values().rev().copied());
         }
     }
 }
@@ -813,6 +840,9 @@ impl ModuleConstantId {
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.kind.name.kind.clone()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
 
     pub fn constant_value(&self, db: &dyn AnalyzerDb) -> Result<Constant, ConstEvalError> {
         db.module_constant_value(*self).value
@@ -886,6 +916,16 @@ impl TypeDef {
         }
     }
 
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        match self {
+            TypeDef::Alias(id) => id.doc(db),
+            TypeDef::Struct(id) => id.doc(db),
+            TypeDef::Enum(id) => id.doc(db),
+            TypeDef::Contract(id) => id.doc(db),
+            TypeDef::Primitive(_) => None,
+        }
+    }
+
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Option<Span> {
         match self {
             TypeDef::Alias(id) => Some(id.name_span(db)),
@@ -910,6 +950,16 @@ impl TypeDef {
         Ok(db.intern_type(self.typ(db)?))
     }
 
+    /// The generic parameters of a struct or enum, see
+    /// [`StructId::type_params`].
+    pub fn type_params(&self, db: &dyn AnalyzerDb) -> Rc<IndexMap<SmolStr, TypeId>> {
+        match self {
+            TypeDef::Struct(id) => id.type_params(db),
+            TypeDef::Enum(id) => id.type_params(db),
+            _ => Rc::new(IndexMap::new()),
+        }
+    }
+
     pub fn is_public(&self, db: &dyn AnalyzerDb) -> bool {
         match self {
             Self::Alias(id) => id.is_public(db),
@@ -961,6 +1011,9 @@ impl TypeAliasId {
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.name().into()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.kind.name.span
     }
@@ -986,6 +1039,9 @@ pub struct Contract {
     pub name: SmolStr,
     pub ast: Node<ast::Contract>,
     pub module: ModuleId,
+    /// Whether this is an `interface`, which only declares the functions of
+    /// contracts deployed elsewhere.
+    pub is_interface: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
@@ -1004,12 +1060,18 @@ impl ContractId {
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.name().into()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn is_public(&self, db: &dyn AnalyzerDb) -> bool {
         self.data(db).ast.kind.pub_qual.is_some()
     }
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.kind.name.span
     }
+    pub fn is_interface(&self, db: &dyn AnalyzerDb) -> bool {
+        self.data(db).is_interface
+    }
 
     pub fn module(&self, db: &dyn AnalyzerDb) -> ModuleId {
         self.data(db).module
@@ -1053,22 +1115,34 @@ impl ContractId {
         db.contract_call_function(*self).value
     }
 
+    /// The function called by the dispatcher when the calldata is empty.
+    pub fn receive_function(&self, db: &dyn AnalyzerDb) -> Option<FunctionId> {
+        db.contract_receive_function(*self).value
+    }
+
+    /// The function called by the dispatcher when the calldata doesn't match
+    /// a public function, or is empty and there's no `receive` function.
+    pub fn fallback_function(&self, db: &dyn AnalyzerDb) -> Option<FunctionId> {
+        db.contract_fallback_function(*self).value
+    }
+
     pub fn all_functions(&self, db: &dyn AnalyzerDb) -> Rc<[FunctionId]> {
         db.contract_all_functions(*self)
     }
 
-    /// User functions, public and not. Excludes `__init__` and `__call__`.
+    /// User functions, public and not. Excludes `__init__`, `__call__`,
+    /// `receive` and `fallback`.
     pub fn functions(&self, db: &dyn AnalyzerDb) -> Rc<IndexMap<SmolStr, FunctionId>> {
         db.contract_function_map(*self).value
     }
 
     /// Lookup a function by name. Searches all user functions, private or not.
-    /// Excludes `__init__` and `__call__`.
+    /// Excludes `__init__`, `__call__`, `receive` and `fallback`.
     pub fn function(&self, db: &dyn AnalyzerDb, name: &str) -> Option<FunctionId> {
         self.functions(db).get(name).copied()
     }
 
-    /// Excludes `__init__` and `__call__`.
+    /// Excludes `__init__`, `__call__`, `receive` and `fallback`.
     pub fn public_functions(&self, db: &dyn AnalyzerDb) -> Rc<IndexMap<SmolStr, FunctionId>> {
         db.contract_public_function_map(*self)
     }
@@ -1091,6 +1165,18 @@ impl ContractId {
         db.contract_runtime_dependency_graph(*self).0
     }
 
+    /// The contract's functions and the functions that they call, directly or
+    /// indirectly.
+    pub fn called_functions(&self, db: &dyn AnalyzerDb) -> Rc<[FunctionId]> {
+        db.contract_called_functions(*self).value
+    }
+
+    /// The error structs that the contract's functions, and the functions
+    /// they call, revert with.
+    pub fn error_types(&self, db: &dyn AnalyzerDb) -> Rc<[StructId]> {
+        db.contract_error_types(*self).value
+    }
+
     pub fn sink_diagnostics(&self, db: &dyn AnalyzerDb, sink: &mut impl DiagnosticSink) {
         // fields
         db.contract_field_map(*self).sink_diagnostics(sink);
@@ -1101,10 +1187,18 @@ impl ContractId {
         // functions
         db.contract_init_function(*self).sink_diagnostics(sink);
         db.contract_call_function(*self).sink_diagnostics(sink);
+        db.contract_receive_function(*self).sink_diagnostics(sink);
+        db.contract_fallback_function(*self).sink_diagnostics(sink);
         db.contract_function_map(*self).sink_diagnostics(sink);
         db.contract_all_functions(*self)
             .iter()
             .for_each(|id| id.sink_diagnostics(db, sink));
+
+        // cheatcodes that are called by the contract
+        sink.push_all(db.contract_called_functions(*self).diagnostics.iter());
+
+        // errors with the same selector
+        sink.push_all(db.contract_error_types(*self).diagnostics.iter());
     }
 }
 
@@ -1121,6 +1215,9 @@ impl ContractFieldId {
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.name().into()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn data(&self, db: &dyn AnalyzerDb) -> Rc<ContractField> {
         db.lookup_intern_contract_field(*self)
     }
@@ -1170,11 +1267,17 @@ impl FunctionSigId {
     }
 
     pub fn is_public(&self, db: &dyn AnalyzerDb) -> bool {
-        self.is_trait_fn(db) || self.is_impl_fn(db) || self.pub_span(db).is_some()
+        self.is_trait_fn(db)
+            || self.is_impl_fn(db)
+            || self.is_interface_fn(db)
+            || self.pub_span(db).is_some()
     }
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.kind.name.kind.clone()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.kind.name.span
     }
@@ -1184,10 +1287,31 @@ impl FunctionSigId {
     pub fn is_constructor(&self, db: &dyn AnalyzerDb) -> bool {
         self.name(db) == "__init__"
     }
+    /// `true` for the `receive` function of a contract, which is called for
+    /// plain ether transfers.
+    pub fn is_receive(&self, db: &dyn AnalyzerDb) -> bool {
+        self.is_contract_func(db) && self.name(db) == "receive"
+    }
+    /// `true` for the `fallback` function of a contract, which is called when
+    /// the calldata doesn't match a public function.
+    pub fn is_fallback(&self, db: &dyn AnalyzerDb) -> bool {
+        self.is_contract_func(db) && self.name(db) == "fallback"
+    }
     pub fn pub_span(&self, db: &dyn AnalyzerDb) -> Option<Span> {
         self.data(db).ast.kind.pub_
     }
 
+    /// The state mutability declared with an attribute like `#view`, and the
+    /// span of the attribute.
+    pub fn declared_mutability(
+        &self,
+        db: &dyn AnalyzerDb,
+    ) -> Option<(types::StateMutability, Span)> {
+        self.data(db).ast.kind.attributes.iter().find_map(|attr| {
+            types::StateMutability::from_attribute(&attr.kind).map(|value| (value, attr.span))
+        })
+    }
+
     pub fn self_item(&self, db: &dyn AnalyzerDb) -> Option<Item> {
         let data = self.data(db);
         data.parent
@@ -1213,6 +1337,10 @@ impl FunctionSigId {
         matches!(self.parent(db), Item::Trait(_))
     }
 
+    pub fn is_interface_fn(&self, db: &dyn AnalyzerDb) -> bool {
+        matches!(self.parent(db), Item::Type(TypeDef::Contract(id)) if id.is_interface(db))
+    }
+
     pub fn is_module_fn(&self, db: &dyn AnalyzerDb) -> bool {
         matches!(self.parent(db), Item::Module(_))
     }
@@ -1291,6 +1419,9 @@ impl FunctionId {
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.sig(db).name(db)
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.sig(db).doc(db)
+    }
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.sig(db).name_span(db)
     }
@@ -1319,6 +1450,12 @@ impl FunctionId {
     pub fn is_constructor(&self, db: &dyn AnalyzerDb) -> bool {
         self.sig(db).is_constructor(db)
     }
+    pub fn is_receive(&self, db: &dyn AnalyzerDb) -> bool {
+        self.sig(db).is_receive(db)
+    }
+    pub fn is_fallback(&self, db: &dyn AnalyzerDb) -> bool {
+        self.sig(db).is_fallback(db)
+    }
     pub fn is_unsafe(&self, db: &dyn AnalyzerDb) -> bool {
         self.unsafe_span(db).is_some()
     }
@@ -1337,9 +1474,33 @@ impl FunctionId {
     pub fn dependency_graph(&self, db: &dyn AnalyzerDb) -> Rc<DepGraph> {
         db.function_dependency_graph(*self).0
     }
+    /// The declared state mutability of the function, or the inferred one if
+    /// it has no attribute. The `receive` function is always payable.
+    pub fn state_mutability(&self, db: &dyn AnalyzerDb) -> types::StateMutability {
+        if self.is_receive(db) {
+            return types::StateMutability::Payable;
+        }
+        self.sig(db).declared_mutability(db).map_or_else(
+            || db.function_state_mutability(*self).value,
+            |(value, _)| value,
+        )
+    }
+    /// `true` if the function accepts ether. Functions have to be declared
+    /// `#payable`, except `receive`, which is always payable.
+    pub fn is_payable(&self, db: &dyn AnalyzerDb) -> bool {
+        self.is_receive(db)
+            || matches!(
+                self.sig(db).declared_mutability(db),
+                Some((types::StateMutability::Payable, _))
+            )
+    }
     pub fn sink_diagnostics(&self, db: &dyn AnalyzerDb, sink: &mut impl DiagnosticSink) {
         sink.push_all(db.function_signature(self.data(db).sig).diagnostics.iter());
         sink.push_all(db.function_body(*self).diagnostics.iter());
+        sink.push_all(db.function_state_mutability(*self).diagnostics.iter());
+        if self.is_test(db) {
+            sink.push_all(db.function_expected_revert(*self).diagnostics.iter());
+        }
     }
     pub fn is_contract_func(self, db: &dyn AnalyzerDb) -> bool {
         self.sig(db).is_contract_func(db)
@@ -1351,6 +1512,19 @@ impl FunctionId {
             .iter()
             .any(|attribute| attribute.name(db) == "test")
     }
+
+    /// Whether this is one of the functions in `std::test`, which are
+    /// cheatcodes that only `fe test` understands.
+    pub fn is_cheatcode(&self, db: &dyn AnalyzerDb) -> bool {
+        let module = self.module(db);
+        module.is_in_std(db) && module.name(db) == "test" && self.is_public(db)
+    }
+
+    /// The revert expected by a `#test(should_revert)` function. `None` if the
+    /// test is expected to succeed.
+    pub fn expected_revert(&self, db: &dyn AnalyzerDb) -> Option<types::ExpectedRevert> {
+        db.function_expected_revert(*self).value
+    }
 }
 
 trait FunctionsAsItems {
@@ -1394,6 +1568,9 @@ impl FunctionsAsItems for ContractId {
 pub struct Struct {
     pub ast: Node<ast::Struct>,
     pub module: ModuleId,
+    /// The types that replace the generic parameters in an instance of a
+    /// generic struct. Empty for the generic definition itself.
+    pub generic_args: Rc<[TypeId]>,
 }
 
 #[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
@@ -1409,6 +1586,9 @@ impl StructId {
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.name().into()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.kind.name.span
     }
@@ -1425,6 +1605,56 @@ impl StructId {
         db.intern_type(Type::Struct(*self))
     }
 
+    /// The ABI of the struct used as an error, or `None` if it can't be
+    /// encoded.
+    pub fn abi_error(&self, db: &dyn AnalyzerDb) -> Option<AbiError> {
+        let fields = match self.as_type(db).abi_type(db)? {
+            AbiType::Tuple(fields) => fields,
+            _ => unreachable!(),
+        };
+        let fields = fields
+            .into_iter()
+            .map(|field| AbiErrorField::new(field.name, field.ty))
+            .collect();
+        Some(AbiError::new(self.name(db).to_string(), fields))
+    }
+
+    pub fn generic_params(&self, db: &dyn AnalyzerDb) -> Vec<GenericParameter> {
+        self.data(db).ast.kind.generic_params.kind.clone()
+    }
+
+    pub fn generic_args(&self, db: &dyn AnalyzerDb) -> Rc<[TypeId]> {
+        self.data(db).generic_args.clone()
+    }
+
+    /// Returns `true` for the definition of a generic struct, as opposed to
+    /// its instances.
+    pub fn is_generic(&self, db: &dyn AnalyzerDb) -> bool {
+        !self.data(db).ast.kind.generic_params.kind.is_empty() && self.generic_args(db).is_empty()
+    }
+
+    /// The generic definition of an instance of a generic struct.
+    pub fn definition(&self, db: &dyn AnalyzerDb) -> StructId {
+        self.instantiate(db, &[])
+    }
+
+    /// The instance of the generic struct with the given generic arguments.
+    pub fn instantiate(&self, db: &dyn AnalyzerDb, args: &[TypeId]) -> StructId {
+        let data = self.data(db);
+        db.intern_struct(Rc::new(Struct {
+            ast: data.ast.clone(),
+            module: data.module,
+            generic_args: args.into(),
+        }))
+    }
+
+    /// Maps the names of the generic parameters to their types, which are
+    /// the generic arguments in an instance and `Type::Generic`s in the
+    /// generic definition.
+    pub fn type_params(&self, db: &dyn AnalyzerDb) -> Rc<IndexMap<SmolStr, TypeId>> {
+        db.struct_type_params(*self).value
+    }
+
     pub fn has_private_field(&self, db: &dyn AnalyzerDb) -> bool {
         self.fields(db).values().any(|field| !field.is_public(db))
     }
@@ -1454,6 +1684,7 @@ impl StructId {
         db.struct_dependency_graph(*self).value.0
     }
     pub fn sink_diagnostics(&self, db: &dyn AnalyzerDb, sink: &mut impl DiagnosticSink) {
+        sink.push_all(db.struct_type_params(*self).diagnostics.iter());
         sink.push_all(db.struct_field_map(*self).diagnostics.iter());
         sink.push_all(db.struct_dependency_graph(*self).diagnostics.iter());
 
@@ -1481,6 +1712,9 @@ impl StructFieldId {
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.name().into()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn span(&self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.span
     }
@@ -1515,7 +1749,7 @@ impl StructFieldId {
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Attribute {
-    pub ast: Node<SmolStr>,
+    pub ast: Node<ast::Attribute>,
     pub module: ModuleId,
 }
 #[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
@@ -1530,7 +1764,12 @@ impl AttributeId {
         self.data(db).ast.span
     }
     pub fn name(self, db: &dyn AnalyzerDb) -> SmolStr {
-        self.data(db).ast.kind.to_owned()
+        self.data(db).ast.kind.name.to_owned()
+    }
+
+    /// The arguments in parentheses after the name, like in `#test(should_revert)`.
+    pub fn args(self, db: &dyn AnalyzerDb) -> Option<Node<Vec<Node<ast::CallArg>>>> {
+        self.data(db).ast.kind.args.clone()
     }
 
     pub fn module(self, db: &dyn AnalyzerDb) -> ModuleId {
@@ -1546,6 +1785,9 @@ impl AttributeId {
 pub struct Enum {
     pub ast: Node<ast::Enum>,
     pub module: ModuleId,
+    /// The types that replace the generic parameters in an instance of a
+    /// generic enum. Empty for the generic definition itself.
+    pub generic_args: Rc<[TypeId]>,
 }
 #[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub struct EnumId(pub(crate) u32);
@@ -1561,6 +1803,9 @@ impl EnumId {
     pub fn name(self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.name().into()
     }
+    pub fn doc(self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn name_span(self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.kind.name.span
     }
@@ -1569,6 +1814,42 @@ impl EnumId {
         db.intern_type(Type::Enum(self))
     }
 
+    pub fn generic_params(self, db: &dyn AnalyzerDb) -> Vec<GenericParameter> {
+        self.data(db).ast.kind.generic_params.kind.clone()
+    }
+
+    pub fn generic_args(self, db: &dyn AnalyzerDb) -> Rc<[TypeId]> {
+        self.data(db).generic_args.clone()
+    }
+
+    /// Returns `true` for the definition of a generic enum, as opposed to its
+    /// instances.
+    pub fn is_generic(self, db: &dyn AnalyzerDb) -> bool {
+        !self.data(db).ast.kind.generic_params.kind.is_empty() && self.generic_args(db).is_empty()
+    }
+
+    /// The generic definition of an instance of a generic enum.
+    pub fn definition(self, db: &dyn AnalyzerDb) -> EnumId {
+        self.instantiate(db, &[])
+    }
+
+    /// The instance of the generic enum with the given generic arguments.
+    pub fn instantiate(self, db: &dyn AnalyzerDb, args: &[TypeId]) -> EnumId {
+        let data = self.data(db);
+        db.intern_enum(Rc::new(Enum {
+            ast: data.ast.clone(),
+            module: data.module,
+            generic_args: args.into(),
+        }))
+    }
+
+    /// Maps the names of the generic parameters to their types, which are
+    /// the generic arguments in an instance and `Type::Generic`s in the
+    /// generic definition.
+    pub fn type_params(self, db: &dyn AnalyzerDb) -> Rc<IndexMap<SmolStr, TypeId>> {
+        db.enum_type_params(self).value
+    }
+
     pub fn is_public(self, db: &dyn AnalyzerDb) -> bool {
         self.data(db).ast.kind.pub_qual.is_some()
     }
@@ -1585,6 +1866,12 @@ impl EnumId {
         self.data(db).module
     }
 
+    /// Returns `true` if this is the enum called `name` in `std`, or an
+    /// instance of it.
+    pub fn is_std_enum(self, db: &dyn AnalyzerDb, name: &str) -> bool {
+        self.module(db).is_in_std(db) && self.name(db) == name
+    }
+
     pub fn parent(self, db: &dyn AnalyzerDb) -> Item {
         Item::Module(self.data(db).module)
     }
@@ -1606,6 +1893,7 @@ impl EnumId {
     }
 
     pub fn sink_diagnostics(self, db: &dyn AnalyzerDb, sink: &mut impl DiagnosticSink) {
+        sink.push_all(db.enum_type_params(self).diagnostics.iter());
         sink.push_all(db.enum_variant_map(self).diagnostics.iter());
         sink.push_all(db.enum_dependency_graph(self).diagnostics.iter());
 
@@ -1637,6 +1925,9 @@ impl EnumVariantId {
     pub fn name(self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.name().into()
     }
+    pub fn doc(self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn span(self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.span
     }
@@ -1660,6 +1951,19 @@ impl EnumVariantId {
     pub fn parent(self, db: &dyn AnalyzerDb) -> EnumId {
         self.data(db).parent
     }
+
+    /// The same variant of `ty`, if `ty` is an instance of the generic enum
+    /// this variant is defined in.
+    pub fn in_instance(self, db: &dyn AnalyzerDb, ty: TypeId) -> Option<EnumVariantId> {
+        match ty.deref_typ(db) {
+            Type::Enum(instance)
+                if instance != self.parent(db) && instance.definition(db) == self.parent(db) =>
+            {
+                instance.variant(db, &self.name(db))
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -1784,6 +2088,9 @@ impl ImplId {
         )
         .into()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
 
     fn validate_type_or_trait_is_in_ingot(
         &self,
@@ -1819,6 +2126,7 @@ impl ImplId {
         match &self.data(db).receiver.typ(db) {
             Type::Contract(_)
             | Type::Map(_)
+            | Type::Vec(_)
             | Type::SelfContract(_)
             | Type::Generic(_)
             | Type::SelfType(_) => sink.push(&errors::fancy_error(
@@ -2018,6 +2326,9 @@ impl TraitId {
     pub fn name(&self, db: &dyn AnalyzerDb) -> SmolStr {
         self.data(db).ast.name().into()
     }
+    pub fn doc(&self, db: &dyn AnalyzerDb) -> Option<SmolStr> {
+        self.data(db).ast.kind.doc.clone()
+    }
     pub fn name_span(&self, db: &dyn AnalyzerDb) -> Span {
         self.data(db).ast.kind.name.span
     }
@@ -2039,6 +2350,11 @@ impl TraitId {
             return true;
         }
 
+        // A generic type implements the traits it's bound by.
+        if let Type::Generic(generic) = ty.typ(db) {
+            return generic.bounds.contains(self);
+        }
+
         db.all_impls(ty).iter().any(|val| &val.trait_id(db) == self)
     }
 
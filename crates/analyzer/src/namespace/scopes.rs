@@ -14,12 +14,15 @@ use fe_common::Span;
 use fe_parser::{ast, node::NodeId, Label};
 use fe_parser::{ast::Expr, node::Node};
 use indexmap::IndexMap;
+use smol_str::SmolStr;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
 pub struct ItemScope<'a> {
     db: &'a dyn AnalyzerDb,
     module: ModuleId,
+    type_params: Rc<IndexMap<SmolStr, TypeId>>,
     expressions: RefCell<IndexMap<NodeId, ExpressionAttributes>>,
     pub diagnostics: RefCell<Vec<Diagnostic>>,
 }
@@ -28,10 +31,18 @@ impl<'a> ItemScope<'a> {
         Self {
             db,
             module,
+            type_params: Rc::new(IndexMap::new()),
             expressions: RefCell::new(IndexMap::default()),
             diagnostics: RefCell::new(vec![]),
         }
     }
+
+    /// Makes the generic parameters of a struct or enum resolvable in the
+    /// scope.
+    pub fn with_type_params(mut self, type_params: Rc<IndexMap<SmolStr, TypeId>>) -> Self {
+        self.type_params = type_params;
+        self
+    }
 }
 
 impl<'a> AnalyzerContext for ItemScope<'a> {
@@ -110,6 +121,10 @@ impl<'a> AnalyzerContext for ItemScope<'a> {
         false
     }
 
+    fn resolve_type_param(&self, name: &str) -> Option<TypeId> {
+        self.type_params.get(name).copied()
+    }
+
     fn resolve_name(&self, name: &str, span: Span) -> Result<Option<NamedThing>, IncompleteItem> {
         let resolved = self.module.resolve_name(self.db, name)?;
 
@@ -317,6 +332,13 @@ impl<'a> AnalyzerContext for FunctionScope<'a> {
         false
     }
 
+    fn resolve_type_param(&self, name: &str) -> Option<TypeId> {
+        match self.function.parent(self.db) {
+            Item::Type(def) => def.type_params(self.db).get(name).copied(),
+            _ => None,
+        }
+    }
+
     fn resolve_name(&self, name: &str, span: Span) -> Result<Option<NamedThing>, IncompleteItem> {
         let sig = self.function.signature(self.db);
 
@@ -462,6 +484,10 @@ impl AnalyzerContext for BlockScope<'_, '_> {
         self.root.db
     }
 
+    fn resolve_type_param(&self, name: &str) -> Option<TypeId> {
+        self.root.resolve_type_param(name)
+    }
+
     fn resolve_name(&self, name: &str, span: Span) -> Result<Option<NamedThing>, IncompleteItem> {
         if let Some(var) =
             self.variable_defs
@@ -7,8 +7,10 @@ use crate::namespace::items::{
 };
 use crate::AnalyzerDb;
 
+use fe_abi::types::{AbiTupleField, AbiType};
 use fe_common::impl_intern_key;
 use fe_common::Span;
+use indexmap::IndexMap;
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 use smol_str::SmolStr;
@@ -17,6 +19,10 @@ use std::rc::Rc;
 use std::str::FromStr;
 use strum::{AsRefStr, EnumIter, EnumString};
 
+fn is_zero_sized_abi(ty: &AbiType) -> bool {
+    matches!(ty, AbiType::Tuple(fields) if fields.is_empty())
+}
+
 pub fn u256_min() -> BigInt {
     BigInt::from(0)
 }
@@ -48,6 +54,8 @@ pub enum Type {
     Base(Base),
     Array(Array),
     Map(Map),
+    /// A dynamically sized vector of elements in memory.
+    Vec(TypeId),
     Tuple(Tuple),
     String(FeString),
     /// An "external" contract. Effectively just a `newtype`d address.
@@ -135,6 +143,9 @@ impl TypeId {
     pub fn is_map(&self, db: &dyn AnalyzerDb) -> bool {
         matches!(self.typ(db), Type::Map(_))
     }
+    pub fn is_vec(&self, db: &dyn AnalyzerDb) -> bool {
+        matches!(self.typ(db), Type::Vec(_))
+    }
     pub fn is_string(&self, db: &dyn AnalyzerDb) -> bool {
         matches!(self.typ(db), Type::String(_))
     }
@@ -178,6 +189,7 @@ impl TypeId {
             Type::Contract(_) | Type::SelfContract(_) => "contract",
             Type::Struct(_) => "struct",
             Type::Array(_) => "array",
+            Type::Vec(_) => "vector",
             Type::Tuple(_) => "tuple",
             _ => "type",
         }
@@ -265,6 +277,7 @@ impl TypeId {
         match self.typ(db) {
             Type::Base(_) | Type::String(_) | Type::Contract(_) => Ok(true),
             Type::Array(arr) => arr.inner.is_encodable(db),
+            Type::Vec(inner) => inner.is_encodable(db),
             Type::Struct(sid) => {
                 // Returns `false` if diagnostics is not empty.
                 // The diagnostics is properly emitted in struct definition site, so there is no
@@ -303,6 +316,63 @@ impl TypeId {
             | Type::SPtr(_) => Ok(false),
         }
     }
+
+    /// The ABI type that a value of this type is encoded as, or `None` if it
+    /// can't be encoded. Zero-sized types are an empty tuple and are left out
+    /// of the tuples and structs that contain them, like in the generated code.
+    pub fn abi_type(self, db: &dyn AnalyzerDb) -> Option<AbiType> {
+        let fields = |items: Vec<(String, TypeId)>| {
+            let mut fields = vec![];
+            for (name, item) in items {
+                let ty = item.abi_type(db)?;
+                if !is_zero_sized_abi(&ty) {
+                    fields.push(AbiTupleField::new(name, ty));
+                }
+            }
+            Some(AbiType::Tuple(fields))
+        };
+
+        Some(match self.typ(db) {
+            Type::Base(Base::Numeric(int)) if int.is_signed() => AbiType::Int(int.bits()),
+            Type::Base(Base::Numeric(int)) => AbiType::UInt(int.bits()),
+            Type::Base(Base::Bool) => AbiType::Bool,
+            Type::Base(Base::Address) | Type::Contract(_) => AbiType::Address,
+            Type::Base(Base::Unit) => AbiType::Tuple(vec![]),
+            Type::Array(array) => {
+                let elem_ty = array.inner.abi_type(db)?;
+                if array.size == 0 || is_zero_sized_abi(&elem_ty) {
+                    AbiType::Tuple(vec![])
+                } else if array.inner.typ(db) == Type::u8() {
+                    AbiType::Bytes
+                } else {
+                    AbiType::Array {
+                        elem_ty: elem_ty.into(),
+                        len: array.size,
+                    }
+                }
+            }
+            Type::Vec(inner) => AbiType::DynArray {
+                elem_ty: inner.abi_type(db)?.into(),
+            },
+            Type::String(_) => AbiType::String,
+            Type::Tuple(tuple) => fields(
+                tuple
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, item)| (idx.to_string(), *item))
+                    .collect(),
+            )?,
+            Type::Struct(id) => fields(
+                id.fields(db)
+                    .iter()
+                    .map(|(name, field)| Some((name.to_string(), field.typ(db).ok()?)))
+                    .collect::<Option<_>>()?,
+            )?,
+            Type::SPtr(inner) | Type::Mut(inner) => inner.abi_type(db)?,
+            _ => return None,
+        })
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -384,6 +454,53 @@ pub struct FunctionSignature {
     pub return_type: Result<TypeId, TypeError>,
 }
 
+/// How a contract function interacts with the chain state, from the least to
+/// the most permissive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StateMutability {
+    /// Doesn't read the contract state or the environment.
+    Pure,
+    /// Reads the contract state or the environment, but doesn't modify them.
+    View,
+    /// May modify the contract state, emit events or call other contracts.
+    Nonpayable,
+    /// May also read the value sent with the call.
+    Payable,
+}
+
+/// The revert expected by a test function declared with `#test(should_revert)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExpectedRevert {
+    /// Any revert, with any data.
+    Any,
+    /// A revert with the given error struct, whatever the values of its fields.
+    Error(StructId),
+    /// A `Panic(uint256)` revert with the given code, like `0x01` for a failed
+    /// `assert`.
+    Panic(u64),
+}
+
+impl StateMutability {
+    /// The state mutability declared by a function attribute like `#view`.
+    pub fn from_attribute(name: &str) -> Option<Self> {
+        match name {
+            "pure" => Some(Self::Pure),
+            "view" => Some(Self::View),
+            "payable" => Some(Self::Payable),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pure => "pure",
+            Self::View => "view",
+            Self::Nonpayable => "nonpayable",
+            Self::Payable => "payable",
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SelfDecl {
     pub span: Span,
@@ -432,6 +549,7 @@ pub enum GenericType {
     Array,
     String,
     Map,
+    Vec,
 }
 
 impl GenericType {
@@ -464,6 +582,10 @@ impl GenericType {
                     kind: GenericParamKind::Int,
                 },
             ],
+            GenericType::Vec => vec![GenericParam {
+                name: "element type".into(),
+                kind: GenericParamKind::FixedSizeType,
+            }],
         }
     }
 
@@ -490,6 +612,10 @@ impl GenericType {
                 })),
                 _ => None,
             },
+            GenericType::Vec => match args {
+                [GenericArg::Type(element)] => Some(Type::Vec(*element)),
+                _ => None,
+            },
         }?;
         Some(db.intern_type(typ))
     }
@@ -506,7 +632,7 @@ pub enum GenericParamKind {
 
     // Ideally these would be represented as trait constraints.
     PrimitiveType,
-    // FixedSizeType, // not needed yet
+    FixedSizeType,
     AnyType,
 }
 
@@ -670,7 +796,7 @@ impl Type {
             | Type::Enum(_)
             | Type::Generic(_)
             | Type::Contract(_) => true,
-            Type::Map(_) | Type::SelfContract(_) => false,
+            Type::Map(_) | Type::Vec(_) | Type::SelfContract(_) => false,
             Type::SelfType(inner) => match inner {
                 TraitOrType::TraitId(_) => true,
                 TraitOrType::TypeId(id) => id.has_fixed_size(db),
@@ -740,6 +866,7 @@ impl DisplayWithDb for Type {
                 let Map { key, value } = map;
                 write!(f, "Map<{}, {}>", key.display(db), value.display(db),)
             }
+            Type::Vec(inner) => write!(f, "Vec<{}>", inner.display(db)),
             Type::Tuple(id) => {
                 write!(f, "(")?;
                 let mut delim = "";
@@ -750,8 +877,14 @@ impl DisplayWithDb for Type {
                 write!(f, ")")
             }
             Type::Contract(id) | Type::SelfContract(id) => write!(f, "{}", id.name(db)),
-            Type::Struct(id) => write!(f, "{}", id.name(db)),
-            Type::Enum(id) => write!(f, "{}", id.name(db)),
+            Type::Struct(id) => {
+                write!(f, "{}", id.name(db))?;
+                write_type_params(db, f, &id.type_params(db))
+            }
+            Type::Enum(id) => {
+                write!(f, "{}", id.name(db))?;
+                write_type_params(db, f, &id.type_params(db))
+            }
             Type::Generic(inner) => inner.fmt(f),
             Type::SPtr(inner) => write!(f, "SPtr<{}>", inner.display(db)),
             Type::Mut(inner) => write!(f, "mut {}", inner.display(db)),
@@ -759,6 +892,24 @@ impl DisplayWithDb for Type {
         }
     }
 }
+/// Writes the `<...>` type parameters of a generic struct or enum.
+fn write_type_params(
+    db: &dyn AnalyzerDb,
+    f: &mut fmt::Formatter<'_>,
+    params: &IndexMap<SmolStr, TypeId>,
+) -> fmt::Result {
+    if params.is_empty() {
+        return Ok(());
+    }
+    write!(f, "<")?;
+    let mut delim = "";
+    for typ in params.values() {
+        write!(f, "{}{}", delim, typ.display(db))?;
+        delim = ", ";
+    }
+    write!(f, ">")
+}
+
 impl DisplayWithDb for TypeId {
     fn format(&self, db: &dyn AnalyzerDb, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.typ(db).format(db, f)
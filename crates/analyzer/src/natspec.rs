@@ -0,0 +1,168 @@
+//! NatSpec tags in doc comments.
+//!
+//! Doc comments of contracts, contract functions and event structs may use
+//! the tags of Solidity's [NatSpec format](https://docs.soliditylang.org/en/latest/natspec-format.html).
+//! Text before the first tag is the `@notice`, and lines that don't start
+//! with a tag continue the previous one.
+
+use crate::errors;
+use fe_common::diagnostics::Diagnostic;
+use fe_common::Span;
+use smol_str::SmolStr;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NatSpec {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub notice: Option<String>,
+    pub dev: Option<String>,
+    /// Documented parameters (or event fields) and their descriptions.
+    pub params: Vec<(SmolStr, String)>,
+    pub returns: Vec<String>,
+    /// `@custom:<name>` tags, keyed by `<name>`.
+    pub custom: Vec<(SmolStr, String)>,
+    /// Tags that aren't part of NatSpec, without the `@`.
+    pub unknown_tags: Vec<SmolStr>,
+}
+
+enum Tag {
+    Title,
+    Author,
+    Notice,
+    Dev,
+    Param(SmolStr),
+    Return,
+    Custom(SmolStr),
+    Unknown(SmolStr),
+}
+
+impl NatSpec {
+    pub fn parse(doc: &str) -> Self {
+        let mut tags: Vec<(Tag, String)> = vec![];
+        for line in doc.lines() {
+            let trimmed = line.trim();
+            let tag_name = trimmed
+                .strip_prefix('@')
+                .map(|rest| rest.split_whitespace().next().unwrap_or_default())
+                .filter(|name| !name.is_empty());
+
+            let (tag, text) = match tag_name {
+                Some(name) => {
+                    let rest = trimmed[name.len() + 1..].trim_start();
+                    match name {
+                        "title" => (Tag::Title, rest),
+                        "author" => (Tag::Author, rest),
+                        "notice" => (Tag::Notice, rest),
+                        "dev" => (Tag::Dev, rest),
+                        "return" => (Tag::Return, rest),
+                        "param" => {
+                            let param = rest.split_whitespace().next().unwrap_or_default();
+                            (Tag::Param(param.into()), rest[param.len()..].trim_start())
+                        }
+                        _ => match name.strip_prefix("custom:") {
+                            Some(custom) if !custom.is_empty() => {
+                                (Tag::Custom(custom.into()), rest)
+                            }
+                            _ => (Tag::Unknown(name.into()), rest),
+                        },
+                    }
+                }
+                None => {
+                    match tags.last_mut() {
+                        Some((_, content)) => {
+                            content.push('\n');
+                            content.push_str(trimmed);
+                        }
+                        None => tags.push((Tag::Notice, trimmed.to_string())),
+                    }
+                    continue;
+                }
+            };
+            tags.push((tag, text.to_string()));
+        }
+
+        let mut natspec = NatSpec::default();
+        for (tag, content) in tags {
+            let content = content.trim().to_string();
+            let append = |field: &mut Option<String>| match field {
+                Some(text) => {
+                    text.push('\n');
+                    text.push_str(&content);
+                }
+                None => *field = Some(content.clone()),
+            };
+            match tag {
+                Tag::Title => append(&mut natspec.title),
+                Tag::Author => append(&mut natspec.author),
+                Tag::Notice if content.is_empty() => {}
+                Tag::Notice => append(&mut natspec.notice),
+                Tag::Dev => append(&mut natspec.dev),
+                Tag::Param(name) => natspec.params.push((name, content)),
+                Tag::Return => natspec.returns.push(content),
+                Tag::Custom(name) => natspec.custom.push((name, content)),
+                Tag::Unknown(name) => natspec.unknown_tags.push(name),
+            }
+        }
+        natspec
+    }
+}
+
+/// The kind of item a doc comment belongs to.
+pub(crate) enum Documented<'a> {
+    Contract,
+    Function {
+        params: &'a [SmolStr],
+        returns: bool,
+    },
+    /// A struct, which is documented the same way whether it's emitted as an
+    /// event or used as an error.
+    Struct {
+        fields: &'a [SmolStr],
+    },
+}
+
+/// Checks the NatSpec tags of the doc comment of the item called `name`.
+/// Mistakes are reported as warnings on the item's name.
+pub(crate) fn check(
+    natspec: &NatSpec,
+    documented: Documented,
+    name: &str,
+    name_span: Span,
+) -> Vec<Diagnostic> {
+    let mut diags = vec![];
+    for tag in &natspec.unknown_tags {
+        let mut diag = errors::warning(
+            format!("unknown NatSpec tag `@{tag}`"),
+            name_span,
+            format!("in the doc comment of `{name}`"),
+        );
+        diag.notes
+            .push("Hint: Custom tags must start with `@custom:`".to_string());
+        diags.push(diag);
+    }
+
+    for (param, _) in &natspec.params {
+        let (names, kind) = match documented {
+            Documented::Function { params, .. } => (params, "parameter"),
+            Documented::Struct { fields } => (fields, "field"),
+            Documented::Contract => (&[][..], "parameter"),
+        };
+        if !names.contains(param) {
+            diags.push(errors::warning(
+                format!("`@param` documents a non-existent {kind} `{param}`"),
+                name_span,
+                format!("`{name}` has no {kind} named `{param}`"),
+            ));
+        }
+    }
+
+    let returns = matches!(documented, Documented::Function { returns: true, .. });
+    if !natspec.returns.is_empty() && !returns {
+        diags.push(errors::warning(
+            "`@return` documents a non-existent return value",
+            name_span,
+            format!("`{name}` doesn't return a value"),
+        ));
+    }
+    diags
+}
@@ -17,6 +17,7 @@ pub fn index(
     match value.typ(context.db()) {
         Type::Array(array) => index_array(context, &array, indext, index_expr),
         Type::Map(map) => index_map(context, &map, indext, index_expr),
+        Type::Vec(inner) => index_vec(context, inner, indext, index_expr),
         Type::SPtr(inner) => {
             Ok(Type::SPtr(index(context, inner, indext, index_expr)?).id(context.db()))
         }
@@ -40,7 +41,7 @@ pub fn index(
 
 pub fn expected_index_type(context: &mut dyn AnalyzerContext, obj: TypeId) -> Option<TypeId> {
     match obj.typ(context.db()) {
-        Type::Array(_) => Some(Type::u256().id(context.db())),
+        Type::Array(_) | Type::Vec(_) => Some(Type::u256().id(context.db())),
         Type::Map(Map { key, .. }) => Some(key),
         Type::SPtr(inner) | Type::Mut(inner) => expected_index_type(context, inner),
         Type::SelfType(inner) => match inner {
@@ -72,6 +73,20 @@ fn index_array(
     Ok(array.inner)
 }
 
+fn index_vec(
+    context: &mut dyn AnalyzerContext,
+    inner: TypeId,
+    index: TypeId,
+    index_expr: &Node<fe::Expr>,
+) -> Result<TypeId, IndexingError> {
+    let u256 = Type::u256().id(context.db());
+    if try_coerce_type(context, Some(index_expr), index, u256, false).is_err() {
+        return Err(IndexingError::WrongIndexType);
+    }
+
+    Ok(inner)
+}
+
 fn index_map(
     context: &mut dyn AnalyzerContext,
     map: &Map,
@@ -58,6 +58,7 @@ pub(crate) fn eval_expr(
         | ast::Expr::Path(_)
         | ast::Expr::Attribute { .. }
         | ast::Expr::Call { .. }
+        | ast::Expr::Try { .. }
         | ast::Expr::List { .. }
         | ast::Expr::Repeat { .. }
         | ast::Expr::Tuple { .. }
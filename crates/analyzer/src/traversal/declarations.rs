@@ -59,7 +59,7 @@ pub fn var_decl(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(),
         }
     } else if matches!(
         declared_type.typ(scope.db()),
-        Type::Array(_) | Type::Struct(_) | Type::Tuple(_)
+        Type::Array(_) | Type::Vec(_) | Type::Struct(_) | Type::Tuple(_)
     ) {
         scope.error(
             "uninitialized variable",
@@ -1,22 +1,26 @@
 use super::borrowck;
-use crate::builtins::{ContractTypeMethod, GlobalFunction, Intrinsic, ValueMethod};
+use crate::builtins::{ContractTypeMethod, GlobalFunction, Intrinsic, ValueMethod, VecMethod};
 use crate::context::{AnalyzerContext, CallType, Constant, ExpressionAttributes, NamedThing};
 use crate::display::Displayable;
 use crate::errors::{self, FatalError, IndexingError, TypeCoercionError};
 use crate::namespace::items::{
-    EnumVariantId, EnumVariantKind, FunctionId, FunctionSigId, ImplId, Item, StructId, TypeDef,
+    ContractId, EnumVariantId, EnumVariantKind, FunctionId, FunctionSigId, ImplId, Item, StructId,
+    TypeDef,
 };
 use crate::namespace::scopes::{check_visibility, BlockScopeType};
 use crate::namespace::types::{
-    self, Array, Base, FeString, Integer, TraitOrType, Tuple, Type, TypeDowncast, TypeId,
+    self, Array, Base, FeString, FunctionParam, Integer, TraitOrType, Tuple, Type, TypeDowncast,
+    TypeId,
 };
 use crate::operations;
 use crate::traversal::call_args::{validate_arg_count, validate_named_args};
 use crate::traversal::const_expr::eval_expr;
 use crate::traversal::types::{
-    apply_generic_type_args, deref_type, try_cast_type, try_coerce_type,
+    apply_generic_type_args, apply_type_def_generic_args, deref_type, try_cast_type,
+    try_coerce_type,
 };
 use crate::traversal::utils::add_bin_operations_errors;
+use crate::AnalyzerDb;
 
 use fe_common::diagnostics::Label;
 use fe_common::{numeric, Span};
@@ -90,6 +94,7 @@ pub fn expr(
         fe::Expr::Num(_) => Ok(expr_num(context, exp, expected)),
 
         fe::Expr::Subscript { .. } => expr_subscript(context, exp, expected),
+        fe::Expr::Try { value } => expr_try(context, exp, value),
         fe::Expr::Attribute { .. } => expr_attribute(context, exp, expected),
         fe::Expr::Ternary { .. } => expr_ternary(context, exp, expected),
         fe::Expr::BoolOperation { .. } => expr_bool_operation(context, exp),
@@ -416,6 +421,7 @@ fn expr_named_thing(
             Ok(typ)
         }
         Some(NamedThing::EnumVariant(variant)) => {
+            let variant = instantiate_variant(context, variant, expected_type, exp.span)?;
             if let Ok(EnumVariantKind::Tuple(_)) = variant.kind(context.db()) {
                 let name = variant.name_with_parent(context.db());
                 context.fancy_error(
@@ -623,6 +629,93 @@ fn expr_subscript(
     unreachable!()
 }
 
+/// Analyzes `value?`. `value` must be an `Option` or a `Result`, and the
+/// enclosing function must return the same kind of enum so that `None` or
+/// `Err` can be returned early. The type of the expression is the type of
+/// the `Some` or `Ok` value.
+fn expr_try(
+    context: &mut dyn AnalyzerContext,
+    exp: &Node<fe::Expr>,
+    value: &Node<fe::Expr>,
+) -> Result<ExpressionAttributes, FatalError> {
+    let value_ty = value_expr_type(context, value, None)?;
+    let enum_ = match value_ty.typ(context.db()) {
+        Type::Enum(id)
+            if (id.is_std_enum(context.db(), "Option")
+                || id.is_std_enum(context.db(), "Result"))
+                && !id.generic_args(context.db()).is_empty() =>
+        {
+            id
+        }
+        _ => {
+            return Err(FatalError::new(context.fancy_error(
+                &format!(
+                    "the `?` operator can't be applied to type `{}`",
+                    value_ty.display(context.db())
+                ),
+                vec![Label::primary(
+                    value.span,
+                    "expected an `Option` or a `Result`",
+                )],
+                vec![],
+            )));
+        }
+    };
+
+    if !context.is_in_function() {
+        return Err(FatalError::new(context.error(
+            "the `?` operator can only be used in a function",
+            exp.span,
+            "",
+        )));
+    }
+
+    let args = enum_.generic_args(context.db());
+    let is_option = enum_.is_std_enum(context.db(), "Option");
+    let early_return = if is_option {
+        "`Option::None`".to_string()
+    } else {
+        format!("`Result::Err` of type `{}`", args[1].display(context.db()))
+    };
+
+    let return_ty = context
+        .parent_function()
+        .signature(context.db())
+        .return_type
+        .clone()?
+        .deref(context.db());
+    let compatible = match return_ty.typ(context.db()) {
+        Type::Enum(ret) if ret.is_std_enum(context.db(), "Option") => is_option,
+        Type::Enum(ret) if ret.is_std_enum(context.db(), "Result") => {
+            !is_option && ret.generic_args(context.db()).get(1) == args.get(1)
+        }
+        _ => false,
+    };
+    if !compatible {
+        let expected = if is_option {
+            "Option<..>".to_string()
+        } else {
+            format!("Result<.., {}>", args[1].display(context.db()))
+        };
+        context.fancy_error(
+            &format!(
+                "the `?` operator can't be used in a function that returns `{}`",
+                return_ty.display(context.db())
+            ),
+            vec![Label::primary(
+                exp.span,
+                format!("this returns {early_return} from the function"),
+            )],
+            vec![format!(
+                "Hint: the function must return `{expected}` to use `?` on `{}`",
+                value_ty.display(context.db())
+            )],
+        );
+    }
+
+    Ok(ExpressionAttributes::new(args[0]))
+}
+
 fn expr_attribute(
     context: &mut dyn AnalyzerContext,
     exp: &Node<fe::Expr>,
@@ -875,8 +968,12 @@ fn expr_call(
     expected_type: Option<TypeId>,
 ) -> Result<ExpressionAttributes, FatalError> {
     let (attributes, call_type) = match &func.kind {
-        fe::Expr::Name(name) => expr_call_name(context, name, func, generic_args, args)?,
-        fe::Expr::Path(path) => expr_call_path(context, path, func, generic_args, args)?,
+        fe::Expr::Name(name) => {
+            expr_call_name(context, name, func, generic_args, args, expected_type)?
+        }
+        fe::Expr::Path(path) => {
+            expr_call_path(context, path, func, generic_args, args, expected_type)?
+        }
         fe::Expr::Attribute { value, attr } => {
             // TODO: err if there are generic args
             expr_call_method(context, value, attr, generic_args, args)?
@@ -940,6 +1037,7 @@ fn expr_call_name<T: std::fmt::Display>(
     func: &Node<T>,
     generic_args: &Option<Node<Vec<fe::GenericArg>>>,
     args: &Node<Vec<Node<fe::CallArg>>>,
+    expected_type: Option<TypeId>,
 ) -> Result<(ExpressionAttributes, CallType), FatalError> {
     check_for_call_to_special_fns(context, name, func.span)?;
 
@@ -986,7 +1084,14 @@ fn expr_call_name<T: std::fmt::Display>(
         }
     })?;
 
-    expr_call_named_thing(context, named_thing, func, generic_args, args)
+    expr_call_named_thing(
+        context,
+        named_thing,
+        func,
+        generic_args,
+        args,
+        expected_type,
+    )
 }
 
 fn expr_call_path<T: std::fmt::Display>(
@@ -995,12 +1100,20 @@ fn expr_call_path<T: std::fmt::Display>(
     func: &Node<T>,
     generic_args: &Option<Node<Vec<fe::GenericArg>>>,
     args: &Node<Vec<Node<fe::CallArg>>>,
+    expected_type: Option<TypeId>,
 ) -> Result<(ExpressionAttributes, CallType), FatalError> {
     match context.resolve_visible_path(path) {
         Some(named_thing) => {
             check_visibility(context, &named_thing, func.span);
             validate_has_no_conflicting_trait_in_scope(context, &named_thing, path, func)?;
-            expr_call_named_thing(context, named_thing, func, generic_args, args)
+            expr_call_named_thing(
+                context,
+                named_thing,
+                func,
+                generic_args,
+                args,
+                expected_type,
+            )
         }
         // If we we can't resolve a call to a path e.g. `foo::Bar::do_thing()` there is a chance that `do_thing`
         // still exists as as a trait associated function for `foo::Bar`.
@@ -1141,6 +1254,7 @@ fn expr_call_named_thing<T: std::fmt::Display>(
     func: &Node<T>,
     generic_args: &Option<Node<Vec<fe::GenericArg>>>,
     args: &Node<Vec<Node<fe::CallArg>>>,
+    expected_type: Option<TypeId>,
 ) -> Result<(ExpressionAttributes, CallType), FatalError> {
     match named_thing {
         NamedThing::Item(Item::BuiltinFunction(function)) => {
@@ -1153,17 +1267,7 @@ fn expr_call_named_thing<T: std::fmt::Display>(
             expr_call_pure(context, function, func.span, generic_args, args)
         }
         NamedThing::Item(Item::Type(def)) => {
-            if let Some(args) = generic_args {
-                context.fancy_error(
-                    &format!("`{}` type is not generic", func.kind),
-                    vec![Label::primary(
-                        args.span,
-                        "unexpected generic argument list",
-                    )],
-                    vec![],
-                );
-            }
-            let typ = def.type_id(context.db())?;
+            let typ = apply_type_def_generic_args(context, def, func.span, generic_args.as_ref())?;
             expr_call_type_constructor(context, typ, func.span, args)
         }
         NamedThing::Item(Item::GenericType(generic)) => {
@@ -1208,6 +1312,7 @@ fn expr_call_named_thing<T: std::fmt::Display>(
         ))),
 
         NamedThing::EnumVariant(variant) => {
+            let variant = instantiate_variant(context, variant, expected_type, func.span)?;
             expr_call_enum_constructor(context, func.span, variant, args)
         }
 
@@ -1437,6 +1542,21 @@ fn expr_call_type_constructor(
                 "",
             )))
         }
+        Type::Vec(_) => {
+            // `Vec<T>()` creates an empty vector.
+            validate_arg_count(
+                context,
+                &typ.name(context.db()),
+                into_span,
+                args,
+                0,
+                "argument",
+            );
+            return Ok((
+                ExpressionAttributes::new(into_type),
+                CallType::TypeConstructor(into_type),
+            ));
+        }
         Type::SPtr(_) => unreachable!(), // unnameable
         _ => {}
     }
@@ -1509,6 +1629,39 @@ fn expr_call_struct_constructor(
     ))
 }
 
+/// Maps a variant of a generic enum to the same variant of the expected
+/// instance of the enum. The generic arguments can't be inferred otherwise.
+pub(crate) fn instantiate_variant(
+    context: &mut dyn AnalyzerContext,
+    variant: EnumVariantId,
+    expected_type: Option<TypeId>,
+    span: Span,
+) -> Result<EnumVariantId, FatalError> {
+    let db = context.db();
+    let enum_ = variant.parent(db);
+    if !enum_.is_generic(db) {
+        return Ok(variant);
+    }
+
+    match expected_type {
+        Some(expected) => Ok(variant.in_instance(db, expected).unwrap_or(variant)),
+        None => Err(FatalError::new(context.fancy_error(
+            &format!(
+                "type annotations needed for `{}`",
+                variant.name_with_parent(db)
+            ),
+            vec![Label::primary(
+                span,
+                format!("cannot infer the generic arguments of `{}`", enum_.name(db)),
+            )],
+            vec![format!(
+                "Hint: use it where the type is known, e.g. `let value: {}<...> = ...`",
+                enum_.name(db)
+            )],
+        ))),
+    }
+}
+
 fn expr_call_enum_constructor(
     context: &mut dyn AnalyzerContext,
     name_span: Span,
@@ -1561,6 +1714,20 @@ fn expr_call_method(
 
     let target_attributes = expr(context, target, None)?;
 
+    if let Type::Vec(elem_type) = target_attributes.typ.deref_typ(context.db()) {
+        if let Ok(method) = VecMethod::from_str(&field.kind) {
+            return expr_call_builtin_vec_method(
+                context,
+                target_attributes.typ,
+                elem_type,
+                target,
+                method,
+                field,
+                args,
+            );
+        }
+    }
+
     // Check built-in methods.
     if let Ok(method) = ValueMethod::from_str(&field.kind) {
         return expr_call_builtin_value_method(
@@ -1576,6 +1743,7 @@ fn expr_call_method(
     let obj_type = target_attributes.typ.deref(context.db());
     if obj_type.is_contract(context.db()) {
         check_for_call_to_special_fns(context, &field.kind, field.span)?;
+        check_for_call_to_dispatcher_fns(context, &field.kind, field.span)?;
     }
 
     match obj_type.function_sigs(context.db(), &field.kind).as_ref() {
@@ -1838,6 +2006,55 @@ fn expr_call_builtin_value_method(
     }
 }
 
+fn expr_call_builtin_vec_method(
+    context: &mut dyn AnalyzerContext,
+    vec_type: TypeId,
+    elem_type: TypeId,
+    vec: &Node<fe::Expr>,
+    method: VecMethod,
+    method_name: &Node<SmolStr>,
+    args: &Node<Vec<Node<fe::CallArg>>>,
+) -> Result<(ExpressionAttributes, CallType), FatalError> {
+    if method.is_mut() && !vec_type.is_mut(context.db()) {
+        context.error(
+            &format!("`{}` takes `mut self`", &method_name.kind),
+            vec.span,
+            "this is not mutable",
+        );
+    }
+
+    let return_type = match method {
+        VecMethod::Push => {
+            let params = [types::FunctionParam::new(Some("_"), "value", Ok(elem_type))];
+            validate_named_args(context, &method_name.kind, method_name.span, args, &params)?;
+            TypeId::unit(context.db())
+        }
+        VecMethod::Pop | VecMethod::Len => {
+            validate_arg_count(
+                context,
+                &method_name.kind,
+                method_name.span,
+                args,
+                method.arg_count(),
+                "argument",
+            );
+            if method == VecMethod::Pop {
+                elem_type
+            } else {
+                Type::u256().id(context.db())
+            }
+        }
+    };
+
+    Ok((
+        ExpressionAttributes::new(return_type),
+        CallType::BuiltinVecMethod {
+            method,
+            typ: vec_type,
+        },
+    ))
+}
+
 fn abi_encoded_type(
     context: &mut dyn AnalyzerContext,
     ty: TypeId,
@@ -1898,7 +2115,17 @@ fn expr_call_type_attribute(
         );
     }
 
-    let arg_attributes = expr_call_args(context, args)?;
+    // The arguments of `create` and `create2` that follow their own arguments
+    // are passed to the contract's `__init__` function, and are checked against
+    // its parameters below.
+    let own_arg_count = match (&typ, ContractTypeMethod::from_str(&field.kind)) {
+        (Type::Contract(_), Ok(function)) => function.arg_count().min(args.kind.len()),
+        _ => args.kind.len(),
+    };
+    let arg_attributes = args.kind[..own_arg_count]
+        .iter()
+        .map(|arg| expr(context, &arg.kind.value, None))
+        .collect::<Result<Vec<_>, _>>()?;
 
     let target_type = typ.id(context.db());
     let target_name = typ.name(context.db());
@@ -1907,6 +2134,21 @@ fn expr_call_type_attribute(
         // Check for Foo.create/create2 (this will go away when the context object is
         // ready)
         if let Ok(function) = ContractTypeMethod::from_str(&field.kind) {
+            if contract.is_interface(context.db()) {
+                context.fancy_error(
+                    &format!(
+                        "`{target_name}.{}(...)` can't be called on an interface",
+                        function.as_ref()
+                    ),
+                    vec![Label::primary(
+                        field.span,
+                        "interfaces have no code to deploy",
+                    )],
+                    vec![format!(
+                        "Hint: use `{target_name}(addr)` to call the contract deployed at `addr`"
+                    )],
+                );
+            }
             if context.root_item() == Item::Type(TypeDef::Contract(contract)) {
                 context.fancy_error(
                         &format!("`{contract}.{}(...)` called within `{contract}` creates an illegal circular dependency", function.as_ref(), contract=&target_name),
@@ -1914,14 +2156,28 @@ fn expr_call_type_attribute(
                         vec![format!("Note: Consider using a dedicated factory contract to create instances of `{}`", &target_name)]);
             }
             let arg_count = function.arg_count();
-            validate_arg_count(
+            let init_params = contract_init_params(context.db(), contract);
+            let init_args = Node::new(args.kind[own_arg_count..].to_vec(), args.span);
+            if validate_arg_count(
                 context,
                 &field.kind,
                 field.span,
                 args,
-                arg_count,
+                arg_count + init_params.len(),
                 "argument",
-            );
+            )
+            .is_none()
+            {
+                validate_named_args(
+                    context,
+                    &format!("{target_name}.{}", function.as_ref()),
+                    field.span,
+                    &init_args,
+                    &init_params,
+                )?;
+            } else {
+                expr_call_args(context, &init_args)?;
+            }
 
             for i in 0..arg_count {
                 if let Some(attrs) = arg_attributes.get(i) {
@@ -2046,6 +2302,18 @@ fn expr_call_type_attribute(
     )))
 }
 
+/// The parameters of the `__init__` function of `contract`, except for `ctx`,
+/// which `create` and `create2` already take.
+fn contract_init_params(db: &dyn AnalyzerDb, contract: ContractId) -> Vec<FunctionParam> {
+    contract
+        .init_function(db)
+        .map(|init| {
+            let sig = init.signature(db);
+            sig.params[usize::from(sig.ctx_decl.is_some())..].to_vec()
+        })
+        .unwrap_or_default()
+}
+
 fn expr_call_args(
     context: &mut dyn AnalyzerContext,
     args: &Node<Vec<Node<fe::CallArg>>>,
@@ -2097,6 +2365,29 @@ fn check_for_call_to_special_fns(
     }
 }
 
+/// `receive` and `fallback` are only called by the function dispatcher of a
+/// contract, so they're only special as contract methods.
+fn check_for_call_to_dispatcher_fns(
+    context: &mut dyn AnalyzerContext,
+    name: &str,
+    span: Span,
+) -> Result<(), FatalError> {
+    let note = match name {
+        "receive" => {
+            "Note: `receive` is called when the contract receives a call with empty calldata."
+        }
+        "fallback" => {
+            "Note: `fallback` is called when the calldata doesn't match a public function."
+        }
+        _ => return Ok(()),
+    };
+    Err(FatalError::new(context.fancy_error(
+        &format!("`{name}()` is not directly callable"),
+        vec![Label::primary(span, "")],
+        vec![note.into()],
+    )))
+}
+
 fn validate_numeric_literal_fits_type(
     context: &mut dyn AnalyzerContext,
     num: BigInt,
@@ -3,7 +3,7 @@ use crate::display::Displayable;
 use crate::errors::{self, FatalError, TypeCoercionError};
 use crate::namespace::items::{EnumVariantId, EnumVariantKind, Item, StructId, TypeDef};
 use crate::namespace::scopes::{BlockScope, BlockScopeType};
-use crate::namespace::types::{Type, TypeId};
+use crate::namespace::types::{Array, Type, TypeId};
 use crate::pattern_analysis::PatternMatrix;
 use crate::traversal::{assignments, declarations, expressions, types};
 use fe_common::diagnostics::Label;
@@ -55,11 +55,11 @@ fn for_loop(scope: &mut BlockScope, stmt: &Node<fe::FuncStmt>) -> Result<(), Fat
             let iter_type = expressions::expr(scope, iter, None)?.typ;
 
             let target_type = match iter_type.deref(scope.db()).typ(scope.db()) {
-                Type::Array(array) => {
+                Type::Array(Array { inner, .. }) | Type::Vec(inner) => {
                     if iter_type.is_sptr(scope.db()) {
                         scope.add_diagnostic(errors::to_mem_error(iter.span));
                     }
-                    array.inner
+                    inner
                 }
                 _ => {
                     return Err(FatalError::new(scope.register_diag(errors::type_error(
@@ -208,6 +208,7 @@ fn match_pattern(
         Pattern::Path(path) => match scope.resolve_visible_path(&path.kind) {
             Some(NamedThing::EnumVariant(variant)) => {
                 let db = scope.db();
+                let variant = variant.in_instance(db, expected_type).unwrap_or(variant);
                 let parent_type = variant.parent(db).as_type(db);
                 let kind = variant.kind(db)?;
                 if kind != EnumVariantKind::Unit {
@@ -294,6 +295,9 @@ fn match_pattern(
                 }
             };
 
+            let variant = variant
+                .in_instance(scope.db(), expected_type)
+                .unwrap_or(variant);
             let parent_type = variant.parent(scope.db()).as_type(scope.db());
             if parent_type != expected_type {
                 let err = scope.type_error("", pat.span, expected_type, parent_type);
@@ -640,7 +640,7 @@ fn simplify_pattern(
 
         Pattern::Path(path) => match scope.resolve_visible_path(&path.kind) {
             Some(NamedThing::EnumVariant(variant)) => SimplifiedPatternKind::Constructor {
-                kind: ConstructorKind::Enum(variant),
+                kind: ConstructorKind::Enum(variant.in_instance(scope.db(), ty).unwrap_or(variant)),
                 fields: vec![],
             },
             _ => {
@@ -654,6 +654,7 @@ fn simplify_pattern(
                 NamedThing::EnumVariant(variant) => variant,
                 _ => unreachable!(),
             };
+            let variant = variant.in_instance(scope.db(), ty).unwrap_or(variant);
             let ctor_kind = ConstructorKind::Enum(variant);
             let elts_tys = ctor_kind.field_types(scope.db());
 
@@ -5,10 +5,10 @@ use crate::context::{
 };
 use crate::display::Displayable;
 use crate::errors::{TypeCoercionError, TypeError};
-use crate::namespace::items::{Item, TraitId};
+use crate::namespace::items::{Item, TraitId, TypeDef};
 use crate::namespace::types::{
-    Base, FeString, GenericArg, GenericParamKind, GenericType, Integer, TraitOrType, Tuple, Type,
-    TypeId,
+    Base, FeString, Generic, GenericArg, GenericParamKind, GenericType, Integer, TraitOrType,
+    Tuple, Type, TypeId,
 };
 use crate::traversal::call_args::validate_arg_count;
 use fe_common::diagnostics::Label;
@@ -16,7 +16,10 @@ use fe_common::utils::humanize::pluralize_conditionally;
 use fe_common::Spanned;
 use fe_parser::ast;
 use fe_parser::node::{Node, Span};
+use indexmap::IndexMap;
+use smol_str::SmolStr;
 use std::cmp::Ordering;
+use std::rc::Rc;
 
 /// Try to perform an explicit type cast, eg `u256(my_address)` or `address(my_contract)`.
 /// Returns nothing. Emits an error if the cast fails; explicit cast failures are not fatal.
@@ -96,6 +99,7 @@ pub fn try_cast_type(
         (_, Type::Struct(_)) => unreachable!(),        // handled in expr_call_type_constructor
         (_, Type::Map(_)) => unreachable!(),           // handled in expr_call_type_constructor
         (_, Type::Array(_)) => unreachable!(),         // handled in expr_call_type_constructor
+        (_, Type::Vec(_)) => unreachable!(),           // handled in expr_call_type_constructor
         (_, Type::Generic(_)) => unreachable!(),       // handled in expr_call_type_constructor
         (_, Type::SelfContract(_)) => unreachable!(),  // contract names become Contract
 
@@ -422,12 +426,31 @@ pub fn apply_generic_type_args(
                 }
             }
 
+            (GenericParamKind::FixedSizeType, ast::GenericArg::TypeDesc(type_node)) => {
+                let typ = type_desc(context, type_node, None)?;
+                if typ.has_fixed_size(context.db()) {
+                    Ok(GenericArg::Type(typ))
+                } else {
+                    Err(TypeError::new(context.error(
+                        &format!("`{}` {} must have a fixed size", generic.name(), param.name),
+                        type_node.span,
+                        &format!(
+                            "`{}` can't be used as the {}",
+                            typ.display(context.db()),
+                            param.name
+                        ),
+                    )))
+                }
+            }
+
             (GenericParamKind::AnyType, ast::GenericArg::TypeDesc(type_node)) => {
                 Ok(GenericArg::Type(type_desc(context, type_node, None)?))
             }
 
             (
-                GenericParamKind::PrimitiveType | GenericParamKind::AnyType,
+                GenericParamKind::PrimitiveType
+                | GenericParamKind::FixedSizeType
+                | GenericParamKind::AnyType,
                 ast::GenericArg::Int(_) | ast::GenericArg::ConstExpr(_),
             ) => Err(TypeError::new(context.fancy_error(
                 &format!("`{}` {} must be a type", generic.name(), param.name),
@@ -441,6 +464,175 @@ pub fn apply_generic_type_args(
         .expect("failed to construct generic type after checking args"))
 }
 
+/// Resolves a user-defined type. Generic structs and enums are instantiated
+/// with the given generic arguments, which must satisfy the bounds of the
+/// generic parameters.
+pub fn apply_type_def_generic_args(
+    context: &mut dyn AnalyzerContext,
+    def: TypeDef,
+    name_span: Span,
+    args: Option<&Node<Vec<ast::GenericArg>>>,
+) -> Result<TypeId, TypeError> {
+    let db = context.db();
+    let params = match def {
+        TypeDef::Struct(id) => id.definition(db).type_params(db),
+        TypeDef::Enum(id) => id.definition(db).type_params(db),
+        _ => Rc::new(IndexMap::new()),
+    };
+
+    if params.is_empty() {
+        if let Some(args) = args {
+            context.fancy_error(
+                &format!("`{}` type is not generic", def.name(db)),
+                vec![Label::primary(
+                    args.span,
+                    "unexpected generic argument list",
+                )],
+                vec![],
+            );
+        }
+        return def.type_id(db);
+    }
+
+    let args = args.ok_or_else(|| {
+        TypeError::new(context.fancy_error(
+            &format!(
+                "missing generic {} for type `{}`",
+                pluralize_conditionally("argument", params.len()),
+                def.name(context.db())
+            ),
+            vec![Label::primary(
+                name_span,
+                format!(
+                    "expected {} generic {}",
+                    params.len(),
+                    pluralize_conditionally("argument", params.len())
+                ),
+            )],
+            vec![format!(
+                "Example: `{}<{}>`",
+                def.name(context.db()),
+                params.keys().map(|name| name.as_str()).collect::<Vec<_>>().join(", ")
+            )],
+        ))
+    })?;
+
+    if let Some(diag) = validate_arg_count(
+        context,
+        &def.name(context.db()),
+        name_span,
+        args,
+        params.len(),
+        "generic argument",
+    ) {
+        return Err(TypeError::new(diag));
+    }
+
+    let mut concrete_args = vec![];
+    for (param, arg) in params.values().zip(args.kind.iter()) {
+        let typ = match arg {
+            ast::GenericArg::TypeDesc(type_node) => type_desc(context, type_node, None)?,
+            ast::GenericArg::Int(_) | ast::GenericArg::ConstExpr(_) => {
+                return Err(TypeError::new(context.fancy_error(
+                    &format!(
+                        "`{}` generic argument must be a type",
+                        def.name(context.db())
+                    ),
+                    vec![Label::primary(arg.span(), "expected a type name")],
+                    vec![],
+                )))
+            }
+        };
+
+        if !typ.has_fixed_size(context.db()) {
+            return Err(TypeError::new(context.error(
+                "generic argument type must have a fixed size",
+                arg.span(),
+                &format!(
+                    "`{}` can't be used as a generic argument",
+                    typ.display(context.db())
+                ),
+            )));
+        }
+
+        if let Type::Generic(Generic { bounds, .. }) = param.typ(context.db()) {
+            for bound in bounds.iter() {
+                if !bound.is_implemented_for(context.db(), typ) {
+                    context.error(
+                        &format!(
+                            "the trait bound `{}: {}` is not satisfied",
+                            typ.display(context.db()),
+                            bound.name(context.db())
+                        ),
+                        arg.span(),
+                        &format!(
+                            "the trait `{}` is not implemented for `{}`",
+                            bound.name(context.db()),
+                            typ.display(context.db()),
+                        ),
+                    );
+                }
+            }
+        }
+        concrete_args.push(typ);
+    }
+
+    Ok(match def {
+        TypeDef::Struct(id) => Type::Struct(id.instantiate(context.db(), &concrete_args)),
+        TypeDef::Enum(id) => Type::Enum(id.instantiate(context.db(), &concrete_args)),
+        _ => unreachable!(),
+    }
+    .id(context.db()))
+}
+
+/// Maps the generic parameters of a struct or enum to their types: the
+/// generic arguments of an instance, or `Type::Generic`s bounded by the
+/// parameters' traits in the generic definition itself.
+pub fn resolve_type_params(
+    context: &mut dyn AnalyzerContext,
+    params: &[ast::GenericParameter],
+    args: &[TypeId],
+) -> IndexMap<SmolStr, TypeId> {
+    let mut types = IndexMap::<SmolStr, TypeId>::new();
+    for (idx, param) in params.iter().enumerate() {
+        let name = param.name();
+        let typ = match (args.get(idx), param) {
+            (Some(arg), _) => *arg,
+            (None, ast::GenericParameter::Unbounded(_)) => Type::Generic(Generic {
+                name: name.clone(),
+                bounds: Rc::new([]),
+            })
+            .id(context.db()),
+            (None, ast::GenericParameter::Bounded { bound, .. }) => {
+                let bounds = match type_desc_to_trait(context, bound) {
+                    Ok(trait_) => Rc::new([trait_]) as Rc<[_]>,
+                    Err(_) => Rc::new([]),
+                };
+                Type::Generic(Generic {
+                    name: name.clone(),
+                    bounds,
+                })
+                .id(context.db())
+            }
+        };
+
+        if let Some(previous) = params[..idx]
+            .iter()
+            .find(|previous| previous.name() == name)
+        {
+            context.duplicate_name_error(
+                "duplicate generic parameter",
+                &name,
+                previous.name_node().span,
+                param.name_node().span,
+            );
+        } else {
+            types.insert(name, typ);
+        }
+    }
+    types
+}
+
 fn friendly_generic_arg_example_string(generic: GenericType) -> String {
     let example_args = generic
         .params()
@@ -448,7 +640,7 @@ fn friendly_generic_arg_example_string(generic: GenericType) -> String {
         .map(|param| match param.kind {
             GenericParamKind::Int => "32",
             GenericParamKind::PrimitiveType => "u64",
-            GenericParamKind::AnyType => "String<32>",
+            GenericParamKind::FixedSizeType | GenericParamKind::AnyType => "String<32>",
         })
         .collect::<Vec<&'static str>>();
 
@@ -482,18 +674,8 @@ pub fn resolve_concrete_type_named_thing<T: std::fmt::Display>(
     generic_args: Option<&Node<Vec<ast::GenericArg>>>,
 ) -> Result<TypeId, TypeError> {
     match named_thing {
-        Some(NamedThing::Item(Item::Type(id))) => {
-            if let Some(args) = generic_args {
-                context.fancy_error(
-                    &format!("`{}` type is not generic", base_desc.kind),
-                    vec![Label::primary(
-                        args.span,
-                        "unexpected generic argument list",
-                    )],
-                    vec![],
-                );
-            }
-            id.type_id(context.db())
+        Some(NamedThing::Item(Item::Type(def))) => {
+            apply_type_def_generic_args(context, def, base_desc.span, generic_args)
         }
         Some(NamedThing::Item(Item::GenericType(generic))) => {
             apply_generic_type_args(context, generic, base_desc.span, generic_args)
@@ -542,7 +724,10 @@ pub fn type_desc(
     self_type: Option<TraitOrType>,
 ) -> Result<TypeId, TypeError> {
     match &desc.kind {
-        ast::TypeDesc::Base { base } => resolve_concrete_type_name(context, base, desc, None),
+        ast::TypeDesc::Base { base } => match context.resolve_type_param(base) {
+            Some(typ) => Ok(typ),
+            None => resolve_concrete_type_name(context, base, desc, None),
+        },
         ast::TypeDesc::Path(path) => resolve_concrete_type_path(context, path, desc, None),
         // generic will need to allow for paths too
         ast::TypeDesc::Generic { base, args } => {
@@ -27,6 +27,26 @@ fn analyze_std_lib() {
     }
 }
 
+#[test]
+fn imports_shadow_prelude() {
+    let mut db = TestDb::default();
+    let std = IngotId::std_lib(&mut db);
+    let ingot = IngotId::from_files(
+        &mut db,
+        "test_ingot",
+        IngotMode::Main,
+        FileKind::Local,
+        &test_files::fixture_dir_files("ingots/prelude_shadowing_ingot/src"),
+        indexmap! { "std".into() => std },
+    );
+
+    let diags = ingot.diagnostics(&db);
+    if !diags.is_empty() {
+        print_diagnostics(&db, &diags);
+        panic!("imported `Result` and `Option` should shadow the prelude")
+    }
+}
+
 #[test]
 fn ingot_files_to_modules() {
     let mut db = TestDb::default();
@@ -225,9 +225,12 @@ test_file! { bad_tuple_attr1 }
 test_file! { bad_tuple_attr2 }
 test_file! { bad_tuple_attr3 }
 test_file! { call_generic_function_with_unsatisfied_bound}
+test_file! { generic_struct_mistakes }
+test_file! { try_operator_mistakes }
 test_file! { call_builtin_object }
 test_file! { call_create_with_wrong_type }
 test_file! { call_create2_with_wrong_type }
+test_file! { call_create_with_wrong_init_args }
 test_file! { call_static_function_without_double_colon }
 test_file! { call_undefined_function_on_external_contract }
 test_file! { call_undefined_function_on_memory_struct }
@@ -235,6 +238,7 @@ test_file! { call_undefined_function_on_storage_struct }
 test_file! { call_non_pub_fn_on_external_contract }
 test_file! { call_non_pub_fn_on_struct }
 test_file! { call_non_pub_fn_on_struct2 }
+test_file! { cheatcode_outside_test }
 test_file! { cannot_move }
 test_file! { cannot_move2 }
 test_file! { circular_dependency_create }
@@ -291,6 +295,7 @@ test_file! { not_emittable }
 test_file! { not_in_scope }
 test_file! { not_in_scope_2 }
 test_file! { private_struct_field }
+test_file! { receive_fallback_mistakes }
 test_file! { return_addition_with_mixed_types }
 test_file! { return_call_to_fn_with_param_type_mismatch }
 test_file! { return_call_to_fn_without_return }
@@ -316,6 +321,8 @@ test_file! { init_wrong_return_type }
 test_file! { init_duplicate_def }
 test_file! { init_call_on_self }
 test_file! { init_call_on_external_contract }
+test_file! { interface_mistakes }
+test_file! { state_mutability_mistakes }
 test_file! { call_wrong_return_type }
 test_file! { call_duplicate_def }
 test_file! { call_call_on_self }
@@ -360,3 +367,7 @@ test_file! { invalid_comparisons }
 
 test_file! { _test_fn_call }
 test_file! { _test_fn_params }
+test_file! { _test_should_revert }
+test_file! { natspec_mistakes }
+test_file! { vec_mistakes }
+test_file! { duplicate_error_selector }
@@ -1,6 +1,8 @@
 use std::rc::Rc;
 
-use fe_abi::{contract::AbiContract, event::AbiEvent, function::AbiFunction, types::AbiType};
+use fe_abi::{
+    contract::AbiContract, error::AbiError, event::AbiEvent, function::AbiFunction, types::AbiType,
+};
 use fe_analyzer::{db::AnalyzerDbStorage, namespace::items::ContractId, AnalyzerDb};
 use fe_common::db::{SourceDb, SourceDbStorage, Upcast, UpcastMut};
 use fe_mir::{
@@ -8,6 +10,8 @@ use fe_mir::{
     ir::{FunctionBody, FunctionId, FunctionSignature, TypeId},
 };
 
+use crate::storage_layout::StorageLayout;
+
 mod queries;
 
 #[salsa::query_group(CodegenDbStorage)]
@@ -28,6 +32,8 @@ pub trait CodegenDb: MirDb + Upcast<dyn MirDb> + UpcastMut<dyn MirDb> {
     fn codegen_abi_function(&self, function_id: FunctionId) -> AbiFunction;
     #[salsa::invoke(queries::abi::abi_event)]
     fn codegen_abi_event(&self, ty: TypeId) -> AbiEvent;
+    #[salsa::invoke(queries::abi::abi_error)]
+    fn codegen_abi_error(&self, ty: TypeId) -> AbiError;
     #[salsa::invoke(queries::abi::abi_contract)]
     fn codegen_abi_contract(&self, contract: ContractId) -> AbiContract;
     #[salsa::invoke(queries::abi::abi_type_maximum_size)]
@@ -43,6 +49,8 @@ pub trait CodegenDb: MirDb + Upcast<dyn MirDb> + UpcastMut<dyn MirDb> {
     fn codegen_contract_symbol_name(&self, contract: ContractId) -> Rc<String>;
     #[salsa::invoke(queries::contract::deployer_symbol_name)]
     fn codegen_contract_deployer_symbol_name(&self, contract: ContractId) -> Rc<String>;
+    #[salsa::invoke(queries::contract::storage_layout)]
+    fn codegen_contract_storage_layout(&self, contract: ContractId) -> Rc<StorageLayout>;
 
     #[salsa::invoke(queries::constant::string_symbol_name)]
     fn codegen_constant_string_symbol_name(&self, data: String) -> Rc<String>;
@@ -1,15 +1,13 @@
 use fe_abi::{
     contract::AbiContract,
+    error::AbiError,
     event::{AbiEvent, AbiEventField},
-    function::{AbiFunction, AbiFunctionType, CtxParam, SelfParam, StateMutability},
+    function::{AbiFunction, AbiFunctionType, StateMutability},
     types::{AbiTupleField, AbiType},
 };
 use fe_analyzer::{
     constants::INDEXED,
-    namespace::{
-        items::ContractId,
-        types::{CtxDecl, SelfDecl},
-    },
+    namespace::{items::ContractId, types as analyzer_types},
 };
 use fe_mir::ir::{self, FunctionId, TypeId};
 
@@ -32,56 +30,84 @@ pub fn abi_contract(db: &dyn CodegenDb, contract: ContractId) -> AbiContract {
         }
     }
 
+    let dispatcher_funcs = [
+        contract.receive_function(db.upcast()),
+        contract.fallback_function(db.upcast()),
+    ];
+    for func in dispatcher_funcs.into_iter().flatten() {
+        let mir_func = db.mir_lowered_func_signature(func);
+        funcs.push(db.codegen_abi_function(mir_func));
+    }
+
+    let error_types = contract.error_types(db.upcast());
+
     let mut events = vec![];
     for &s in db.module_structs(contract.module(db.upcast())).as_ref() {
         let struct_ty = s.as_type(db.upcast());
         // TODO: This is a hack to avoid generating an ABI for non-`emittable` structs.
-        if struct_ty.is_emittable(db.upcast()) {
+        // The structs that the contract reverts with are listed as errors instead.
+        if struct_ty.is_emittable(db.upcast()) && !error_types.contains(&s) {
             let mir_event = db.mir_lowered_type(struct_ty);
             let event = db.codegen_abi_event(mir_event);
             events.push(event);
         }
     }
 
-    AbiContract::new(funcs, events)
+    // Errors with the same signature, like a user defined `Error(uint256)`
+    // and the one of `std`, share a single ABI entry.
+    let mut errors: Vec<AbiError> = vec![];
+    for error in error_types.iter() {
+        let error = db.codegen_abi_error(db.mir_lowered_type(error.as_type(db.upcast())));
+        if !errors.iter().any(|other| {
+            other.selector().selector_signature() == error.selector().selector_signature()
+        }) {
+            errors.push(error);
+        }
+    }
+
+    AbiContract::new(funcs, events, errors)
 }
 
 pub fn abi_function(db: &dyn CodegenDb, function: FunctionId) -> AbiFunction {
     // We use a legalized signature.
     let sig = db.codegen_legalized_signature(function);
 
-    let name = function.name(db.upcast());
-    let args = sig
-        .params
-        .iter()
-        .map(|param| (param.name.to_string(), db.codegen_abi_type(param.ty)))
-        .collect();
-    let ret_ty = sig.return_type.map(|ty| db.codegen_abi_type(ty));
-
+    let analyzer_func = sig.analyzer_func_id;
     let func_type = if function.is_contract_init(db.upcast()) {
         AbiFunctionType::Constructor
+    } else if analyzer_func.is_receive(db.upcast()) {
+        AbiFunctionType::Receive
+    } else if analyzer_func.is_fallback(db.upcast()) {
+        AbiFunctionType::Fallback
     } else {
         AbiFunctionType::Function
     };
 
-    // The "stateMutability" field is derived from the presence & mutability of
-    // `self` and `ctx` params in the analyzer fn sig.
-    let analyzer_sig = sig.analyzer_func_id.signature(db.upcast());
-    let self_param = match analyzer_sig.self_decl {
-        None => SelfParam::None,
-        Some(SelfDecl { mut_: None, .. }) => SelfParam::Imm,
-        Some(SelfDecl { mut_: Some(_), .. }) => SelfParam::Mut,
-    };
-    let ctx_param = match analyzer_sig.ctx_decl {
-        None => CtxParam::None,
-        Some(CtxDecl { mut_: None, .. }) => CtxParam::Imm,
-        Some(CtxDecl { mut_: Some(_), .. }) => CtxParam::Mut,
+    let name = match func_type {
+        // The ABI doesn't name the `receive` and `fallback` functions.
+        AbiFunctionType::Receive | AbiFunctionType::Fallback => "".into(),
+        _ => function.name(db.upcast()),
     };
+    let args = sig
+        .params
+        .iter()
+        .map(|param| (param.name.to_string(), db.codegen_abi_type(param.ty)))
+        .collect();
+    let ret_ty = sig.return_type.map(|ty| db.codegen_abi_type(ty));
 
-    let state_mutability = if name == "__init__" {
-        StateMutability::Payable
-    } else {
-        StateMutability::from_self_and_ctx_params(self_param, ctx_param)
+    let state_mutability = match analyzer_func.state_mutability(db.upcast()) {
+        analyzer_types::StateMutability::Payable => StateMutability::Payable,
+        // A constructor or `fallback` function can't be `pure` or `view`.
+        _ if matches!(
+            func_type,
+            AbiFunctionType::Constructor | AbiFunctionType::Fallback
+        ) =>
+        {
+            StateMutability::Nonpayable
+        }
+        analyzer_types::StateMutability::Pure => StateMutability::Pure,
+        analyzer_types::StateMutability::View => StateMutability::View,
+        analyzer_types::StateMutability::Nonpayable => StateMutability::Nonpayable,
     };
 
     AbiFunction::new(func_type, name.to_string(), args, ret_ty, state_mutability)
@@ -90,7 +116,7 @@ pub fn abi_function(db: &dyn CodegenDb, function: FunctionId) -> AbiFunction {
 pub fn abi_function_argument_maximum_size(db: &dyn CodegenDb, function: FunctionId) -> usize {
     let sig = db.codegen_legalized_signature(function);
     sig.params.iter().fold(0, |acc, param| {
-        acc + db.codegen_abi_type_maximum_size(param.ty)
+        acc.saturating_add(db.codegen_abi_type_maximum_size(param.ty))
     })
 }
 
@@ -117,6 +143,8 @@ pub fn abi_type_maximum_size(db: &dyn CodegenDb, ty: TypeId) -> usize {
             }
 
             ir::TypeKind::String(len) => abi_type.header_size() + 32 + ceil_32(*len),
+            // `Vec` has no upper bound on its length.
+            ir::TypeKind::Vec(_) => usize::MAX,
             _ if ty.is_aggregate(db.upcast()) => {
                 let mut maximum = 0;
                 for i in 0..ty.aggregate_field_num(db.upcast()) {
@@ -146,7 +174,7 @@ pub fn abi_type_minimum_size(db: &dyn CodegenDb, ty: TypeId) -> usize {
                 db.codegen_abi_type_minimum_size(def.elem_ty) * def.len + 32
             }
 
-            ir::TypeKind::String(_) => abi_type.header_size() + 32,
+            ir::TypeKind::String(_) | ir::TypeKind::Vec(_) => abi_type.header_size() + 32,
 
             _ if ty.is_aggregate(db.upcast()) => {
                 let mut minimum = 0;
@@ -227,6 +255,9 @@ pub fn abi_type(db: &dyn CodegenDb, ty: TypeId) -> AbiType {
 
             AbiType::Tuple(fields)
         }
+        ir::TypeKind::Vec(def) => AbiType::DynArray {
+            elem_ty: db.codegen_abi_type(def.elem_ty).into(),
+        },
         ir::TypeKind::MPtr(inner) => db.codegen_abi_type(*inner),
 
         ir::TypeKind::Contract(_)
@@ -268,6 +299,15 @@ pub fn abi_event(db: &dyn CodegenDb, ty: TypeId) -> AbiEvent {
     AbiEvent::new(event_def.name.to_string(), fields, false)
 }
 
+/// The ABI of an error type. The analyzer derives it, so that the selectors
+/// that it checks for collisions are the ones the generated code reverts with.
+pub fn abi_error(db: &dyn CodegenDb, ty: TypeId) -> AbiError {
+    ty.analyzer_ty(db.upcast())
+        .and_then(|ty| ty.as_struct(db.upcast()))
+        .and_then(|error| error.abi_error(db.upcast()))
+        .expect("error type must be an ABI encodable struct")
+}
+
 fn ceil_32(value: usize) -> usize {
     ((value + 31) / 32) * 32
 }
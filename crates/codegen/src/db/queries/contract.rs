@@ -1,8 +1,13 @@
 use std::rc::Rc;
 
-use fe_analyzer::namespace::items::ContractId;
+use fe_analyzer::namespace::{items::ContractId, types::Type};
+use fe_mir::ir::TypeKind;
 
-use crate::db::CodegenDb;
+use crate::{
+    db::CodegenDb,
+    storage_layout::{StorageEncoding, StorageField, StorageLayout},
+    yul::slot_size::SLOT_SIZE,
+};
 
 pub fn symbol_name(db: &dyn CodegenDb, contract: ContractId) -> Rc<String> {
     let module = contract.module(db.upcast());
@@ -18,3 +23,37 @@ pub fn symbol_name(db: &dyn CodegenDb, contract: ContractId) -> Rc<String> {
 pub fn deployer_symbol_name(db: &dyn CodegenDb, contract: ContractId) -> Rc<String> {
     format!("deploy_{}", symbol_name(db, contract).as_ref()).into()
 }
+
+/// Computes the storage locations of the contract fields from the same
+/// legalized contract type the generated code accesses them through.
+pub fn storage_layout(db: &dyn CodegenDb, contract: ContractId) -> Rc<StorageLayout> {
+    let contract_ty = Type::SelfContract(contract).id(db.upcast());
+    let contract_ty = db.codegen_legalized_type(db.mir_lowered_type(contract_ty));
+
+    let fields = match &contract_ty.data(db.upcast()).kind {
+        TypeKind::Contract(def) => def
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(idx, (name, ty))| {
+                let offset = contract_ty.aggregate_elem_offset(db.upcast(), idx, SLOT_SIZE);
+                let encoding = match &ty.data(db.upcast()).kind {
+                    TypeKind::Map(_) => StorageEncoding::Mapping,
+                    TypeKind::Vec(_) => StorageEncoding::Dynamic,
+                    _ => StorageEncoding::Inplace,
+                };
+                StorageField {
+                    name: name.clone(),
+                    ty: *ty,
+                    slot: offset / SLOT_SIZE,
+                    offset: offset % SLOT_SIZE,
+                    size: ty.size_of(db.upcast(), SLOT_SIZE),
+                    encoding,
+                }
+            })
+            .collect(),
+        _ => unreachable!(),
+    };
+
+    Rc::new(StorageLayout { fields })
+}
@@ -45,6 +45,10 @@ pub fn symbol_name(db: &dyn CodegenDb, function: FunctionId) -> Rc<String> {
             );
             format!("{class_name}${func_name}")
         }
+        Some(Item::Type(def)) => {
+            let class_name = safe_name(db, def.type_id(db.upcast()).unwrap());
+            format!("{class_name}${func_name}")
+        }
         Some(class) => {
             let class_name = class.name(db.upcast());
             format!("{class_name}${func_name}")
@@ -71,6 +75,21 @@ fn safe_name(db: &dyn CodegenDb, ty: TypeId) -> SmolStr {
         // TODO: Would be nice to get more human friendly names here
         Type::Array(_) => format!("array_{:?}", ty.as_intern_id()).into(),
         Type::Tuple(_) => format!("tuple_{:?}", ty.as_intern_id()).into(),
+        // Instances of generic structs and enums are named after their generic arguments.
+        Type::Struct(id) => {
+            instance_safe_name(db, id.name(db.upcast()), &id.generic_args(db.upcast()))
+        }
+        Type::Enum(id) => {
+            instance_safe_name(db, id.name(db.upcast()), &id.generic_args(db.upcast()))
+        }
         _ => format!("{}", ty.display(db.upcast())).into(),
     }
 }
+
+fn instance_safe_name(db: &dyn CodegenDb, name: SmolStr, args: &[TypeId]) -> SmolStr {
+    args.iter()
+        .fold(name.to_string(), |acc, arg| {
+            format!("{}_{}", acc, safe_name(db, *arg))
+        })
+        .into()
+}
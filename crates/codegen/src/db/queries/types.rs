@@ -1,5 +1,5 @@
 use fe_mir::ir::{
-    types::{ArrayDef, MapDef, StructDef, TupleDef},
+    types::{ArrayDef, MapDef, StructDef, TupleDef, VecDef},
     Type, TypeId, TypeKind,
 };
 
@@ -84,6 +84,13 @@ pub fn legalized_type(db: &dyn CodegenDb, ty: TypeId) -> TypeId {
             TypeKind::Map(new_def)
         }
 
+        TypeKind::Vec(def) => {
+            let new_def = VecDef {
+                elem_ty: legalized_type(db, def.elem_ty),
+            };
+            TypeKind::Vec(new_def)
+        }
+
         TypeKind::MPtr(ty) => {
             let new_ty = legalized_type(db, *ty);
             TypeKind::MPtr(new_ty)
@@ -1,2 +1,3 @@
 pub mod db;
+pub mod storage_layout;
 pub mod yul;
@@ -0,0 +1,49 @@
+use fe_mir::ir::TypeId;
+use smol_str::SmolStr;
+
+/// The storage locations of the fields of a contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageLayout {
+    pub fields: Vec<StorageField>,
+}
+
+/// The storage location of a contract field.
+///
+/// Consecutive fields that fit into a slot share it, so a field is located by
+/// its slot and its byte offset in the slot. The offset is counted from the
+/// most significant byte of the slot. Note that this isn't the layout of
+/// Solidity, which right-aligns packed values and starts a new slot after a
+/// struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageField {
+    pub name: SmolStr,
+    pub ty: TypeId,
+    pub slot: usize,
+    pub offset: usize,
+    /// The number of bytes the field occupies in its slot(s).
+    pub size: usize,
+    /// How the value of the field is laid out in storage.
+    pub encoding: StorageEncoding,
+}
+
+/// How the value of a field is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageEncoding {
+    /// The value is stored in the slot(s) of the field.
+    Inplace,
+    /// The field slot is only used to derive the slots of the map values.
+    Mapping,
+    /// The field slot holds the length, and the elements are stored from the
+    /// slot derived from the hash of the field slot.
+    Dynamic,
+}
+
+impl StorageEncoding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inplace => "inplace",
+            Self::Mapping => "mapping",
+            Self::Dynamic => "dynamic",
+        }
+    }
+}
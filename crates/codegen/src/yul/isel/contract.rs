@@ -16,7 +16,8 @@ pub fn lower_contract_deployable(db: &dyn CodegenDb, contract: ContractId) -> yu
         let init = db.mir_lowered_func_signature(init);
         make_init(db, &mut context, contract, init)
     } else {
-        statements! {}
+        // The default constructor isn't payable.
+        vec![revert_if_callvalue()]
     };
 
     let deploy_code = make_deploy(db, contract);
@@ -79,11 +80,11 @@ pub fn lower_contract(db: &dyn CodegenDb, contract: ContractId) -> yul::Object {
         let call_fn = db.mir_lowered_func_signature(call_fn);
         context.function_dependency.insert(call_fn);
         let call_symbol = identifier! { (db.codegen_function_symbol_name(call_fn)) };
-        statement! {
+        statements! {
             ([call_symbol]())
         }
     } else {
-        make_dispatcher(db, &mut context, &exported_funcs)
+        make_dispatcher(db, &mut context, contract, &exported_funcs)
     };
 
     let dep_functions: Vec<_> = context
@@ -99,7 +100,7 @@ pub fn lower_contract(db: &dyn CodegenDb, contract: ContractId) -> yul::Object {
         .collect();
 
     let code = code! {
-        ([dispatcher])
+        [dispatcher...]
         [dep_functions...]
         [runtime_funcs...]
     };
@@ -122,27 +123,77 @@ pub fn lower_contract(db: &dyn CodegenDb, contract: ContractId) -> yul::Object {
 fn make_dispatcher(
     db: &dyn CodegenDb,
     context: &mut Context,
+    contract: ContractId,
     funcs: &[FunctionId],
-) -> yul::Statement {
+) -> Vec<yul::Statement> {
     let arms = funcs
         .iter()
         .map(|func| dispatch_arm(db, context, *func))
         .collect::<Vec<_>>();
 
+    let receive = contract
+        .receive_function(db.upcast())
+        .map(|func| db.mir_lowered_func_signature(func));
+    let fallback = contract
+        .fallback_function(db.upcast())
+        .map(|func| db.mir_lowered_func_signature(func));
+
+    // A call with empty calldata goes to `receive`, or to `fallback` if there's
+    // no `receive` function.
+    let receive_call = match receive.or(fallback) {
+        Some(func) => {
+            let call = dispatch_without_selector(db, context, func);
+            statements! {
+                (if (iszero((calldatasize()))) { [call...] })
+            }
+        }
+        None => statements! {},
+    };
+
+    // A call that doesn't match any selector goes to `fallback`.
+    let default = match fallback {
+        Some(func) => dispatch_without_selector(db, context, func),
+        None => statements! { (return(0, 0)) },
+    };
+
     if arms.is_empty() {
-        statement! { return(0, 0) }
+        statements! {
+            [receive_call...]
+            [default...]
+        }
     } else {
         let selector = expression! {
             and((shr((sub(256, 32)), (calldataload(0)))), 0xffffffff)
         };
-        switch! {
+        let switch = switch! {
             switch ([selector])
             [arms...]
-            (default { (return(0, 0)) })
+            (default { [default...] })
+        };
+        statements! {
+            [receive_call...]
+            [switch]
         }
     }
 }
 
+/// Calls the `receive` or `fallback` function, which take no arguments and
+/// return nothing.
+fn dispatch_without_selector(
+    db: &dyn CodegenDb,
+    context: &mut Context,
+    func: FunctionId,
+) -> Vec<yul::Statement> {
+    context.function_dependency.insert(func);
+    let name = identifier! { (db.codegen_function_symbol_name(func)) };
+    let check_callvalue = check_callvalue(db, func);
+    statements! {
+        [check_callvalue...]
+        ([name]())
+        (return(0, 0))
+    }
+}
+
 fn dispatch_arm(db: &dyn CodegenDb, context: &mut Context, func: FunctionId) -> yul::Case {
     context.function_dependency.insert(func);
     let func_sig = db.codegen_legalized_signature(func);
@@ -198,15 +249,34 @@ fn dispatch_arm(db: &dyn CodegenDb, context: &mut Context, func: FunctionId) ->
     };
 
     let abi_sig = db.codegen_abi_function(func);
+    let check_callvalue = check_callvalue(db, func);
     let selector = literal! { (format!("0x{}", abi_sig.selector().hex())) };
     case! {
         case [selector] {
+            [check_callvalue...]
             [decode_params...]
             [call_and_encode_return...]
         }
     }
 }
 
+/// Reverts if ether is sent to a function that isn't declared `#payable`.
+fn check_callvalue(db: &dyn CodegenDb, func: FunctionId) -> Vec<yul::Statement> {
+    let analyzer_func = db.codegen_legalized_signature(func).analyzer_func_id;
+    if analyzer_func.is_payable(db.upcast()) {
+        vec![]
+    } else {
+        vec![revert_if_callvalue()]
+    }
+}
+
+/// Reverts if any ether is sent with the call.
+fn revert_if_callvalue() -> yul::Statement {
+    statement! {
+        if (callvalue()) { (revert(0, 0)) }
+    }
+}
+
 fn make_init(
     db: &dyn CodegenDb,
     context: &mut Context,
@@ -238,8 +308,11 @@ fn make_init(
         }
     };
 
+    let check_callvalue = check_callvalue(db, init);
+
     let call = expression! {[init_func_name]([(param_vars.iter().map(YulVariable::expr).collect::<Vec<_>>())...])};
     statements! {
+        [check_callvalue...]
         (let [program_size.ident()] := datasize([contract_name]))
         (let [code_size.ident()] := codesize())
         (let [arg_size.ident()] := sub([code_size.expr()], [program_size.expr()]))
@@ -191,11 +191,11 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                         self.ctx.runtime.primitive_cast(self.db, value, from_ty)
                     }
                     CastKind::Untag => {
-                        let from_ty = from_ty.deref(self.db.upcast());
-                        debug_assert!(from_ty.is_enum(self.db.upcast()));
+                        let enum_ty = from_ty.deref(self.db.upcast());
+                        debug_assert!(enum_ty.is_enum(self.db.upcast()));
                         let value = self.value_expr(*value);
-                        let offset = literal_expression! {(from_ty.enum_data_offset(self.db.upcast(), SLOT_SIZE))};
-                        expression! {add([value], [offset])}
+                        let offset = literal_expression! {(enum_ty.enum_data_offset(self.db.upcast(), SLOT_SIZE))};
+                        self.ptr_offset(value, offset, from_ty)
                     }
                 };
 
@@ -251,6 +251,21 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                 }
             }
 
+            InstKind::MemCopy { src }
+                if self
+                    .body
+                    .store
+                    .value_ty(*src)
+                    .deref(self.db.upcast())
+                    .is_vec(self.db.upcast()) =>
+            {
+                // Copying a `Vec` duplicates its elements as well.
+                let src_ty = self.body.store.value_ty(*src);
+                let src = self.value_expr(*src);
+                let result = self.ctx.runtime.vec_clone(self.db, src, src_ty);
+                self.assign_inst_result(inst, result, src_ty.deref(self.db.upcast()))
+            }
+
             InstKind::MemCopy { src } => {
                 let lhs = self.body.store.inst_result(inst).unwrap();
                 let dst_ptr = self.lower_assignable_value(lhs);
@@ -286,7 +301,7 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                 let mut ptr = base;
                 let mut inner_ty = self.body.store.value_ty(*value);
                 for &idx in indices {
-                    ptr = self.aggregate_elem_ptr(ptr, idx, inner_ty.deref(self.db.upcast()));
+                    ptr = self.aggregate_elem_ptr(ptr, idx, inner_ty);
                     inner_ty =
                         inner_ty.projection_ty(self.db.upcast(), self.body.store.value_data(idx));
                 }
@@ -312,6 +327,68 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                 self.assign_inst_result(inst, ptr, value_ty.make_sptr(self.db.upcast()));
             }
 
+            InstKind::VecNew { .. } => {
+                let result = self.ctx.runtime.vec_new(self.db);
+                let u256_ty = yul_primitive_type(self.db);
+                self.assign_inst_result(inst, result, u256_ty)
+            }
+
+            InstKind::VecPush { vec, value } => {
+                let vec_ty = self.body.store.value_ty(*vec);
+                let value_ty = self.body.store.value_ty(*value);
+                let vec = self.value_expr(*vec);
+                let value = self.value_expr(*value);
+                self.sink.push(yul::Statement::Expression(
+                    self.ctx
+                        .runtime
+                        .vec_push(self.db, vec, value, vec_ty, value_ty),
+                ))
+            }
+
+            InstKind::VecPop { vec } => {
+                let vec_ty = self.body.store.value_ty(*vec);
+                let is_sptr = vec_ty.is_sptr(self.db.upcast());
+                let elem_ptr_ty = match &vec_ty.deref(self.db.upcast()).data(self.db.upcast()).kind
+                {
+                    TypeKind::Vec(def) => runtime::make_ptr(self.db, def.elem_ty, is_sptr),
+                    _ => unreachable!(),
+                };
+                let vec = self.value_expr(*vec);
+                let elem_ptr = self.ctx.runtime.vec_pop(self.db, vec, vec_ty);
+                match self.body.store.inst_result(inst) {
+                    // The popped slot is reused by the next `push`, so an aggregate
+                    // element is copied out of the vector.
+                    Some(result)
+                        if elem_ptr_ty
+                            .deref(self.db.upcast())
+                            .is_aggregate(self.db.upcast()) =>
+                    {
+                        let dst = self.lower_assignable_value(result);
+                        let ty_size = elem_ptr_ty
+                            .deref(self.db.upcast())
+                            .size_of(self.db.upcast(), SLOT_SIZE);
+                        self.sink
+                            .push(yul::Statement::Expression(self.ctx.runtime.ptr_copy(
+                                self.db,
+                                elem_ptr,
+                                dst,
+                                literal_expression! { (ty_size) },
+                                is_sptr,
+                                false,
+                            )))
+                    }
+                    _ => self.assign_inst_result(inst, elem_ptr, elem_ptr_ty),
+                }
+            }
+
+            InstKind::VecLen { vec } => {
+                // The length is stored in the first word of a `Vec`.
+                let is_sptr = self.body.store.value_ty(*vec).is_sptr(self.db.upcast());
+                let vec = self.value_expr(*vec);
+                let len_ptr_ty = runtime::make_ptr(self.db, yul_primitive_type(self.db), is_sptr);
+                self.assign_inst_result(inst, vec, len_ptr_ty)
+            }
+
             InstKind::Call {
                 func,
                 args,
@@ -403,11 +480,19 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                 });
             }
 
-            InstKind::Create { value, contract } => {
+            InstKind::Create {
+                value,
+                contract,
+                args,
+            } => {
                 self.ctx.contract_dependency.insert(*contract);
 
                 let value_expr = self.value_expr(*value);
-                let result = self.ctx.runtime.create(self.db, *contract, value_expr);
+                let args: Vec<_> = args.iter().map(|arg| self.value_expr(*arg)).collect();
+                let result = self
+                    .ctx
+                    .runtime
+                    .create(self.db, *contract, value_expr, args);
                 let u256_ty = yul_primitive_type(self.db);
                 self.assign_inst_result(inst, result, u256_ty)
             }
@@ -416,15 +501,17 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                 value,
                 salt,
                 contract,
+                args,
             } => {
                 self.ctx.contract_dependency.insert(*contract);
 
                 let value_expr = self.value_expr(*value);
                 let salt_expr = self.value_expr(*salt);
+                let args: Vec<_> = args.iter().map(|arg| self.value_expr(*arg)).collect();
                 let result = self
                     .ctx
                     .runtime
-                    .create2(self.db, *contract, value_expr, salt_expr);
+                    .create2(self.db, *contract, value_expr, salt_expr, args);
                 let u256_ty = yul_primitive_type(self.db);
                 self.assign_inst_result(inst, result, u256_ty)
             }
@@ -721,7 +808,17 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                     }
                 }
                 AssignableValue::Aggregate { .. } | AssignableValue::Map { .. } => {
-                    let expr = if rhs_ty.is_ptr(self.db.upcast()) {
+                    let expr = if lhs_ty.deref(self.db.upcast()).is_vec(self.db.upcast()) {
+                        // Only storage `Vec`s can be assigned to, and the elements are copied
+                        // into storage.
+                        debug_assert!(lhs_ty.is_sptr(self.db.upcast()));
+                        let src = if rhs_ty.is_sptr(self.db.upcast()) {
+                            self.ctx.runtime.vec_clone(self.db, rhs, rhs_ty)
+                        } else {
+                            rhs
+                        };
+                        self.ctx.runtime.vec_to_storage(self.db, src, lhs, lhs_ty)
+                    } else if rhs_ty.is_ptr(self.db.upcast()) {
                         let ty_size = rhs_ty
                             .deref(self.db.upcast())
                             .size_of(self.db.upcast(), SLOT_SIZE);
@@ -846,9 +943,7 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
 
             AssignableValue::Aggregate { lhs, idx } => {
                 let base_ptr = self.lower_assignable_value(lhs);
-                let ty = lhs
-                    .ty(self.db.upcast(), &self.body.store)
-                    .deref(self.db.upcast());
+                let ty = lhs.ty(self.db.upcast(), &self.body.store);
                 self.aggregate_elem_ptr(base_ptr, *idx, ty)
             }
             AssignableValue::Map { lhs, key } => {
@@ -866,18 +961,25 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
         &mut self,
         base_ptr: yul::Expression,
         idx: ValueId,
-        base_ty: TypeId,
+        base_ptr_ty: TypeId,
     ) -> yul::Expression {
-        debug_assert!(base_ty.is_aggregate(self.db.upcast()));
+        let base_ty = base_ptr_ty.deref(self.db.upcast());
+        debug_assert!(base_ty.is_aggregate(self.db.upcast()) || base_ty.is_vec(self.db.upcast()));
 
         match &base_ty.data(self.db.upcast()).kind {
+            TypeKind::Vec(_) => {
+                let idx = self.value_expr(idx);
+                self.ctx
+                    .runtime
+                    .vec_elem_ptr(self.db, base_ptr, idx, base_ptr_ty)
+            }
             TypeKind::Array(def) => {
                 let elem_size =
                     literal_expression! {(base_ty.array_elem_size(self.db.upcast(), SLOT_SIZE))};
                 self.validate_array_indexing(def.len, idx);
                 let idx = self.value_expr(idx);
                 let offset = expression! {mul([elem_size], [idx])};
-                expression! { add([base_ptr], [offset]) }
+                self.ptr_offset(base_ptr, offset, base_ptr_ty)
             }
             _ => {
                 let elem_idx = match self.body.store.value_data(idx) {
@@ -885,11 +987,24 @@ impl<'db, 'a> FuncLowerHelper<'db, 'a> {
                     _ => panic!("only array type can use dynamic value indexing"),
                 };
                 let offset = literal_expression! {(base_ty.aggregate_elem_offset(self.db.upcast(), elem_idx.clone(), SLOT_SIZE))};
-                expression! {add([base_ptr], [offset])}
+                self.ptr_offset(base_ptr, offset, base_ptr_ty)
             }
         }
     }
 
+    fn ptr_offset(
+        &mut self,
+        ptr: yul::Expression,
+        offset: yul::Expression,
+        ptr_ty: TypeId,
+    ) -> yul::Expression {
+        if ptr_ty.is_sptr(self.db.upcast()) {
+            self.ctx.runtime.sptr_offset(self.db, ptr, offset)
+        } else {
+            expression! { add([ptr], [offset]) }
+        }
+    }
+
     fn validate_array_indexing(&mut self, array_len: usize, idx: ValueId) {
         const PANIC_OUT_OF_BOUNDS: usize = 0x32;
 
@@ -100,7 +100,9 @@ fn legalize_inst_arg(db: &dyn CodegenDb, body: &mut FunctionBody, inst_id: InstI
             args.retain(|arg| !is_value_zst(db, body, *arg));
         }
 
-        InstKind::Call { args, .. } => {
+        InstKind::Call { args, .. }
+        | InstKind::Create { args, .. }
+        | InstKind::Create2 { args, .. } => {
             args.retain(|arg| !is_value_zst(db, body, *arg) && !is_value_contract(db, body, *arg))
         }
 
@@ -159,7 +161,8 @@ fn legalize_inst_result(db: &dyn CodegenDb, body: &mut FunctionBody, inst_id: In
             InstKind::AggregateAccess { value, .. } => {
                 let value_ty = body.store.value_ty(*value);
                 match &value_ty.data(db.upcast()).kind {
-                    TypeKind::MPtr(..) => result_ty.make_mptr(db.upcast()),
+                    // `Vec` elements always live in memory.
+                    TypeKind::MPtr(..) | TypeKind::Vec(..) => result_ty.make_mptr(db.upcast()),
                     // Note: All SPtr aggregate access results should be SPtr already
                     _ => unreachable!(),
                 }
@@ -125,6 +125,72 @@ pub(super) fn make_abi_encode_dynamic_array_type(
     RuntimeFunction::from_statement(func)
 }
 
+pub(super) fn make_abi_encode_vec_type(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+    legalized_ty: TypeId,
+    is_dst_storage: bool,
+) -> RuntimeFunction {
+    let elem_ty = match &legalized_ty.data(db.upcast()).kind {
+        ir::TypeKind::Vec(def) => def.elem_ty,
+        _ => unreachable!(),
+    };
+    let elem_abi_ty = db.codegen_abi_type(elem_ty);
+    let elem_ptr_ty = make_ptr(db, elem_ty, false);
+    let elem_ty_size = literal_expression! {(legalized_ty.array_elem_size(db.upcast(), SLOT_SIZE))};
+    let header_ty = make_ptr(db, yul_primitive_type(db), is_dst_storage);
+
+    let func_name = YulVariable::new(func_name);
+    let src = YulVariable::new("src");
+    let dst = YulVariable::new("dst");
+    let len = YulVariable::new("len");
+    let elem_ptr = YulVariable::new("elem_ptr");
+    let header_ptr = YulVariable::new("header_ptr");
+    let data_ptr = YulVariable::new("data_ptr");
+    let enc_size = YulVariable::new("enc_size");
+
+    let encode_elems = if elem_abi_ty.is_static() {
+        let elem_header_size = literal_expression! {(elem_abi_ty.header_size())};
+        statements! {
+            (for {(let i := 0)} (lt(i, [len.expr()])) {(i := (add(i, 1)))}
+            {
+                (pop([provider.abi_encode(db, elem_ptr.expr(), dst.expr(), elem_ptr_ty, is_dst_storage)]))
+                ([elem_ptr.ident()] := add([elem_ptr.expr()], [elem_ty_size]))
+                ([dst.ident()] := add([dst.expr()], [elem_header_size.clone()]))
+            })
+            ([enc_size.ident()] := mul([len.expr()], [elem_header_size]))
+        }
+    } else {
+        statements! {
+            (let [header_ptr.ident()] := [dst.expr()])
+            ([enc_size.ident()] := mul([len.expr()], 32))
+            (let [data_ptr.ident()] := add([dst.expr()], [enc_size.expr()]))
+            (for {(let i := 0)} (lt(i, [len.expr()])) {(i := (add(i, 1)))}
+            {
+                ([yul::Statement::Expression(provider.ptr_store(db, header_ptr.expr(), enc_size.expr(), header_ty))])
+                ([enc_size.ident()] := add([provider.abi_encode(db, elem_ptr.expr(), data_ptr.expr(), elem_ptr_ty, is_dst_storage)], [enc_size.expr()]))
+                ([header_ptr.ident()] := add([header_ptr.expr()], 32))
+                ([data_ptr.ident()] := add([dst.expr()], [enc_size.expr()]))
+                ([elem_ptr.ident()] := add([elem_ptr.expr()], [elem_ty_size]))
+            })
+        }
+    };
+
+    let func = function_definition! {
+        function [func_name.ident()]([src.ident()], [dst.ident()]) -> [enc_size.ident()] {
+            (let [len.ident()] := mload([src.expr()]))
+            (let [elem_ptr.ident()] := mload((add([src.expr()], 64))))
+            ([yul::Statement::Expression(provider.ptr_store(db, dst.expr(), len.expr(), header_ty))])
+            ([dst.ident()] := add([dst.expr()], 32))
+            [encode_elems...]
+            ([enc_size.ident()] := add([enc_size.expr()], 32))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
 pub(super) fn make_abi_encode_static_aggregate_type(
     provider: &mut DefaultRuntimeProvider,
     db: &dyn CodegenDb,
@@ -204,7 +270,11 @@ pub(super) fn make_abi_encode_dynamic_aggregate_type(
         let field_abi_ty = db.codegen_abi_type(field_ty);
         let field_offset =
             literal_expression! { (deref_ty.aggregate_elem_offset(db.upcast(), idx, SLOT_SIZE)) };
-        let field_ptr = expression! { add([src.expr()], [field_offset]) };
+        let field_ptr = if is_src_storage {
+            provider.sptr_offset(db, src.expr(), field_offset)
+        } else {
+            expression! { add([src.expr()], [field_offset]) }
+        };
         let field_ptr_ty = make_ptr(db, field_ty, is_src_storage);
 
         let stmts = if field_abi_ty.is_static() {
@@ -528,6 +598,13 @@ impl DefaultRuntimeProvider {
                 })
             }
 
+            AbiType::DynArray { .. } => {
+                let name = format! {"$abi_decode_vec_{}_from_{}", ty.0, func_name_postfix};
+                self.create_then_call(&name, args, |provider| {
+                    make_abi_decode_vec_type(provider, db, &name, ty, abi_loc)
+                })
+            }
+
             AbiType::Tuple(_) => {
                 let name =
                     format! {"$abi_decode_dynamic_aggregate_{}_from_{}", ty.0, func_name_postfix};
@@ -786,6 +863,100 @@ fn make_abi_decode_dynamic_elem_array_type(
     RuntimeFunction::from_statement(func)
 }
 
+fn make_abi_decode_vec_type(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+    legalized_ty: TypeId,
+    abi_loc: AbiSrcLocation,
+) -> RuntimeFunction {
+    // Rejects lengths that would overflow the memory size computation.
+    const MAXIMUM_LEN: &str = "0xffffffffffffffff";
+
+    let elem_ty = match &legalized_ty.data(db.upcast()).kind {
+        ir::TypeKind::Vec(def) => def.elem_ty,
+        _ => unreachable!(),
+    };
+    let elem_abi_ty = db.codegen_abi_type(elem_ty);
+    let elem_ty_size = literal_expression! {(legalized_ty.array_elem_size(db.upcast(), SLOT_SIZE))};
+    let elem_size = elem_ty.size_of(db.upcast(), SLOT_SIZE);
+
+    let func_name = YulVariable::new(func_name);
+    let src = YulVariable::new("src");
+    let len = YulVariable::new("len");
+    let data = YulVariable::new("data");
+    let header_ptr = YulVariable::new("header_ptr");
+    let data_ptr = YulVariable::new("data_ptr");
+    let decoded_data = YulVariable::new("decoded_data");
+    let decoded_size = YulVariable::new("decoded_size");
+    let decoded_size_tmp = YulVariable::new("decoded_size_tmp");
+    let ret_elem_ptr = YulVariable::new("ret_elem_ptr");
+    let elem_data = YulVariable::new("elem_data");
+
+    let copy_to_ret = if elem_ty.is_primitive(db.upcast()) {
+        provider.ptr_store(
+            db,
+            ret_elem_ptr.expr(),
+            elem_data.expr(),
+            make_ptr(db, elem_ty, false),
+        )
+    } else {
+        provider.ptr_copy(
+            db,
+            elem_data.expr(),
+            ret_elem_ptr.expr(),
+            literal_expression! {(elem_size)},
+            false,
+            false,
+        )
+    };
+
+    let decode_elem = if elem_abi_ty.is_static() {
+        let elem_header_size = literal_expression! {(elem_abi_ty.header_size())};
+        statements! {
+            ([elem_data.ident()] := [provider.abi_decode_static(db, header_ptr.expr(), elem_ty, abi_loc)])
+            ([decoded_size.ident()] := add([decoded_size.expr()], [elem_header_size.clone()]))
+            ([header_ptr.ident()] := add([header_ptr.expr()], [elem_header_size]))
+        }
+    } else {
+        statements! {
+            ([data_ptr.ident()] := add([src.expr()], [provider.abi_decode_static(db, header_ptr.expr(), yul_primitive_type(db), abi_loc)]))
+            ([assignment! {[elem_data.ident()], [decoded_size_tmp.ident()] := [provider.abi_decode_dynamic(db, data_ptr.expr(), elem_ty, abi_loc)] }])
+            ([decoded_size.ident()] := add([decoded_size.expr()], (add([decoded_size_tmp.expr()], 32))))
+            ([header_ptr.ident()] := add([header_ptr.expr()], 32))
+        }
+    };
+
+    let func = function_definition! {
+        function [func_name.ident()]([src.ident()]) -> [decoded_data.ident()], [decoded_size.ident()] {
+            (let [len.ident()] := [provider.abi_decode_static(db, src.expr(), yul_primitive_type(db), abi_loc)])
+            (if (gt([len.expr()], [literal_expression! {(MAXIMUM_LEN)}])) { [revert_with_invalid_abi_data(provider, db)] })
+            ([src.ident()] := add([src.expr()], 32))
+            ([decoded_size.ident()] := 32)
+            (let [decoded_size_tmp.ident()] := 0)
+            (let [header_ptr.ident()] := [src.expr()])
+            (let [data_ptr.ident()] := 0)
+            (let [elem_data.ident()] := 0)
+
+            ([decoded_data.ident()] := [provider.alloc(db, literal_expression! {96})])
+            (let [data.ident()] := [provider.alloc(db, expression! {mul([len.expr()], [elem_ty_size.clone()])})])
+            (mstore([decoded_data.expr()], [len.expr()]))
+            (mstore((add([decoded_data.expr()], 32)), [len.expr()]))
+            (mstore((add([decoded_data.expr()], 64)), [data.expr()]))
+            (let [ret_elem_ptr.ident()] := [data.expr()])
+
+            (for {(let i := 0)} (lt(i, [len.expr()])) {(i := (add(i, 1)))}
+             {
+                 [decode_elem...]
+                 ([yul::Statement::Expression(copy_to_ret)])
+                 ([ret_elem_ptr.ident()] := add([ret_elem_ptr.expr()], [elem_ty_size]))
+             })
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
 fn make_abi_decode_dynamic_aggregate_type(
     provider: &mut DefaultRuntimeProvider,
     db: &dyn CodegenDb,
@@ -889,11 +1060,11 @@ enum AbiEncodingSize {
 
 fn abi_enc_size(db: &dyn CodegenDb, types: &[TypeId]) -> AbiEncodingSize {
     let mut min = 0;
-    let mut max = 0;
+    let mut max: usize = 0;
     for &ty in types {
         let legalized_ty = db.codegen_legalized_type(ty);
         min += db.codegen_abi_type_minimum_size(legalized_ty);
-        max += db.codegen_abi_type_maximum_size(legalized_ty);
+        max = max.saturating_add(db.codegen_abi_type_maximum_size(legalized_ty));
     }
 
     if min == max {
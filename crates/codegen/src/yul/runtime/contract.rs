@@ -17,19 +17,13 @@ pub(super) fn make_create(
     contract: ContractId,
 ) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
-    let contract_symbol = literal_expression! {
-        (format!(r#""{}""#, db.codegen_contract_deployer_symbol_name(contract)))
-    };
+    let (params, init_code) = make_init_code(provider, db, contract);
 
-    let size = YulVariable::new("size");
-    let value = YulVariable::new("value");
+    let value = YulVariable::new("$value");
     let func = function_definition! {
-        function [func_name.ident()]([value.ident()]) -> addr {
-            (let [size.ident()] := datasize([contract_symbol.clone()]))
-            (let mem_ptr := [provider.avail(db)])
-            (let contract_ptr := dataoffset([contract_symbol]))
-            (datacopy(mem_ptr, contract_ptr, [size.expr()]))
-            (addr := create([value.expr()], mem_ptr, [size.expr()]))
+        function [func_name.ident()]([value.ident()], [params...]) -> addr {
+            [init_code...]
+            (addr := create([value.expr()], mem_ptr, init_code_size))
         }
     };
 
@@ -43,23 +37,70 @@ pub(super) fn make_create2(
     contract: ContractId,
 ) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
+    let (params, init_code) = make_init_code(provider, db, contract);
+
+    let value = YulVariable::new("$value");
+    let salt = YulVariable::new("$salt");
+    let func = function_definition! {
+        function [func_name.ident()]([value.ident()], [salt.ident()], [params...]) -> addr {
+            [init_code...]
+            (addr := create2([value.expr()], mem_ptr, init_code_size, [salt.expr()]))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+/// Returns the parameters of the `__init__` function of `contract`, and the
+/// statements that copy the deployer of `contract` to `mem_ptr`, followed by
+/// the ABI encoded arguments. The size of the result is stored in
+/// `init_code_size`.
+fn make_init_code(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    contract: ContractId,
+) -> (Vec<yul::Identifier>, Vec<yul::Statement>) {
     let contract_symbol = literal_expression! {
         (format!(r#""{}""#, db.codegen_contract_deployer_symbol_name(contract)))
     };
 
-    let size = YulVariable::new("size");
-    let value = YulVariable::new("value");
-    let func = function_definition! {
-        function [func_name.ident()]([value.ident()], salt) -> addr {
-            (let [size.ident()] := datasize([contract_symbol.clone()]))
-            (let mem_ptr := [provider.avail(db)])
-            (let contract_ptr := dataoffset([contract_symbol]))
-            (datacopy(mem_ptr, contract_ptr, [size.expr()]))
-            (addr := create2([value.expr()], mem_ptr, [size.expr()], salt))
+    let init_sig = contract
+        .init_function(db.upcast())
+        .map(|init| db.codegen_legalized_signature(db.mir_lowered_func_signature(init)));
+    let mut args = vec![];
+    let mut arg_tys = vec![];
+    if let Some(sig) = &init_sig {
+        // The parameters are numbered so they can't shadow `$value`, `$salt`
+        // or `$size`.
+        for (i, param) in sig.params.iter().enumerate() {
+            args.push(YulVariable::new(format!("init_arg_{i}")));
+            arg_tys.push(param.ty);
         }
+    }
+    let params: Vec<_> = args.iter().map(YulVariable::ident).collect();
+    let params_expr: Vec<_> = args.iter().map(YulVariable::expr).collect();
+
+    let size = YulVariable::new("$size");
+    let mut init_code = statements! {
+        (let [size.ident()] := datasize([contract_symbol.clone()]))
+        (let mem_ptr := [provider.avail(db)])
+        (let contract_ptr := dataoffset([contract_symbol]))
+        (datacopy(mem_ptr, contract_ptr, [size.expr()]))
     };
+    if args.is_empty() {
+        init_code.push(statement! { let init_code_size := [size.expr()] });
+    } else {
+        let encoded_size = provider.abi_encode_seq(
+            db,
+            &params_expr,
+            expression! { add(mem_ptr, [size.expr()]) },
+            &arg_tys,
+            false,
+        );
+        init_code.push(statement! { let init_code_size := add([size.expr()], [encoded_size]) });
+    }
 
-    RuntimeFunction::from_statement(func)
+    (params, init_code)
 }
 
 pub(super) fn make_external_call(
@@ -79,7 +79,10 @@ pub(super) fn make_mcopym(func_name: &str) -> RuntimeFunction {
     RuntimeFunction::from_statement(func)
 }
 
-pub(super) fn make_mcopys(func_name: &str) -> RuntimeFunction {
+pub(super) fn make_mcopys(
+    provider: &mut DefaultRuntimeProvider,
+    func_name: &str,
+) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
     let src = YulVariable::new("src");
     let dst = YulVariable::new("dst");
@@ -87,7 +90,7 @@ pub(super) fn make_mcopys(func_name: &str) -> RuntimeFunction {
 
     let func = function_definition! {
         function [func_name.ident()]([src.ident()], [dst.ident()], [size.ident()]) {
-            ([dst.ident()] := div([dst.expr()], 32))
+            ([dst.ident()] := [provider.sptr_slot(dst.expr())])
             (let iter_count := div([size.expr()], 32))
             (let original_src := [src.expr()])
             (for {(let i := 0)} (lt(i, iter_count)) {(i := (add(i, 1)))}
@@ -112,7 +115,10 @@ pub(super) fn make_mcopys(func_name: &str) -> RuntimeFunction {
     RuntimeFunction::from_statement(func)
 }
 
-pub(super) fn make_scopym(func_name: &str) -> RuntimeFunction {
+pub(super) fn make_scopym(
+    provider: &mut DefaultRuntimeProvider,
+    func_name: &str,
+) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
     let src = YulVariable::new("src");
     let dst = YulVariable::new("dst");
@@ -120,7 +126,7 @@ pub(super) fn make_scopym(func_name: &str) -> RuntimeFunction {
 
     let func = function_definition! {
         function [func_name.ident()]([src.ident()], [dst.ident()], [size.ident()]) {
-            ([src.ident()] := div([src.expr()], 32))
+            ([src.ident()] := [provider.sptr_slot(src.expr())])
             (let iter_count := div([size.expr()], 32))
             (let original_dst := [dst.expr()])
             (for {(let i := 0)} (lt(i, iter_count)) {(i := (add(i, 1)))}
@@ -145,15 +151,18 @@ pub(super) fn make_scopym(func_name: &str) -> RuntimeFunction {
     RuntimeFunction::from_statement(func)
 }
 
-pub(super) fn make_scopys(func_name: &str) -> RuntimeFunction {
+pub(super) fn make_scopys(
+    provider: &mut DefaultRuntimeProvider,
+    func_name: &str,
+) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
     let src = YulVariable::new("src");
     let dst = YulVariable::new("dst");
     let size = YulVariable::new("size");
     let func = function_definition! {
         function [func_name.ident()]([src.ident()], [dst.ident()], [size.ident()]) {
-            ([src.ident()] := div([src.expr()], 32))
-            ([dst.ident()] := div([dst.expr()], 32))
+            ([src.ident()] := [provider.sptr_slot(src.expr())])
+            ([dst.ident()] := [provider.sptr_slot(dst.expr())])
             (let iter_count := div((add([size.expr()], 31)), 32))
             (for {(let i := 0)} (lt(i, iter_count)) {(i := (add(i, 1)))}
             {
@@ -167,15 +176,18 @@ pub(super) fn make_scopys(func_name: &str) -> RuntimeFunction {
     RuntimeFunction::from_statement(func)
 }
 
-pub(super) fn make_sptr_store(func_name: &str) -> RuntimeFunction {
+pub(super) fn make_sptr_store(
+    provider: &mut DefaultRuntimeProvider,
+    func_name: &str,
+) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
     let func = function_definition! {
         function [func_name.ident()](ptr, value, size_bits) {
-            (let rem_bits := mul((mod(ptr, 32)), 8))
+            (let rem_bits := mul([provider.sptr_byte(expression! {ptr})], 8))
             (let shift_bits := sub(256, (add(rem_bits, size_bits))))
             (let mask := (shl(shift_bits, (sub((shl(size_bits, 1)), 1)))))
             (let inv_mask := not(mask))
-            (let slot := div(ptr, 32))
+            (let slot := [provider.sptr_slot(expression! {ptr})])
             (let new_value := or((and((sload(slot)), inv_mask)), (and((shl(shift_bits, value)), mask))))
             (sstore(slot, new_value))
         }
@@ -198,13 +210,16 @@ pub(super) fn make_mptr_store(func_name: &str) -> RuntimeFunction {
     RuntimeFunction::from_statement(func)
 }
 
-pub(super) fn make_sptr_load(func_name: &str) -> RuntimeFunction {
+pub(super) fn make_sptr_load(
+    provider: &mut DefaultRuntimeProvider,
+    func_name: &str,
+) -> RuntimeFunction {
     let func_name = YulVariable::new(func_name);
     let func = function_definition! {
         function [func_name.ident()](ptr, size_bits) -> ret {
-            (let rem_bits := mul((mod(ptr, 32)), 8))
+            (let rem_bits := mul([provider.sptr_byte(expression! {ptr})], 8))
             (let shift_num := sub(256, (add(rem_bits, size_bits))))
-            (let slot := div(ptr, 32))
+            (let slot := [provider.sptr_slot(expression! {ptr})])
             (ret := shr(shift_num, (sload(slot))))
         }
     };
@@ -223,6 +238,91 @@ pub(super) fn make_mptr_load(func_name: &str) -> RuntimeFunction {
     RuntimeFunction::from_statement(func)
 }
 
+/// A storage pointer is a byte address `slot * 32 + offset`. Slots derived
+/// from `keccak256`, e.g. the elements of a storage `Vec`, don't fit into that
+/// form, so a pointer to them is a memory box holding the slot and the offset,
+/// tagged by the highest bit.
+const SPTR_BOX_TAG_BIT: usize = 255;
+
+pub(super) fn make_sptr_slot(func_name: &str) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let tag_bit = literal_expression! {(SPTR_BOX_TAG_BIT)};
+    let func = function_definition! {
+        function [func_name.ident()](ptr) -> slot {
+            (slot := shr(5, ptr))
+            (if (shr([tag_bit.clone()], ptr)) {
+                (slot := mload((and(ptr, (not((shl([tag_bit], 1))))))))
+            })
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_sptr_byte(func_name: &str) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let tag_bit = literal_expression! {(SPTR_BOX_TAG_BIT)};
+    let func = function_definition! {
+        function [func_name.ident()](ptr) -> ret {
+            (ret := and(ptr, 31))
+            (if (shr([tag_bit.clone()], ptr)) {
+                (ret := mload((add((and(ptr, (not((shl([tag_bit], 1)))))), 32))))
+            })
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_sptr_from_slot(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let tag_bit = literal_expression! {(SPTR_BOX_TAG_BIT)};
+    // `slot * 32` must leave the tag bit clear.
+    let max_slot_bits = literal_expression! {(SPTR_BOX_TAG_BIT - 5)};
+    let func = function_definition! {
+        function [func_name.ident()](slot, offset) -> ptr {
+            (ptr := or((shl(5, slot)), offset))
+            (if (shr([max_slot_bits], slot)) {
+                (let box_ptr := [provider.alloc(db, literal_expression! {(64)})])
+                (mstore(box_ptr, slot))
+                (mstore((add(box_ptr, 32)), offset))
+                (ptr := or(box_ptr, (shl([tag_bit], 1))))
+            })
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_sptr_offset(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let tag_bit = literal_expression! {(SPTR_BOX_TAG_BIT)};
+    let total = YulVariable::new("total");
+    let func = function_definition! {
+        function [func_name.ident()](ptr, offset) -> ret {
+            (ret := add(ptr, offset))
+            (if (or((shr([tag_bit.clone()], ptr)), (shr([tag_bit], ret)))) {
+                (let [total.ident()] := add([provider.sptr_byte(expression! {ptr})], offset))
+                (ret := [provider.sptr_from_slot(
+                    db,
+                    expression! {add([provider.sptr_slot(expression! {ptr})], (shr(5, [total.expr()])))},
+                    expression! {and([total.expr()], 31)},
+                )])
+            })
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
 // TODO: We can optimize aggregate initialization by combining multiple
 // `ptr_store` operations into single `ptr_store` operation.
 pub(super) fn make_aggregate_init(
@@ -251,7 +351,11 @@ pub(super) fn make_aggregate_init(
         let field_offset =
             literal_expression! {(inner_ty.aggregate_elem_offset(db.upcast(), idx, SLOT_SIZE))};
 
-        let field_ptr = expression! { add([ptr.expr()], [field_offset] )};
+        let field_ptr = if is_sptr {
+            provider.sptr_offset(db, ptr.expr(), field_offset)
+        } else {
+            expression! { add([ptr.expr()], [field_offset] )}
+        };
         let copy_expr = if field_ty.is_aggregate(db.upcast()) || field_ty.is_string(db.upcast()) {
             // Call ptr copy function if field type is aggregate.
             debug_assert!(field_arg_ty.is_ptr(db.upcast()));
@@ -319,8 +423,14 @@ pub(super) fn make_enum_init(
     let data_offset = legalized_ty
         .deref(db.upcast())
         .enum_data_offset(db.upcast(), SLOT_SIZE);
+    let data_offset = literal_expression! {(data_offset)};
+    let data_ptr = if is_sptr {
+        provider.sptr_offset(db, ptr.expr(), data_offset)
+    } else {
+        expression! { add([ptr.expr()], [data_offset]) }
+    };
     let enum_data_init = statements! {
-        [statement! {[ptr.ident()] := add([ptr.expr()], [literal_expression!{(data_offset)}])}]
+        [statement! {[ptr.ident()] := [data_ptr]}]
         [yul::Statement::Expression(provider.aggregate_init(
         db,
         ptr.expr(),
@@ -354,13 +464,15 @@ pub(super) fn make_string_copy(
         let data_size = YulVariable::new("data_size");
         function_definition! {
             function [func_name.ident()]([dst_ptr.ident()]) {
-                (let [tmp_ptr.ident()] := [provider.avail(db)])
                 (let data_offset := dataoffset([symbol_name.clone()]))
                 (let [data_size.ident()] := datasize([symbol_name]))
-                (let len_slot := div([dst_ptr.expr()], 32))
+                (let len_slot := [provider.sptr_slot(dst_ptr.expr())])
                 (sstore(len_slot, [data_size.expr()]))
+                // Moving the pointer may allocate, so it's done before the
+                // data is staged in the free memory.
+                ([dst_ptr.ident()] := [provider.sptr_offset(db, dst_ptr.expr(), literal_expression! {(32)})])
+                (let [tmp_ptr.ident()] := [provider.avail(db)])
                 (datacopy([tmp_ptr.expr()], data_offset, [data_size.expr()]))
-                ([dst_ptr.ident()] := add([dst_ptr.expr()], 32))
                 ([yul::Statement::Expression(
                     provider.ptr_copy(db, tmp_ptr.expr(), dst_ptr.expr(), data_size.expr(), false, true))
                 ])
@@ -415,7 +527,9 @@ pub(super) fn make_map_value_ptr_with_primitive_key(
     let key = YulVariable::new("key");
     let yul_primitive_type = yul_primitive_type(db);
 
-    let mask = BitMask::new(1).not();
+    // The two highest bits are cleared as well, so that the pointer never needs
+    // to be boxed.
+    let mask = BitMask::new(1).not().and(&BitMask::new(32).shr(2));
 
     let func = function_definition! {
         function [func_name.ident()]([map_ptr.ident()], [key.ident()]) -> ret {
@@ -459,3 +573,411 @@ pub(super) fn make_map_value_ptr_with_ptr_key(
     };
     RuntimeFunction::from_statement(def)
 }
+
+/// The byte size of the `Vec` header, which consists of the length, the
+/// capacity and the pointer to the element data.
+const VEC_HEADER_SIZE: usize = 96;
+
+pub(super) fn make_vec_new(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let vec = YulVariable::new("vec");
+    let header_size = literal_expression! {(VEC_HEADER_SIZE)};
+
+    let func = function_definition! {
+        function [func_name.ident()]() -> [vec.ident()] {
+            ([vec.ident()] := [provider.alloc(db, header_size)])
+            (mstore([vec.expr()], 0))
+            (mstore((add([vec.expr()], 32)), 0))
+            (mstore((add([vec.expr()], 64)), 0))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_vec_elem_ptr(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    const PANIC_OUT_OF_BOUNDS: usize = 0x32;
+
+    let func_name = YulVariable::new(func_name);
+    let vec = YulVariable::new("vec");
+    let idx = YulVariable::new("idx");
+    let elem_size = YulVariable::new("elem_size");
+    let ptr = YulVariable::new("ptr");
+
+    let func = function_definition! {
+        function [func_name.ident()]([vec.ident()], [idx.ident()], [elem_size.ident()]) -> [ptr.ident()] {
+            (if (iszero((lt([idx.expr()], (mload([vec.expr()])))))) {
+                ([super::panic_revert_numeric(provider, db, literal_expression! {(PANIC_OUT_OF_BOUNDS)})])
+            })
+            ([ptr.ident()] := add((mload((add([vec.expr()], 64)))), (mul([idx.expr()], [elem_size.expr()]))))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_vec_push(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+    vec_ty: TypeId,
+    value_ty: TypeId,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let vec = YulVariable::new("vec");
+    let value = YulVariable::new("value");
+    let len = YulVariable::new("len");
+    let new_cap = YulVariable::new("new_cap");
+    let new_data = YulVariable::new("new_data");
+    let elem_ptr = YulVariable::new("elem_ptr");
+
+    let elem_ty = match &vec_ty.data(db.upcast()).kind {
+        TypeKind::Vec(def) => def.elem_ty,
+        _ => unreachable!(),
+    };
+    let elem_size = literal_expression! {(vec_ty.array_elem_size(db.upcast(), SLOT_SIZE))};
+    let store_value = if elem_ty.is_aggregate(db.upcast()) || elem_ty.is_string(db.upcast()) {
+        let elem_ty_size = literal_expression! {(elem_ty.size_of(db.upcast(), SLOT_SIZE))};
+        provider.ptr_copy(
+            db,
+            value.expr(),
+            elem_ptr.expr(),
+            elem_ty_size,
+            value_ty.is_sptr(db.upcast()),
+            false,
+        )
+    } else {
+        provider.ptr_store(
+            db,
+            elem_ptr.expr(),
+            value.expr(),
+            elem_ty.make_mptr(db.upcast()),
+        )
+    };
+
+    let func = function_definition! {
+        function [func_name.ident()]([vec.ident()], [value.ident()]) {
+            (let [len.ident()] := mload([vec.expr()]))
+            (if (eq([len.expr()], (mload((add([vec.expr()], 32)))))) {
+                // Doubles the capacity and moves the elements to the new buffer.
+                (let [new_cap.ident()] := mul([len.expr()], 2))
+                (if (lt([new_cap.expr()], 4)) { ([new_cap.ident()] := 4) })
+                (let [new_data.ident()] := [provider.alloc(db, expression! {mul([new_cap.expr()], [elem_size.clone()])})])
+                ([yul::Statement::Expression(provider.ptr_copy(
+                    db,
+                    expression! {mload((add([vec.expr()], 64)))},
+                    new_data.expr(),
+                    expression! {mul([len.expr()], [elem_size.clone()])},
+                    false,
+                    false,
+                ))])
+                (mstore((add([vec.expr()], 32)), [new_cap.expr()]))
+                (mstore((add([vec.expr()], 64)), [new_data.expr()]))
+            })
+            (let [elem_ptr.ident()] := add((mload((add([vec.expr()], 64)))), (mul([len.expr()], [elem_size]))))
+            ([yul::Statement::Expression(store_value)])
+            (mstore([vec.expr()], (add([len.expr()], 1))))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_vec_pop(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    const PANIC_POP_EMPTY_ARRAY: usize = 0x31;
+
+    let func_name = YulVariable::new(func_name);
+    let vec = YulVariable::new("vec");
+    let elem_size = YulVariable::new("elem_size");
+    let len = YulVariable::new("len");
+    let ptr = YulVariable::new("ptr");
+
+    let func = function_definition! {
+        function [func_name.ident()]([vec.ident()], [elem_size.ident()]) -> [ptr.ident()] {
+            (let [len.ident()] := mload([vec.expr()]))
+            (if (iszero([len.expr()])) {
+                ([super::panic_revert_numeric(provider, db, literal_expression! {(PANIC_POP_EMPTY_ARRAY)})])
+            })
+            ([len.ident()] := sub([len.expr()], 1))
+            (mstore([vec.expr()], [len.expr()]))
+            ([ptr.ident()] := add((mload((add([vec.expr()], 64)))), (mul([len.expr()], [elem_size.expr()]))))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_vec_clone(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let src = YulVariable::new("src");
+    let elem_size = YulVariable::new("elem_size");
+    let dst = YulVariable::new("dst");
+    let len = YulVariable::new("len");
+    let data_size = YulVariable::new("data_size");
+    let data = YulVariable::new("data");
+    let header_size = literal_expression! {(VEC_HEADER_SIZE)};
+
+    let func = function_definition! {
+        function [func_name.ident()]([src.ident()], [elem_size.ident()]) -> [dst.ident()] {
+            (let [len.ident()] := mload([src.expr()]))
+            (let [data_size.ident()] := mul([len.expr()], [elem_size.expr()]))
+            ([dst.ident()] := [provider.alloc(db, header_size)])
+            (let [data.ident()] := [provider.alloc(db, data_size.expr())])
+            ([yul::Statement::Expression(provider.ptr_copy(
+                db,
+                expression! {mload((add([src.expr()], 64)))},
+                data.expr(),
+                data_size.expr(),
+                false,
+                false,
+            ))])
+            (mstore([dst.expr()], [len.expr()]))
+            (mstore((add([dst.expr()], 32)), [len.expr()]))
+            (mstore((add([dst.expr()], 64)), [data.expr()]))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+/// A storage `Vec` keeps its length in its own slot, and its elements are
+/// laid out contiguously from the slot `keccak256(slot)`, like a Solidity
+/// dynamic array.
+pub(super) fn make_svec_data_ptr(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let scratch_space = literal_expression! {(HASH_SCRATCH_SPACE_START)};
+    let vec = YulVariable::new("vec");
+    let ptr = YulVariable::new("ptr");
+
+    let func = function_definition! {
+        function [func_name.ident()]([vec.ident()]) -> [ptr.ident()] {
+            (mstore([scratch_space.clone()], [provider.sptr_slot(vec.expr())]))
+            ([ptr.ident()] := [provider.sptr_from_slot(db, expression! {keccak256([scratch_space], 32)}, literal_expression! {(0)})])
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_svec_elem_ptr(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    const PANIC_OUT_OF_BOUNDS: usize = 0x32;
+
+    let func_name = YulVariable::new(func_name);
+    let vec = YulVariable::new("vec");
+    let idx = YulVariable::new("idx");
+    let elem_size = YulVariable::new("elem_size");
+    let ptr = YulVariable::new("ptr");
+
+    let func = function_definition! {
+        function [func_name.ident()]([vec.ident()], [idx.ident()], [elem_size.ident()]) -> [ptr.ident()] {
+            (if (iszero((lt([idx.expr()], (sload([provider.sptr_slot(vec.expr())])))))) {
+                ([super::panic_revert_numeric(provider, db, literal_expression! {(PANIC_OUT_OF_BOUNDS)})])
+            })
+            (let data_ptr := [provider.svec_data_ptr(db, vec.expr())])
+            ([ptr.ident()] := [provider.sptr_offset(db, expression! {data_ptr}, expression! {mul([idx.expr()], [elem_size.expr()])})])
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_svec_push(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+    vec_ty: TypeId,
+    value_ty: TypeId,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let vec = YulVariable::new("vec");
+    let value = YulVariable::new("value");
+    let slot = YulVariable::new("slot");
+    let len = YulVariable::new("len");
+    let elem_ptr = YulVariable::new("elem_ptr");
+
+    let elem_ty = match &vec_ty.data(db.upcast()).kind {
+        TypeKind::Vec(def) => def.elem_ty,
+        _ => unreachable!(),
+    };
+    let elem_size = literal_expression! {(vec_ty.array_elem_size(db.upcast(), SLOT_SIZE))};
+    let store_value = if elem_ty.is_aggregate(db.upcast()) || elem_ty.is_string(db.upcast()) {
+        let elem_ty_size = literal_expression! {(elem_ty.size_of(db.upcast(), SLOT_SIZE))};
+        provider.ptr_copy(
+            db,
+            value.expr(),
+            elem_ptr.expr(),
+            elem_ty_size,
+            value_ty.is_sptr(db.upcast()),
+            true,
+        )
+    } else {
+        provider.ptr_store(
+            db,
+            elem_ptr.expr(),
+            value.expr(),
+            elem_ty.make_sptr(db.upcast()),
+        )
+    };
+
+    let func = function_definition! {
+        function [func_name.ident()]([vec.ident()], [value.ident()]) {
+            (let [slot.ident()] := [provider.sptr_slot(vec.expr())])
+            (let [len.ident()] := sload([slot.expr()]))
+            (let data_ptr := [provider.svec_data_ptr(db, vec.expr())])
+            (let [elem_ptr.ident()] := [provider.sptr_offset(db, expression! {data_ptr}, expression! {mul([len.expr()], [elem_size])})])
+            ([yul::Statement::Expression(store_value)])
+            (sstore([slot.expr()], (add([len.expr()], 1))))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_svec_pop(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    const PANIC_POP_EMPTY_ARRAY: usize = 0x31;
+
+    let func_name = YulVariable::new(func_name);
+    let vec = YulVariable::new("vec");
+    let elem_size = YulVariable::new("elem_size");
+    let slot = YulVariable::new("slot");
+    let len = YulVariable::new("len");
+    let ptr = YulVariable::new("ptr");
+
+    let func = function_definition! {
+        function [func_name.ident()]([vec.ident()], [elem_size.ident()]) -> [ptr.ident()] {
+            (let [slot.ident()] := [provider.sptr_slot(vec.expr())])
+            (let [len.ident()] := sload([slot.expr()]))
+            (if (iszero([len.expr()])) {
+                ([super::panic_revert_numeric(provider, db, literal_expression! {(PANIC_POP_EMPTY_ARRAY)})])
+            })
+            ([len.ident()] := sub([len.expr()], 1))
+            (sstore([slot.expr()], [len.expr()]))
+            (let data_ptr := [provider.svec_data_ptr(db, vec.expr())])
+            ([ptr.ident()] := [provider.sptr_offset(db, expression! {data_ptr}, expression! {mul([len.expr()], [elem_size.expr()])})])
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_svec_to_mem(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let src = YulVariable::new("src");
+    let elem_size = YulVariable::new("elem_size");
+    let dst = YulVariable::new("dst");
+    let len = YulVariable::new("len");
+    let data_size = YulVariable::new("data_size");
+    let data = YulVariable::new("data");
+    let header_size = literal_expression! {(VEC_HEADER_SIZE)};
+
+    let func = function_definition! {
+        function [func_name.ident()]([src.ident()], [elem_size.ident()]) -> [dst.ident()] {
+            (let [len.ident()] := sload([provider.sptr_slot(src.expr())]))
+            (let [data_size.ident()] := mul([len.expr()], [elem_size.expr()]))
+            ([dst.ident()] := [provider.alloc(db, header_size)])
+            (let [data.ident()] := [provider.alloc(db, data_size.expr())])
+            ([yul::Statement::Expression(provider.ptr_copy(
+                db,
+                provider.svec_data_ptr(db, src.expr()),
+                data.expr(),
+                data_size.expr(),
+                true,
+                false,
+            ))])
+            (mstore([dst.expr()], [len.expr()]))
+            (mstore((add([dst.expr()], 32)), [len.expr()]))
+            (mstore((add([dst.expr()], 64)), [data.expr()]))
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
+
+pub(super) fn make_vec_to_storage(
+    provider: &mut DefaultRuntimeProvider,
+    db: &dyn CodegenDb,
+    func_name: &str,
+) -> RuntimeFunction {
+    let func_name = YulVariable::new(func_name);
+    let src = YulVariable::new("src");
+    let dst = YulVariable::new("dst");
+    let elem_size = YulVariable::new("elem_size");
+    let len = YulVariable::new("len");
+    let old_len = YulVariable::new("old_len");
+    let data = YulVariable::new("data");
+    let data_slot = YulVariable::new("data_slot");
+    let end = YulVariable::new("end");
+    let old_end = YulVariable::new("old_end");
+    let rem_bits = YulVariable::new("rem_bits");
+    let slot = YulVariable::new("slot");
+
+    // The elements beyond the new length are cleared, so that they read as
+    // zero if the `Vec` grows again. Packed elements may share the last slot
+    // with the live ones, so only the bytes after `end` are cleared there.
+    let func = function_definition! {
+        function [func_name.ident()]([src.ident()], [dst.ident()], [elem_size.ident()]) {
+            (let [len.ident()] := mload([src.expr()]))
+            (let [old_len.ident()] := sload([provider.sptr_slot(dst.expr())]))
+            (let [data.ident()] := [provider.svec_data_ptr(db, dst.expr())])
+            (sstore([provider.sptr_slot(dst.expr())], [len.expr()]))
+            ([yul::Statement::Expression(provider.ptr_copy(
+                db,
+                expression! {mload((add([src.expr()], 64)))},
+                data.expr(),
+                expression! {mul([len.expr()], [elem_size.expr()])},
+                false,
+                true,
+            ))])
+            (let [end.ident()] := mul([len.expr()], [elem_size.expr()]))
+            (let [old_end.ident()] := mul([old_len.expr()], [elem_size.expr()]))
+            (if (gt([old_end.expr()], [end.expr()])) {
+                (let [data_slot.ident()] := [provider.sptr_slot(data.expr())])
+                (let [slot.ident()] := div([end.expr()], 32))
+                (let [rem_bits.ident()] := mul((mod([end.expr()], 32)), 8))
+                (if [rem_bits.expr()] {
+                    (let partial := add([data_slot.expr()], [slot.expr()]))
+                    (sstore(partial, (and((sload(partial)), (not((sub((shl((sub(256, [rem_bits.expr()])), 1)), 1))))))))
+                    ([slot.ident()] := add([slot.expr()], 1))
+                })
+                (for {} (lt([slot.expr()], (div((add([old_end.expr()], 31)), 32)))) {([slot.ident()] := add([slot.expr()], 1))}
+                {
+                    (sstore((add([data_slot.expr()], [slot.expr()])), 0))
+                })
+            })
+        }
+    };
+
+    RuntimeFunction::from_statement(func)
+}
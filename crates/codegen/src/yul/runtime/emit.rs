@@ -18,6 +18,22 @@ pub(super) fn make_emit(
     let func_name = YulVariable::new(func_name);
     let event_ptr = YulVariable::new("event_ptr");
     let deref_ty = legalized_ty.deref(db.upcast());
+    let is_sptr = legalized_ty.is_sptr(db.upcast());
+
+    // The field pointers are computed up front, because a storage pointer may
+    // allocate memory that would overlap the encoded event data.
+    let mut field_ptrs = vec![];
+    let field_ptr = |idx: usize| YulVariable::new(format!("field{idx}"));
+    for idx in 0..deref_ty.aggregate_field_num(db.upcast()) {
+        let offset =
+            literal_expression! {(deref_ty.aggregate_elem_offset(db.upcast(), idx, SLOT_SIZE))};
+        let ptr = if is_sptr {
+            provider.sptr_offset(db, event_ptr.expr(), offset)
+        } else {
+            expression! { add([event_ptr.expr()], [offset]) }
+        };
+        field_ptrs.push(statement! { let [field_ptr(idx).ident()] := [ptr] });
+    }
 
     let abi = db.codegen_abi_event(deref_ty);
     let mut topics = vec![literal_expression! {(format!("0x{}", abi.signature().hash_hex()))}];
@@ -26,17 +42,11 @@ pub(super) fn make_emit(
             continue;
         }
         let field_ty = deref_ty.projection_ty_imm(db.upcast(), idx);
-        let offset =
-            literal_expression! {(deref_ty.aggregate_elem_offset(db.upcast(), idx, SLOT_SIZE))};
-        let elem_ptr = expression! { add([event_ptr.expr()], [offset]) };
+        let elem_ptr = field_ptr(idx).expr();
         let topic = if field_ty.is_aggregate(db.upcast()) {
             todo!()
         } else {
-            let topic = provider.ptr_load(
-                db,
-                elem_ptr,
-                make_ptr(db, field_ty, legalized_ty.is_sptr(db.upcast())),
-            );
+            let topic = provider.ptr_load(db, elem_ptr, make_ptr(db, field_ty, is_sptr));
             provider.primitive_cast(db, topic, field_ty)
         };
 
@@ -51,10 +61,8 @@ pub(super) fn make_emit(
         }
 
         let field_ty = deref_ty.projection_ty_imm(db.upcast(), idx);
-        let field_offset =
-            literal_expression! { (deref_ty.aggregate_elem_offset(db.upcast(), idx, SLOT_SIZE)) };
-        event_data_tys.push(make_ptr(db, field_ty, legalized_ty.is_sptr(db.upcast())));
-        event_data_values.push(expression! { add([event_ptr.expr()], [field_offset]) });
+        event_data_tys.push(make_ptr(db, field_ty, is_sptr));
+        event_data_values.push(field_ptr(idx).expr());
     }
 
     debug_assert!(topics.len() < 5);
@@ -64,6 +72,7 @@ pub(super) fn make_emit(
     let event_enc_size = YulVariable::new("event_enc_size");
     let func = function_definition! {
         function [func_name.ident()]([event_ptr.ident()]) {
+            [field_ptrs...]
             (let [event_data_ptr.ident()] := [provider.avail(db)])
             (let [event_enc_size.ident()] := [provider.abi_encode_seq(db, &event_data_values, event_data_ptr.expr(), &event_data_tys, false )])
             ([log_func]([event_data_ptr.expr()], [event_enc_size.expr()], [topics...]))
@@ -31,6 +31,7 @@ pub trait RuntimeProvider {
         db: &dyn CodegenDb,
         contract: ContractId,
         value: yul::Expression,
+        args: Vec<yul::Expression>,
     ) -> yul::Expression;
 
     fn create2(
@@ -39,6 +40,7 @@ pub trait RuntimeProvider {
         contract: ContractId,
         value: yul::Expression,
         salt: yul::Expression,
+        args: Vec<yul::Expression>,
     ) -> yul::Expression;
 
     fn emit(
@@ -71,6 +73,50 @@ pub trait RuntimeProvider {
         key_ty: TypeId,
     ) -> yul::Expression;
 
+    fn vec_new(&mut self, db: &dyn CodegenDb) -> yul::Expression;
+
+    fn vec_elem_ptr(
+        &mut self,
+        db: &dyn CodegenDb,
+        vec: yul::Expression,
+        idx: yul::Expression,
+        vec_ty: TypeId,
+    ) -> yul::Expression;
+
+    fn vec_push(
+        &mut self,
+        db: &dyn CodegenDb,
+        vec: yul::Expression,
+        value: yul::Expression,
+        vec_ty: TypeId,
+        value_ty: TypeId,
+    ) -> yul::Expression;
+
+    fn vec_pop(
+        &mut self,
+        db: &dyn CodegenDb,
+        vec: yul::Expression,
+        vec_ty: TypeId,
+    ) -> yul::Expression;
+
+    /// Copies the elements of `vec` into a new memory `Vec`. `vec` is either a
+    /// memory `Vec` or a pointer to a storage `Vec`.
+    fn vec_clone(
+        &mut self,
+        db: &dyn CodegenDb,
+        vec: yul::Expression,
+        vec_ty: TypeId,
+    ) -> yul::Expression;
+
+    /// Copies the memory `Vec` `src` into the storage `Vec` pointed to by `dst`.
+    fn vec_to_storage(
+        &mut self,
+        db: &dyn CodegenDb,
+        src: yul::Expression,
+        dst: yul::Expression,
+        vec_ty: TypeId,
+    ) -> yul::Expression;
+
     fn aggregate_init(
         &mut self,
         db: &dyn CodegenDb,
@@ -115,6 +161,14 @@ pub trait RuntimeProvider {
         ptr_ty: TypeId,
     ) -> yul::Expression;
 
+    /// Returns the storage pointer `offset` bytes after `ptr`.
+    fn sptr_offset(
+        &mut self,
+        db: &dyn CodegenDb,
+        ptr: yul::Expression,
+        offset: yul::Expression,
+    ) -> yul::Expression;
+
     fn ptr_load(
         &mut self,
         db: &dyn CodegenDb,
@@ -247,6 +301,35 @@ impl DefaultRuntimeProvider {
             result
         }
     }
+
+    fn svec_data_ptr(&mut self, db: &dyn CodegenDb, vec: yul::Expression) -> yul::Expression {
+        let name = "$svec_data_ptr";
+        self.create_then_call(name, vec![vec], |provider| {
+            data::make_svec_data_ptr(provider, db, name)
+        })
+    }
+
+    fn sptr_slot(&mut self, ptr: yul::Expression) -> yul::Expression {
+        let name = "$sptr_slot";
+        self.create_then_call(name, vec![ptr], |_| data::make_sptr_slot(name))
+    }
+
+    fn sptr_byte(&mut self, ptr: yul::Expression) -> yul::Expression {
+        let name = "$sptr_byte";
+        self.create_then_call(name, vec![ptr], |_| data::make_sptr_byte(name))
+    }
+
+    fn sptr_from_slot(
+        &mut self,
+        db: &dyn CodegenDb,
+        slot: yul::Expression,
+        offset: yul::Expression,
+    ) -> yul::Expression {
+        let name = "$sptr_from_slot";
+        self.create_then_call(name, vec![slot, offset], |provider| {
+            data::make_sptr_from_slot(provider, db, name)
+        })
+    }
 }
 
 impl RuntimeProvider for DefaultRuntimeProvider {
@@ -274,9 +357,11 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         db: &dyn CodegenDb,
         contract: ContractId,
         value: yul::Expression,
+        args: Vec<yul::Expression>,
     ) -> yul::Expression {
         let name = format!("$create_{}", db.codegen_contract_symbol_name(contract));
-        let arg = vec![value];
+        let mut arg = vec![value];
+        arg.extend(args);
         self.create_then_call(&name, arg, |provider| {
             contract::make_create(provider, db, &name, contract)
         })
@@ -288,9 +373,11 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         contract: ContractId,
         value: yul::Expression,
         salt: yul::Expression,
+        args: Vec<yul::Expression>,
     ) -> yul::Expression {
         let name = format!("$create2_{}", db.codegen_contract_symbol_name(contract));
-        let arg = vec![value, salt];
+        let mut arg = vec![value, salt];
+        arg.extend(args);
         self.create_then_call(&name, arg, |provider| {
             contract::make_create2(provider, db, &name, contract)
         })
@@ -363,6 +450,111 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         }
     }
 
+    fn vec_new(&mut self, db: &dyn CodegenDb) -> yul::Expression {
+        let name = "$vec_new";
+        self.create_then_call(name, vec![], |provider| {
+            data::make_vec_new(provider, db, name)
+        })
+    }
+
+    fn vec_elem_ptr(
+        &mut self,
+        db: &dyn CodegenDb,
+        vec: yul::Expression,
+        idx: yul::Expression,
+        vec_ty: TypeId,
+    ) -> yul::Expression {
+        let elem_size = literal_expression! {(vec_ty.deref(db.upcast()).array_elem_size(db.upcast(), SLOT_SIZE))};
+        let args = vec![vec, idx, elem_size];
+        if vec_ty.is_sptr(db.upcast()) {
+            let name = "$svec_elem_ptr";
+            self.create_then_call(name, args, |provider| {
+                data::make_svec_elem_ptr(provider, db, name)
+            })
+        } else {
+            let name = "$vec_elem_ptr";
+            self.create_then_call(name, args, |provider| {
+                data::make_vec_elem_ptr(provider, db, name)
+            })
+        }
+    }
+
+    fn vec_push(
+        &mut self,
+        db: &dyn CodegenDb,
+        vec: yul::Expression,
+        value: yul::Expression,
+        vec_ty: TypeId,
+        value_ty: TypeId,
+    ) -> yul::Expression {
+        let legalized_ty = db.codegen_legalized_type(vec_ty.deref(db.upcast()));
+        if vec_ty.is_sptr(db.upcast()) {
+            let name = format!("$svec_push_{}_{}", legalized_ty.0, value_ty.0);
+            self.create_then_call(&name, vec![vec, value], |provider| {
+                data::make_svec_push(provider, db, &name, legalized_ty, value_ty)
+            })
+        } else {
+            let name = format!("$vec_push_{}_{}", legalized_ty.0, value_ty.0);
+            self.create_then_call(&name, vec![vec, value], |provider| {
+                data::make_vec_push(provider, db, &name, legalized_ty, value_ty)
+            })
+        }
+    }
+
+    fn vec_pop(
+        &mut self,
+        db: &dyn CodegenDb,
+        vec: yul::Expression,
+        vec_ty: TypeId,
+    ) -> yul::Expression {
+        let elem_size = literal_expression! {(vec_ty.deref(db.upcast()).array_elem_size(db.upcast(), SLOT_SIZE))};
+        if vec_ty.is_sptr(db.upcast()) {
+            let name = "$svec_pop";
+            self.create_then_call(name, vec![vec, elem_size], |provider| {
+                data::make_svec_pop(provider, db, name)
+            })
+        } else {
+            let name = "$vec_pop";
+            self.create_then_call(name, vec![vec, elem_size], |provider| {
+                data::make_vec_pop(provider, db, name)
+            })
+        }
+    }
+
+    fn vec_clone(
+        &mut self,
+        db: &dyn CodegenDb,
+        vec: yul::Expression,
+        vec_ty: TypeId,
+    ) -> yul::Expression {
+        let elem_size = literal_expression! {(vec_ty.deref(db.upcast()).array_elem_size(db.upcast(), SLOT_SIZE))};
+        if vec_ty.is_sptr(db.upcast()) {
+            let name = "$svec_to_mem";
+            self.create_then_call(name, vec![vec, elem_size], |provider| {
+                data::make_svec_to_mem(provider, db, name)
+            })
+        } else {
+            let name = "$vec_clone";
+            self.create_then_call(name, vec![vec, elem_size], |provider| {
+                data::make_vec_clone(provider, db, name)
+            })
+        }
+    }
+
+    fn vec_to_storage(
+        &mut self,
+        db: &dyn CodegenDb,
+        src: yul::Expression,
+        dst: yul::Expression,
+        vec_ty: TypeId,
+    ) -> yul::Expression {
+        let name = "$vec_to_storage";
+        let elem_size = literal_expression! {(vec_ty.deref(db.upcast()).array_elem_size(db.upcast(), SLOT_SIZE))};
+        self.create_then_call(name, vec![src, dst, elem_size], |provider| {
+            data::make_vec_to_storage(provider, db, name)
+        })
+    }
+
     fn aggregate_init(
         &mut self,
         db: &dyn CodegenDb,
@@ -452,15 +644,15 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         match (is_src_storage, is_dst_storage) {
             (true, true) => {
                 let name = "scopys";
-                self.create_then_call(name, args, |_| data::make_scopys(name))
+                self.create_then_call(name, args, |provider| data::make_scopys(provider, name))
             }
             (true, false) => {
                 let name = "scopym";
-                self.create_then_call(name, args, |_| data::make_scopym(name))
+                self.create_then_call(name, args, |provider| data::make_scopym(provider, name))
             }
             (false, true) => {
                 let name = "mcopys";
-                self.create_then_call(name, args, |_| data::make_mcopys(name))
+                self.create_then_call(name, args, |provider| data::make_mcopys(provider, name))
             }
             (false, false) => {
                 let name = "mcopym";
@@ -484,7 +676,7 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         if ptr_ty.is_sptr(db.upcast()) {
             let name = "$sptr_store";
             let args = vec![ptr, imm, literal_expression! {(size_bits)}];
-            self.create_then_call(name, args, |_| data::make_sptr_store(name))
+            self.create_then_call(name, args, |provider| data::make_sptr_store(provider, name))
         } else if ptr_ty.is_mptr(db.upcast()) {
             let name = "$mptr_store";
             let shift_num = literal_expression! {(256 - size_bits)};
@@ -496,6 +688,18 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         }
     }
 
+    fn sptr_offset(
+        &mut self,
+        db: &dyn CodegenDb,
+        ptr: yul::Expression,
+        offset: yul::Expression,
+    ) -> yul::Expression {
+        let name = "$sptr_offset";
+        self.create_then_call(name, vec![ptr, offset], |provider| {
+            data::make_sptr_offset(provider, db, name)
+        })
+    }
+
     fn ptr_load(
         &mut self,
         db: &dyn CodegenDb,
@@ -510,7 +714,7 @@ impl RuntimeProvider for DefaultRuntimeProvider {
         if ptr_ty.is_sptr(db.upcast()) {
             let name = "$sptr_load";
             let args = vec![ptr, literal_expression! {(size_bits)}];
-            self.create_then_call(name, args, |_| data::make_sptr_load(name))
+            self.create_then_call(name, args, |provider| data::make_sptr_load(provider, name))
         } else if ptr_ty.is_mptr(db.upcast()) {
             let name = "$mptr_load";
             let shift_num = literal_expression! {(256 - size_bits)};
@@ -576,6 +780,12 @@ impl RuntimeProvider for DefaultRuntimeProvider {
                     })
                 }
             }
+            AbiType::DynArray { .. } => {
+                let name = format!("$abi_encode_vec_type_{}_to_{}", src_ty.0, func_name_postfix);
+                self.create_then_call(&name, args, |provider| {
+                    abi::make_abi_encode_vec_type(provider, db, &name, legalized_ty, is_dst_storage)
+                })
+            }
             AbiType::Tuple(_) => {
                 if abi_ty.is_static() {
                     let name = format!(
@@ -623,7 +833,7 @@ impl RuntimeProvider for DefaultRuntimeProvider {
                     abi::make_abi_encode_string_type(provider, db, &name, is_dst_storage)
                 })
             }
-            AbiType::Function => unreachable!(),
+            AbiType::Function | AbiType::FixedBytes(_) | AbiType::Fixed { .. } => unreachable!(),
         }
     }
 
@@ -770,7 +980,7 @@ impl RuntimeFunction {
     }
 }
 
-fn make_ptr(db: &dyn CodegenDb, inner: TypeId, is_sptr: bool) -> TypeId {
+pub(super) fn make_ptr(db: &dyn CodegenDb, inner: TypeId, is_sptr: bool) -> TypeId {
     if is_sptr {
         inner.make_sptr(db.upcast())
     } else {
@@ -795,6 +1005,14 @@ impl BitMask {
         Self(u256_max ^ &self.0)
     }
 
+    fn and(&self, rhs: &Self) -> Self {
+        Self(&self.0 & &rhs.0)
+    }
+
+    fn shr(&self, bits: usize) -> Self {
+        Self(&self.0 >> bits)
+    }
+
     fn as_expr(&self) -> yul::Expression {
         let mask = format!("{:#x}", self.0);
         literal_expression! {(mask)}
@@ -5,8 +5,8 @@ use crate::{
 
 use super::{DefaultRuntimeProvider, RuntimeFunction, RuntimeProvider};
 
-use fe_abi::function::{AbiFunction, AbiFunctionType, StateMutability};
-use fe_mir::ir::{self, TypeId};
+use fe_abi::error::{AbiError, AbiErrorField};
+use fe_mir::ir::TypeId;
 use yultsur::*;
 
 pub(super) fn make_revert(
@@ -63,28 +63,17 @@ pub(super) fn make_revert(
 /// Returns signature hash of the type.
 fn type_signature_for_revert(db: &dyn CodegenDb, name: &str, ty: TypeId) -> yul::Expression {
     let deref_ty = ty.deref(db.upcast());
-    let ty_data = deref_ty.data(db.upcast());
-    let args = match &ty_data.kind {
-        ir::TypeKind::Struct(def) => def
-            .fields
-            .iter()
-            .map(|(_, ty)| ("".to_string(), db.codegen_abi_type(*ty)))
-            .collect(),
-        _ => {
-            let abi_ty = db.codegen_abi_type(deref_ty);
-            vec![("_".to_string(), abi_ty)]
-        }
+    // Struct errors use the same selector as their entry in the contract ABI.
+    let error = if deref_ty.is_struct(db.upcast()) {
+        db.codegen_abi_error(deref_ty)
+    } else {
+        let abi_ty = db.codegen_abi_type(deref_ty);
+        AbiError::new(
+            name.to_string(),
+            vec![AbiErrorField::new("_".into(), abi_ty)],
+        )
     };
 
-    // selector and state mutability is independent we can set has_self and has_ctx any value.
-    let selector = AbiFunction::new(
-        AbiFunctionType::Function,
-        name.to_string(),
-        args,
-        None,
-        StateMutability::Pure,
-    )
-    .selector();
-    let type_sig = selector.hex();
+    let type_sig = error.selector().hex();
     literal_expression! {(format!{"0x{type_sig}" })}
 }
@@ -5,6 +5,8 @@ pub use codespan_reporting::diagnostic as cs;
 use codespan_reporting::files::Error as CsError;
 use codespan_reporting::term;
 pub use cs::Severity;
+use serde::Serialize;
+use serde_json::json;
 use std::ops::Range;
 use std::rc::Rc;
 use term::termcolor::{BufferWriter, ColorChoice};
@@ -26,6 +28,10 @@ impl Diagnostic {
             notes: self.notes,
         }
     }
+    /// Errors stop compilation, warnings don't.
+    pub fn is_error(&self) -> bool {
+        matches!(self.severity, Severity::Bug | Severity::Error)
+    }
     pub fn error(message: String) -> Self {
         Self {
             severity: Severity::Error,
@@ -116,6 +122,217 @@ pub fn diagnostics_string(db: &dyn SourceDb, diagnostics: &[Diagnostic]) -> Stri
     std::str::from_utf8(buffer.as_slice()).unwrap().to_string()
 }
 
+/// The format in which diagnostics are reported.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum DiagnosticFormat {
+    /// Colored, human-readable output rendered by `codespan_reporting`.
+    Human,
+    /// One JSON object per diagnostic, separated by newlines.
+    /// See [`JsonDiagnostic`] for the structure of each object.
+    Json,
+    /// A single SARIF 2.1.0 log containing every diagnostic.
+    Sarif,
+}
+
+/// Print the given diagnostics in the given format. Human-readable output is
+/// printed to stderr, machine-readable output to stdout.
+///
+/// Note that a SARIF log is printed even if there are no diagnostics.
+pub fn print_diagnostics_in_format(
+    db: &dyn SourceDb,
+    diagnostics: &[Diagnostic],
+    format: DiagnosticFormat,
+) {
+    match format {
+        DiagnosticFormat::Human if diagnostics.is_empty() => {}
+        DiagnosticFormat::Human => print_diagnostics(db, diagnostics),
+        DiagnosticFormat::Json => print!("{}", diagnostics_json(db, diagnostics)),
+        DiagnosticFormat::Sarif => println!("{}", diagnostics_sarif(db, diagnostics)),
+    }
+}
+
+/// The JSON representation of a [`Diagnostic`].
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct JsonDiagnostic {
+    /// One of `"bug"`, `"error"`, `"warning"`, `"note"` or `"help"`.
+    pub severity: &'static str,
+    pub message: String,
+    pub labels: Vec<JsonLabel>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct JsonLabel {
+    /// Either `"primary"` or `"secondary"`.
+    pub style: &'static str,
+    pub message: String,
+    /// `None` if the label doesn't point into a source file.
+    pub file: Option<String>,
+    pub start: Option<JsonPosition>,
+    pub end: Option<JsonPosition>,
+}
+
+/// A position in a source file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub struct JsonPosition {
+    /// Zero-based byte offset.
+    pub byte: usize,
+    /// One-based line number.
+    pub line: usize,
+    /// One-based column number, counted in unicode code points.
+    pub column: usize,
+}
+
+impl JsonDiagnostic {
+    pub fn new(db: &dyn SourceDb, diagnostic: &Diagnostic) -> Self {
+        JsonDiagnostic {
+            severity: severity_name(diagnostic.severity),
+            message: diagnostic.message.clone(),
+            labels: diagnostic
+                .labels
+                .iter()
+                .map(|label| JsonLabel::new(db, label))
+                .collect(),
+            notes: diagnostic.notes.clone(),
+        }
+    }
+}
+
+impl JsonLabel {
+    pub fn new(db: &dyn SourceDb, label: &Label) -> Self {
+        let style = match label.style {
+            LabelStyle::Primary => "primary",
+            LabelStyle::Secondary => "secondary",
+        };
+        let span = label.span;
+        let (file, start, end) = if span.file_id.is_dummy() {
+            (None, None, None)
+        } else {
+            (
+                Some(span.file_id.path(db).to_string()),
+                Some(JsonPosition::new(db, span.file_id, span.start)),
+                Some(JsonPosition::new(db, span.file_id, span.end)),
+            )
+        };
+
+        JsonLabel {
+            style,
+            message: label.message.clone(),
+            file,
+            start,
+            end,
+        }
+    }
+}
+
+impl JsonPosition {
+    pub fn new(db: &dyn SourceDb, file: SourceFileId, byte: usize) -> Self {
+        let content = file.content(db);
+        let byte = byte.min(content.len());
+        let line = file.line_index(db, byte);
+        let line_start = file.line_range(db, line).map_or(0, |range| range.start);
+        let column = content
+            .get(line_start..byte)
+            .map_or(0, |prefix| prefix.chars().count());
+
+        JsonPosition {
+            byte,
+            line: line + 1,
+            column: column + 1,
+        }
+    }
+}
+
+/// Format the given diagnostics as JSON, one object per line.
+pub fn diagnostics_json(db: &dyn SourceDb, diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diag| {
+            let json = serde_json::to_string(&JsonDiagnostic::new(db, diag))
+                .expect("failed to serialize diagnostic");
+            json + "\n"
+        })
+        .collect()
+}
+
+/// Format the given diagnostics as a [SARIF](https://sarifweb.azurewebsites.net/)
+/// 2.1.0 log.
+pub fn diagnostics_sarif(db: &dyn SourceDb, diagnostics: &[Diagnostic]) -> String {
+    let results = diagnostics
+        .iter()
+        .map(|diag| JsonDiagnostic::new(db, diag))
+        .map(|diag| {
+            let level = match diag.severity {
+                "bug" | "error" => "error",
+                "warning" => "warning",
+                _ => "note",
+            };
+            let text = std::iter::once(diag.message.as_str())
+                .chain(diag.notes.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let (primary, secondary): (Vec<_>, Vec<_>) = diag
+                .labels
+                .iter()
+                .filter(|label| label.file.is_some())
+                .partition(|label| label.style == "primary");
+
+            json!({
+                "level": level,
+                "message": { "text": text },
+                "locations": primary.into_iter().map(sarif_location).collect::<Vec<_>>(),
+                "relatedLocations": secondary.into_iter().map(sarif_location).collect::<Vec<_>>(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "fe",
+                    "informationUri": "https://github.com/ethereum/fe",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&log).expect("failed to serialize SARIF log")
+}
+
+fn sarif_location(label: &JsonLabel) -> serde_json::Value {
+    let start = label.start.expect("label without position");
+    let end = label.end.expect("label without position");
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": label.file },
+            "region": {
+                "startLine": start.line,
+                "startColumn": start.column,
+                "endLine": end.line,
+                "endColumn": end.column,
+                "byteOffset": start.byte,
+                "byteLength": end.byte - start.byte,
+            }
+        },
+        "message": { "text": label.message },
+    })
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
 struct SourceDbWrapper<'a>(pub &'a dyn SourceDb);
 
 impl<'a> codespan_reporting::files::Files<'_> for SourceDbWrapper<'a> {
@@ -143,3 +360,46 @@ impl<'a> codespan_reporting::files::Files<'_> for SourceDbWrapper<'a> {
             })
     }
 }
+
+#[test]
+fn test_diagnostics_json() {
+    use crate::db::TestDb;
+    use crate::files::FileKind;
+
+    let mut db = TestDb::default();
+    let file = SourceFileId::new(
+        &mut db,
+        FileKind::Local,
+        "src/main.fe",
+        "contract Foo {\n    x: ü8\n}\n".into(),
+    );
+    let diag = Diagnostic {
+        severity: Severity::Error,
+        message: "undefined type".into(),
+        labels: vec![Label::primary(Span::new(file, 22, 25), "not defined")],
+        notes: vec!["note: types are case sensitive".into()],
+    };
+
+    assert_eq!(
+        diagnostics_json(&db, &[diag.clone()]),
+        concat!(
+            r#"{"severity":"error","message":"undefined type","labels":[{"style":"primary","#,
+            r#""message":"not defined","file":"src/main.fe","start":{"byte":22,"line":2,"column":8},"#,
+            r#""end":{"byte":25,"line":2,"column":10}}],"notes":["note: types are case sensitive"]}"#,
+            "\n"
+        )
+    );
+
+    let sarif: serde_json::Value = serde_json::from_str(&diagnostics_sarif(&db, &[diag])).unwrap();
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["message"]["text"],
+        "undefined type\nnote: types are case sensitive"
+    );
+    let region = &result["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 2);
+    assert_eq!(region["startColumn"], 8);
+    assert_eq!(region["endColumn"], 10);
+    assert_eq!(region["byteLength"], 3);
+}
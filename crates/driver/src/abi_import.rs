@@ -0,0 +1,299 @@
+use fe_abi::{
+    contract::AbiContract,
+    function::{AbiFunction, AbiFunctionType, StateMutability},
+    types::{AbiTupleField, AbiType},
+};
+use fe_common::files::SourceFileId;
+use fe_parser::{lexer::Lexer, TokenKind};
+use indexmap::IndexSet;
+
+/// A Fe module generated from the ABI of a deployed contract.
+pub struct ImportedAbi {
+    pub src: String,
+    /// The ABI entries that can't be expressed in Fe, and why.
+    pub skipped: Vec<String>,
+}
+
+/// Generates a module with an interface named `name` for the functions of
+/// `abi_json`, and a struct for each of its events, errors and tuple types.
+///
+/// Fe strings and byte arrays have a fixed capacity, so `string` and `bytes`
+/// become `String<max_len>` and `Array<u8, max_len>`.
+pub fn import_abi(name: &str, abi_json: &str, max_len: usize) -> Result<ImportedAbi, String> {
+    let abi: AbiContract = serde_json::from_str(abi_json).map_err(|err| err.to_string())?;
+    if !is_identifier(name) {
+        return Err(format!(
+            "`{name}` can't be used as the name of an interface"
+        ));
+    }
+
+    let mut importer = Importer {
+        max_len,
+        names: IndexSet::new(),
+        tuples: vec![],
+        items: vec![],
+        skipped: vec![],
+    };
+    importer.names.insert(name.to_string());
+
+    for event in &abi.events {
+        if event.anonymous {
+            importer.skip(&event.name, "event", "anonymous events aren't supported");
+            continue;
+        }
+        let fields = event
+            .inputs
+            .iter()
+            .map(|input| (input.name.as_str(), &input.ty, input.indexed));
+        importer.struct_item(&event.name, "event", fields);
+    }
+
+    for error in &abi.errors {
+        let fields = error
+            .inputs
+            .iter()
+            .map(|input| (input.name.as_str(), &input.ty, false));
+        importer.struct_item(&error.name, "error", fields);
+    }
+
+    let mut functions = IndexSet::new();
+    let mut sigs = vec![];
+    for func in &abi.funcs {
+        if func.func_type != AbiFunctionType::Function {
+            continue;
+        }
+        if !functions.insert(func.name.as_str()) {
+            importer.skip(
+                &func.name,
+                "function",
+                "overloaded functions aren't supported",
+            );
+            continue;
+        }
+        match importer.function_sig(func) {
+            Ok(sig) => sigs.push(sig),
+            Err(reason) => importer.skip(&func.name, "function", &reason),
+        }
+    }
+
+    let mut src = String::new();
+    for item in &importer.items {
+        src += item;
+        src += "\n";
+    }
+    src += &format!("pub interface {name} {{\n");
+    for sig in sigs {
+        src += &format!("    {sig};\n");
+    }
+    src += "}\n";
+
+    Ok(ImportedAbi {
+        src,
+        skipped: importer.skipped,
+    })
+}
+
+struct Importer {
+    max_len: usize,
+    /// The names of the generated items.
+    names: IndexSet<String>,
+    /// The name of the struct generated for each tuple type.
+    tuples: Vec<(AbiType, String)>,
+    /// The generated structs.
+    items: Vec<String>,
+    skipped: Vec<String>,
+}
+
+impl Importer {
+    fn skip(&mut self, name: &str, kind: &str, reason: &str) {
+        self.skipped.push(format!("{kind} `{name}`: {reason}"));
+    }
+
+    fn struct_item<'a>(
+        &mut self,
+        name: &str,
+        kind: &str,
+        fields: impl Iterator<Item = (&'a str, &'a AbiType, bool)>,
+    ) {
+        if !is_identifier(name) {
+            return self.skip(name, kind, "the name is a Fe keyword");
+        }
+        if self.names.contains(name) {
+            return self.skip(name, kind, "the name is already used by another item");
+        }
+        match self.struct_def(name, fields) {
+            Ok(item) => {
+                self.names.insert(name.to_string());
+                self.items.push(item);
+            }
+            Err(reason) => self.skip(name, kind, &reason),
+        }
+    }
+
+    fn struct_def<'a>(
+        &mut self,
+        name: &str,
+        fields: impl Iterator<Item = (&'a str, &'a AbiType, bool)>,
+    ) -> Result<String, String> {
+        let mut def = format!("pub struct {name} {{\n");
+        for (i, (field_name, ty, indexed)) in fields.enumerate() {
+            let field_name = local_name(field_name, i);
+            if let Some(dyn_array) = dyn_array(ty) {
+                return Err(format!(
+                    "`{}` can't be used in a struct field",
+                    dyn_array.selector_type_name()
+                ));
+            }
+            if indexed {
+                def += "    #indexed\n";
+            }
+            def += &format!("    pub {field_name}: {}\n", self.fe_type(ty, &field_name)?);
+        }
+        def += "}\n";
+        Ok(def)
+    }
+
+    fn function_sig(&mut self, func: &AbiFunction) -> Result<String, String> {
+        if !is_identifier(&func.name) {
+            return Err("the name is a Fe keyword".into());
+        }
+
+        let mut params = vec![match func.state_mutability {
+            StateMutability::Pure | StateMutability::View => "self".to_string(),
+            StateMutability::Nonpayable | StateMutability::Payable => "mut self".to_string(),
+        }];
+        for (i, input) in func.inputs.iter().enumerate() {
+            let name = local_name(&input.name, i);
+            params.push(format!("{name}: {}", self.fe_type(&input.ty, &name)?));
+        }
+
+        let mut sig = format!("fn {}({})", func.name, params.join(", "));
+        let outputs = func
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(i, output)| self.fe_type(&output.ty, &local_name(&output.name, i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        match outputs.as_slice() {
+            [] => {}
+            [output] => sig += &format!(" -> {output}"),
+            _ => sig += &format!(" -> ({})", outputs.join(", ")),
+        }
+        Ok(sig)
+    }
+
+    /// The Fe type of an ABI type. `hint` names the struct of a tuple type.
+    fn fe_type(&mut self, ty: &AbiType, hint: &str) -> Result<String, String> {
+        let ty = match ty {
+            AbiType::UInt(bits) | AbiType::Int(bits)
+                if !matches!(bits, 8 | 16 | 32 | 64 | 128 | 256) =>
+            {
+                return Err(format!(
+                    "`{}` has no equivalent in Fe",
+                    ty.selector_type_name()
+                ))
+            }
+            AbiType::UInt(bits) => format!("u{bits}"),
+            AbiType::Int(bits) => format!("i{bits}"),
+            AbiType::Address => "address".into(),
+            AbiType::Bool => "bool".into(),
+            AbiType::String => format!("String<{}>", self.max_len),
+            AbiType::Bytes => format!("Array<u8, {}>", self.max_len),
+            // Fe's `Array<u8, N>` is encoded as `bytes`.
+            AbiType::Array { elem_ty, len } if **elem_ty == AbiType::UInt(8) => {
+                return Err(format!("`uint8[{len}]` has no equivalent in Fe"))
+            }
+            AbiType::Array { elem_ty, len } => {
+                format!("Array<{}, {len}>", self.fe_type(elem_ty, hint)?)
+            }
+            AbiType::DynArray { elem_ty } => format!("Vec<{}>", self.fe_type(elem_ty, hint)?),
+            AbiType::Tuple(fields) => self.tuple_struct(ty, fields, hint)?,
+            AbiType::Function | AbiType::FixedBytes(_) | AbiType::Fixed { .. } => {
+                return Err(format!(
+                    "`{}` has no equivalent in Fe",
+                    ty.selector_type_name()
+                ))
+            }
+        };
+        Ok(ty)
+    }
+
+    fn tuple_struct(
+        &mut self,
+        ty: &AbiType,
+        fields: &[AbiTupleField],
+        hint: &str,
+    ) -> Result<String, String> {
+        if let Some((_, name)) = self.tuples.iter().find(|(tuple, _)| tuple == ty) {
+            return Ok(name.clone());
+        }
+
+        let base = struct_name(hint);
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while self.names.contains(&name) {
+            suffix += 1;
+            name = format!("{base}{suffix}");
+        }
+
+        let fields = fields
+            .iter()
+            .map(|field| (field.name.as_str(), &field.ty, false));
+        let def = self.struct_def(&name, fields)?;
+        self.names.insert(name.clone());
+        self.tuples.push((ty.clone(), name.clone()));
+        self.items.push(def);
+        Ok(name)
+    }
+}
+
+/// The first dynamic array in `ty`, outside of tuples. Fe's `Vec` can't be a
+/// struct field, while a tuple type gets a struct of its own.
+fn dyn_array(ty: &AbiType) -> Option<&AbiType> {
+    match ty {
+        AbiType::DynArray { .. } => Some(ty),
+        AbiType::Array { elem_ty, .. } => dyn_array(elem_ty),
+        _ => None,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut lexer = Lexer::new(SourceFileId::dummy_file(), name);
+    matches!(
+        (lexer.next(), lexer.next()),
+        (Some(token), None) if token.kind == TokenKind::Name
+    )
+}
+
+/// The name of a parameter or field, which is positional if the ABI leaves
+/// it empty.
+fn local_name(name: &str, position: usize) -> String {
+    if name.is_empty() {
+        format!("arg{position}")
+    } else if is_identifier(name) {
+        name.to_string()
+    } else {
+        format!("{name}_")
+    }
+}
+
+/// A struct name for a tuple type, derived from the name of a parameter or
+/// field that has the type.
+pub(crate) fn struct_name(hint: &str) -> String {
+    let name = hint
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("Tuple{name}")
+    } else {
+        name
+    }
+}
@@ -0,0 +1,717 @@
+use fe_analyzer::context::NamedThing;
+use fe_analyzer::namespace::items::{
+    ContractId, EnumId, FunctionId, ImplId, IngotId, Item, ModuleConstantId, ModuleId, StructId,
+    TraitId, TypeAliasId, TypeDef,
+};
+use fe_analyzer::AnalyzerDb;
+use fe_codegen::db::CodegenDb;
+use fe_mir::db::MirDb;
+use fe_parser::ast::{self, FunctionArg, GenericArg, GenericParameter, TypeDesc, VariantKind};
+use indexmap::IndexMap;
+use smol_str::SmolStr;
+
+use crate::Db;
+
+/// The output format of the generated documentation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DocFormat {
+    Html,
+    Markdown,
+}
+
+impl DocFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            DocFormat::Html => "html",
+            DocFormat::Markdown => "md",
+        }
+    }
+}
+
+/// Renders the documentation of all modules of the ingot.
+///
+/// Returns the generated pages, keyed by their path relative to the output
+/// directory: an index page that lists the modules, and one page per module.
+/// Type names in signatures link to their definitions if they are defined in
+/// the ingot. The ingot must be free of errors, since the ABI signatures of
+/// contract functions are derived from the lowered code.
+pub fn document_ingot(db: &Db, ingot: IngotId, format: DocFormat) -> IndexMap<String, String> {
+    let mut modules = ingot
+        .all_modules(db)
+        .iter()
+        .copied()
+        .filter(|module| !module.ast(db).body.is_empty())
+        .collect::<Vec<_>>();
+    // The root module comes first, the others are sorted by path.
+    let root = ingot.root_module(db);
+    modules.sort_by_key(|module| (Some(*module) != root, module_path(db, *module)));
+
+    let mut index = format!("# Ingot `{}`\n\n## Modules\n\n", ingot.name(db));
+    for module in &modules {
+        index += &format!(
+            "- [`{}`]({}.{})\n",
+            module_path(db, *module),
+            page_name(db, *module),
+            format.extension()
+        );
+    }
+
+    let mut pages = IndexMap::new();
+    let title = ingot.name(db);
+    pages.insert(
+        format!("index.{}", format.extension()),
+        render_page(format, &title, &index),
+    );
+    for module in modules {
+        let mut page = ModulePage {
+            db,
+            ingot,
+            module,
+            format,
+            out: String::new(),
+        };
+        page.module();
+        pages.insert(
+            format!("{}.{}", page_name(db, module), format.extension()),
+            render_page(format, &module_path(db, module), &page.out),
+        );
+    }
+    pages
+}
+
+/// The `::` separated path of the module, starting with the ingot name.
+fn module_path(db: &Db, module: ModuleId) -> String {
+    let ingot = module.ingot(db).name(db);
+    let path = module.file_path_relative_to_src_dir(db);
+    let path = path.trim_end_matches(".fe");
+    if Some(module) == module.ingot(db).root_module(db) {
+        ingot.to_string()
+    } else {
+        format!("{ingot}::{}", path.replace('/', "::"))
+    }
+}
+
+/// The file name of the module's page, without extension.
+fn page_name(db: &Db, module: ModuleId) -> String {
+    module
+        .file_path_relative_to_src_dir(db)
+        .trim_end_matches(".fe")
+        .replace('/', ".")
+}
+
+fn render_page(format: DocFormat, title: &str, markdown: &str) -> String {
+    match format {
+        DocFormat::Markdown => markdown.to_string(),
+        DocFormat::Html => {
+            let mut body = String::new();
+            let parser = pulldown_cmark::Parser::new(markdown);
+            pulldown_cmark::html::push_html(&mut body, parser);
+            format!(
+                r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{}</title>
+<style>
+body {{ font-family: sans-serif; max-width: 60em; margin: 2em auto; padding: 0 1em; line-height: 1.5; }}
+code {{ background: #f4f4f4; padding: 0.1em 0.3em; border-radius: 3px; }}
+h3, h4, h5 {{ border-bottom: 1px solid #ddd; }}
+a {{ color: #2a6ebb; text-decoration: none; }}
+</style>
+</head>
+<body>
+{}</body>
+</html>
+"#,
+                title.replace('<', "&lt;"),
+                body
+            )
+        }
+    }
+}
+
+/// The ABI signature and selector of a public contract function, e.g.
+/// `transfer(address,uint256)` and `0xa9059cbb`.
+fn abi_selector(db: &Db, function: FunctionId) -> (String, String) {
+    let selector = db
+        .codegen_abi_function(db.mir_lowered_func_signature(function))
+        .selector();
+    (
+        selector.selector_signature().to_string(),
+        format!("0x{}", selector.hex()),
+    )
+}
+
+/// A signature, made of plain code and links to the documentation of the
+/// items it refers to.
+#[derive(Default)]
+struct Sig {
+    parts: Vec<(String, Option<String>)>,
+}
+
+impl Sig {
+    fn text(&mut self, text: &str) -> &mut Self {
+        match self.parts.last_mut() {
+            Some((last, None)) => last.push_str(text),
+            _ => self.parts.push((text.to_string(), None)),
+        }
+        self
+    }
+
+    fn link(&mut self, text: &str, target: Option<String>) -> &mut Self {
+        match target {
+            Some(target) => {
+                self.parts.push((text.to_string(), Some(target)));
+                self
+            }
+            None => self.text(text),
+        }
+    }
+
+    /// Renders the signature as Markdown code spans. Spaces at the edges of
+    /// a span are moved outside of it, since Markdown strips them.
+    fn markdown(&self) -> String {
+        let mut out = String::new();
+        for (text, target) in &self.parts {
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                out += text;
+                continue;
+            }
+            if text.starts_with(' ') {
+                out.push(' ');
+            }
+            match target {
+                Some(target) => out += &format!("[`{trimmed}`]({target})"),
+                None => out += &format!("`{trimmed}`"),
+            }
+            if text.ends_with(' ') {
+                out.push(' ');
+            }
+        }
+        out
+    }
+}
+
+struct ModulePage<'a> {
+    db: &'a Db,
+    ingot: IngotId,
+    module: ModuleId,
+    format: DocFormat,
+    out: String,
+}
+
+impl<'a> ModulePage<'a> {
+    fn module(&mut self) {
+        let db = self.db;
+        self.out += &format!("# Module `{}`\n\n", module_path(db, self.module));
+
+        let items = self
+            .module
+            .all_items(db)
+            .iter()
+            .copied()
+            .filter(|item| match item {
+                Item::Function(function) => !function.is_test(db),
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+        for contract in self.section("Contracts", &items, |item| match item {
+            Item::Type(TypeDef::Contract(id)) => Some(id),
+            _ => None,
+        }) {
+            self.contract(contract);
+        }
+        for struct_ in self.section("Structs", &items, |item| match item {
+            Item::Type(TypeDef::Struct(id)) => Some(id),
+            _ => None,
+        }) {
+            self.struct_(struct_);
+        }
+        for enum_ in self.section("Enums", &items, |item| match item {
+            Item::Type(TypeDef::Enum(id)) => Some(id),
+            _ => None,
+        }) {
+            self.enum_(enum_);
+        }
+        for trait_ in self.section("Traits", &items, |item| match item {
+            Item::Trait(id) => Some(id),
+            _ => None,
+        }) {
+            self.trait_(trait_);
+        }
+        for function in self.section("Functions", &items, |item| match item {
+            Item::Function(id) => Some(id),
+            _ => None,
+        }) {
+            let anchor = anchor(db, Item::Function(function)).unwrap();
+            self.function(function, &anchor, 3);
+        }
+        for constant in self.section("Constants", &items, |item| match item {
+            Item::Constant(id) => Some(id),
+            _ => None,
+        }) {
+            self.constant(constant);
+        }
+        for alias in self.section("Type aliases", &items, |item| match item {
+            Item::Type(TypeDef::Alias(id)) => Some(id),
+            _ => None,
+        }) {
+            self.type_alias(alias);
+        }
+        let impls = self.module.all_impls(db);
+        if !impls.is_empty() {
+            self.out += "## Implementations\n\n";
+        }
+        for impl_ in impls.iter().copied() {
+            self.impl_(impl_);
+        }
+    }
+
+    /// Selects the items of a section and writes its heading, unless the
+    /// section is empty.
+    fn section<T>(
+        &mut self,
+        title: &str,
+        items: &[Item],
+        select: impl Fn(Item) -> Option<T>,
+    ) -> Vec<T> {
+        let selected = items
+            .iter()
+            .filter_map(|item| select(*item))
+            .collect::<Vec<_>>();
+        if !selected.is_empty() {
+            self.out += &format!("## {title}\n\n");
+        }
+        selected
+    }
+
+    fn contract(&mut self, contract: ContractId) {
+        let db = self.db;
+        let ast = &contract.data(db).ast.kind;
+        let anchor = anchor(db, Item::Type(TypeDef::Contract(contract))).unwrap();
+        let mut sig = Sig::default();
+        sig.text(pub_prefix(ast.pub_qual.is_some()))
+            .text(if contract.is_interface(db) {
+                "interface "
+            } else {
+                "contract "
+            })
+            .text(&ast.name.kind);
+        self.heading(3, &anchor, &sig);
+        self.doc(ast.doc.as_ref());
+
+        let init = contract.init_function(db);
+        let public = contract.public_functions(db);
+        if init.is_none() && public.is_empty() {
+            return;
+        }
+        self.out += "#### Functions\n\n";
+        for function in init.into_iter().chain(public.values().copied()) {
+            let fn_anchor = format!("{anchor}.fn.{}", function.name(db));
+            let sig = self.fn_sig(&function.sig(db).data(db).ast.kind);
+            self.heading(5, &fn_anchor, &sig);
+            if !function.is_constructor(db) {
+                let (signature, selector) = abi_selector(db, function);
+                self.out += &format!("ABI: `{signature}`, selector `{selector}`\n\n");
+            }
+            self.doc(function.doc(db).as_ref());
+        }
+    }
+
+    fn struct_(&mut self, struct_: StructId) {
+        let db = self.db;
+        let ast = &struct_.data(db).ast.kind;
+        let anchor = anchor(db, Item::Type(TypeDef::Struct(struct_))).unwrap();
+        let mut sig = Sig::default();
+        sig.text(pub_prefix(ast.pub_qual.is_some()))
+            .text("struct ")
+            .text(&ast.name.kind);
+        self.generic_params(&mut sig, &ast.generic_params.kind);
+        self.heading(3, &anchor, &sig);
+        self.doc(ast.doc.as_ref());
+
+        if !ast.fields.is_empty() {
+            self.out += "#### Fields\n\n";
+            for field in &ast.fields {
+                let mut sig = Sig::default();
+                sig.text(pub_prefix(field.kind.is_pub))
+                    .text(&field.kind.name.kind)
+                    .text(": ");
+                self.type_desc(&mut sig, &field.kind.typ.kind);
+                self.list_item(&sig, field.kind.doc.as_ref());
+            }
+            self.out += "\n";
+        }
+        self.methods(&anchor, &struct_.all_functions(db));
+        self.implementations(Item::Type(TypeDef::Struct(struct_)));
+    }
+
+    fn enum_(&mut self, enum_: EnumId) {
+        let db = self.db;
+        let ast = &enum_.data(db).ast.kind;
+        let anchor = anchor(db, Item::Type(TypeDef::Enum(enum_))).unwrap();
+        let mut sig = Sig::default();
+        sig.text(pub_prefix(ast.pub_qual.is_some()))
+            .text("enum ")
+            .text(&ast.name.kind);
+        self.generic_params(&mut sig, &ast.generic_params.kind);
+        self.heading(3, &anchor, &sig);
+        self.doc(ast.doc.as_ref());
+
+        if !ast.variants.is_empty() {
+            self.out += "#### Variants\n\n";
+            for variant in &ast.variants {
+                let mut sig = Sig::default();
+                sig.text(&variant.kind.name.kind);
+                if let VariantKind::Tuple(items) = &variant.kind.kind {
+                    sig.text("(");
+                    for (idx, item) in items.iter().enumerate() {
+                        if idx > 0 {
+                            sig.text(", ");
+                        }
+                        self.type_desc(&mut sig, &item.kind);
+                    }
+                    sig.text(")");
+                }
+                self.list_item(&sig, variant.kind.doc.as_ref());
+            }
+            self.out += "\n";
+        }
+        self.methods(&anchor, &enum_.all_functions(db));
+        self.implementations(Item::Type(TypeDef::Enum(enum_)));
+    }
+
+    fn trait_(&mut self, trait_: TraitId) {
+        let db = self.db;
+        let ast = &trait_.data(db).ast.kind;
+        let anchor = anchor(db, Item::Trait(trait_)).unwrap();
+        let mut sig = Sig::default();
+        sig.text(pub_prefix(ast.pub_qual.is_some()))
+            .text("trait ")
+            .text(&ast.name.kind);
+        self.heading(3, &anchor, &sig);
+        self.doc(ast.doc.as_ref());
+
+        if !ast.functions.is_empty() {
+            self.out += "#### Functions\n\n";
+            for function in &ast.functions {
+                let fn_anchor = format!("{anchor}.fn.{}", function.kind.name.kind);
+                let sig = self.fn_sig(&function.kind);
+                self.heading(5, &fn_anchor, &sig);
+                self.doc(function.kind.doc.as_ref());
+            }
+        }
+        self.implementations(Item::Trait(trait_));
+    }
+
+    fn impl_(&mut self, impl_: ImplId) {
+        let db = self.db;
+        let ast = impl_.ast(db).kind;
+        let anchor = impl_anchor(db, impl_);
+        let mut sig = Sig::default();
+        sig.text("impl ");
+        self.name(&mut sig, &ast.impl_trait.kind);
+        sig.text(" for ");
+        self.type_desc(&mut sig, &ast.receiver.kind);
+        self.heading(3, &anchor, &sig);
+        self.doc(ast.doc.as_ref());
+
+        let functions = impl_.all_functions(db);
+        if !functions.is_empty() {
+            self.out += "#### Functions\n\n";
+            for function in functions.iter() {
+                self.function(*function, &format!("{anchor}.fn.{}", function.name(db)), 5);
+            }
+        }
+    }
+
+    fn function(&mut self, function: FunctionId, anchor: &str, level: usize) {
+        let sig = self.fn_sig(&function.sig(self.db).data(self.db).ast.kind);
+        self.heading(level, anchor, &sig);
+        self.doc(function.doc(self.db).as_ref());
+    }
+
+    fn constant(&mut self, constant: ModuleConstantId) {
+        let db = self.db;
+        let ast = &constant.data(db).ast.kind;
+        let mut sig = Sig::default();
+        sig.text(pub_prefix(ast.pub_qual.is_some()))
+            .text("const ")
+            .text(&ast.name.kind)
+            .text(": ");
+        self.type_desc(&mut sig, &ast.typ.kind);
+        self.heading(3, &anchor(db, Item::Constant(constant)).unwrap(), &sig);
+        self.doc(ast.doc.as_ref());
+    }
+
+    fn type_alias(&mut self, alias: TypeAliasId) {
+        let db = self.db;
+        let ast = &alias.data(db).ast.kind;
+        let mut sig = Sig::default();
+        sig.text(pub_prefix(ast.pub_qual.is_some()))
+            .text("type ")
+            .text(&ast.name.kind)
+            .text(" = ");
+        self.type_desc(&mut sig, &ast.typ.kind);
+        let item = Item::Type(TypeDef::Alias(alias));
+        self.heading(3, &anchor(db, item).unwrap(), &sig);
+        self.doc(ast.doc.as_ref());
+    }
+
+    /// The public functions of a struct or enum.
+    fn methods(&mut self, anchor: &str, functions: &[FunctionId]) {
+        let db = self.db;
+        let public = functions
+            .iter()
+            .filter(|function| function.is_public(db))
+            .collect::<Vec<_>>();
+        if public.is_empty() {
+            return;
+        }
+        self.out += "#### Functions\n\n";
+        for function in public {
+            self.function(*function, &format!("{anchor}.fn.{}", function.name(db)), 5);
+        }
+    }
+
+    /// Lists the impls of the ingot that implement the trait or are
+    /// implemented for the type.
+    fn implementations(&mut self, item: Item) {
+        let db = self.db;
+        let impls = self
+            .ingot
+            .all_modules(db)
+            .iter()
+            .flat_map(|module| module.all_impls(db).to_vec())
+            .filter(|impl_| match item {
+                Item::Trait(trait_) => impl_.trait_id(db) == trait_,
+                Item::Type(typ) => Some(impl_.receiver(db)) == typ.type_id(db).ok(),
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+        if impls.is_empty() {
+            return;
+        }
+        self.out += "#### Implementations\n\n";
+        for impl_ in impls {
+            let ast = impl_.ast(db).kind;
+            let text = format!("impl {} for {}", ast.impl_trait.kind, ast.receiver.kind);
+            let target = self.target(impl_.module(db), &impl_anchor(db, impl_));
+            self.out += &format!("- [`{text}`]({target})\n");
+        }
+        self.out += "\n";
+    }
+
+    fn generic_params(&self, out: &mut Sig, params: &[GenericParameter]) {
+        if params.is_empty() {
+            return;
+        }
+        out.text("<");
+        for (idx, param) in params.iter().enumerate() {
+            if idx > 0 {
+                out.text(", ");
+            }
+            out.text(&param.name());
+            if let GenericParameter::Bounded { bound, .. } = param {
+                out.text(": ");
+                self.type_desc(out, &bound.kind);
+            }
+        }
+        out.text(">");
+    }
+
+    fn fn_sig(&self, sig: &ast::FunctionSignature) -> Sig {
+        let mut out = Sig::default();
+        out.text(pub_prefix(sig.pub_.is_some()));
+        if sig.unsafe_.is_some() {
+            out.text("unsafe ");
+        }
+        out.text("fn ").text(&sig.name.kind);
+        self.generic_params(&mut out, &sig.generic_params.kind);
+        out.text("(");
+        for (idx, arg) in sig.args.iter().enumerate() {
+            if idx > 0 {
+                out.text(", ");
+            }
+            match &arg.kind {
+                FunctionArg::Self_ { mut_ } => {
+                    out.text(if mut_.is_some() { "mut self" } else { "self" });
+                }
+                FunctionArg::Regular {
+                    mut_,
+                    label,
+                    name,
+                    typ,
+                } => {
+                    if mut_.is_some() {
+                        out.text("mut ");
+                    }
+                    if let Some(label) = label {
+                        out.text(&label.kind).text(" ");
+                    }
+                    out.text(&name.kind).text(": ");
+                    self.type_desc(&mut out, &typ.kind);
+                }
+            }
+        }
+        out.text(")");
+        if let Some(return_type) = &sig.return_type {
+            out.text(" -> ");
+            self.type_desc(&mut out, &return_type.kind);
+        }
+        out
+    }
+
+    fn type_desc(&self, sig: &mut Sig, typ: &TypeDesc) {
+        match typ {
+            TypeDesc::Base { base } => self.name(sig, base),
+            TypeDesc::Path(path) => {
+                let target = match self.module.resolve_path_internal(self.db, path).value {
+                    Some(NamedThing::Item(item)) => self.item_target(item),
+                    _ => None,
+                };
+                sig.link(&path.to_string(), target);
+            }
+            TypeDesc::Generic { base, args } => {
+                self.name(sig, &base.kind);
+                sig.text("<");
+                for (idx, arg) in args.kind.iter().enumerate() {
+                    if idx > 0 {
+                        sig.text(", ");
+                    }
+                    match arg {
+                        GenericArg::TypeDesc(typ) => self.type_desc(sig, &typ.kind),
+                        _ => {
+                            sig.text(&arg.to_string());
+                        }
+                    }
+                }
+                sig.text(">");
+            }
+            TypeDesc::Tuple { items } => {
+                sig.text("(");
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        sig.text(", ");
+                    }
+                    self.type_desc(sig, &item.kind);
+                }
+                sig.text(")");
+            }
+            TypeDesc::Unit => {
+                sig.text("()");
+            }
+            TypeDesc::SelfType => {
+                sig.text("Self");
+            }
+        }
+    }
+
+    /// A name, linked to its definition if it resolves to an item of the
+    /// documented ingot.
+    fn name(&self, sig: &mut Sig, name: &SmolStr) {
+        let target = match self.module.resolve_name(self.db, name) {
+            Ok(Some(NamedThing::Item(item))) => self.item_target(item),
+            _ => None,
+        };
+        sig.link(name, target);
+    }
+
+    fn item_target(&self, item: Item) -> Option<String> {
+        let module = match item {
+            Item::Module(module) => module,
+            _ => item.module(self.db)?,
+        };
+        if module.ingot(self.db) != self.ingot {
+            return None;
+        }
+        match item {
+            Item::Module(_) => Some(format!(
+                "{}.{}",
+                page_name(self.db, module),
+                self.format.extension()
+            )),
+            _ => Some(self.target(module, &anchor(self.db, item)?)),
+        }
+    }
+
+    /// The link to an anchor on the page of the module.
+    fn target(&self, module: ModuleId, anchor: &str) -> String {
+        if module == self.module {
+            format!("#{anchor}")
+        } else {
+            format!(
+                "{}.{}#{anchor}",
+                page_name(self.db, module),
+                self.format.extension()
+            )
+        }
+    }
+
+    fn heading(&mut self, level: usize, anchor: &str, sig: &Sig) {
+        self.out += &format!(
+            "<a id=\"{anchor}\"></a>\n\n{} {}\n\n",
+            "#".repeat(level),
+            sig.markdown()
+        );
+    }
+
+    fn list_item(&mut self, sig: &Sig, doc: Option<&SmolStr>) {
+        self.out += &format!("- {}\n", sig.markdown());
+        if let Some(doc) = doc {
+            for line in doc.lines() {
+                if line.is_empty() {
+                    self.out += "\n";
+                } else {
+                    self.out += &format!("\n  {line}\n");
+                }
+            }
+        }
+    }
+
+    fn doc(&mut self, doc: Option<&SmolStr>) {
+        if let Some(doc) = doc {
+            self.out += doc;
+            self.out += "\n\n";
+        }
+    }
+}
+
+fn pub_prefix(is_pub: bool) -> &'static str {
+    if is_pub {
+        "pub "
+    } else {
+        ""
+    }
+}
+
+/// The anchor of an item on its module's page.
+fn anchor(db: &dyn AnalyzerDb, item: Item) -> Option<String> {
+    let kind = match item {
+        Item::Type(TypeDef::Contract(_)) => "contract",
+        Item::Type(TypeDef::Struct(_)) => "struct",
+        Item::Type(TypeDef::Enum(_)) => "enum",
+        Item::Type(TypeDef::Alias(_)) => "type",
+        Item::Trait(_) => "trait",
+        Item::Function(_) => "fn",
+        Item::Constant(_) => "const",
+        Item::Impl(impl_) => return Some(impl_anchor(db, impl_)),
+        _ => return None,
+    };
+    Some(format!("{kind}.{}", item.name(db)))
+}
+
+fn impl_anchor(db: &dyn AnalyzerDb, impl_: ImplId) -> String {
+    let ast = impl_.ast(db).kind;
+    let name = format!("impl.{}.for.{}", ast.impl_trait.kind, ast.receiver.kind);
+    name.chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || ch == '_' || ch == '.' {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,75 @@
+use crate::lockfile::update_lockfile;
+use crate::resolver::{resolve, DependencyGraph};
+use crate::Db;
+use fe_analyzer::namespace::items::{IngotId, IngotMode};
+use fe_common::diagnostics::Diagnostic;
+use fe_common::files::FileKind;
+use indexmap::indexmap;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Loads the ingot in the directory `dir`, along with every ingot it depends on.
+///
+/// If the directory contains an `fe.toml` manifest, the ingot's sources are
+/// read from its `src` directory and the dependencies listed in the manifest
+/// are loaded (recursively) as library ingots. Otherwise, every `.fe` file in
+/// the directory is part of the ingot, which only depends on `std`.
+///
+/// For ingots with a manifest, the resolved dependency graph is recorded in
+/// `fe.lock` (see [`update_lockfile`](crate::update_lockfile)).
+pub fn load_ingot(db: &mut Db, dir: &Path, locked: bool) -> Result<IngotId, Vec<Diagnostic>> {
+    let graph = resolve(dir)?;
+    if graph.root().has_manifest() {
+        update_lockfile(&graph, locked)?;
+    }
+    Ok(ingots_from_graph(db, &graph))
+}
+
+/// Returns `(file_path, file_content)` for every `.fe` file in `dir` and its
+/// subdirectories.
+pub fn load_files_from_dir(dir: &Path) -> Result<Vec<(String, String)>, std::io::Error> {
+    let entries = WalkDir::new(dir);
+    let mut files = vec![];
+    for entry in entries.into_iter() {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() && path.extension().and_then(std::ffi::OsStr::to_str) == Some("fe") {
+            let content = std::fs::read_to_string(path)?;
+            files.push((path.to_string_lossy().to_string(), content));
+        }
+    }
+    Ok(files)
+}
+
+/// Creates an `IngotId` for every ingot in the graph and returns the root one.
+pub fn ingots_from_graph(db: &mut Db, graph: &DependencyGraph) -> IngotId {
+    let std = IngotId::std_lib(db);
+    let root_idx = graph.root_index();
+
+    // `graph.ingots` is in dependency order. This matters, because
+    // `IngotId::from_files` marks the new ingot as the root ingot.
+    let mut ids: Vec<IngotId> = vec![];
+    for (idx, ingot) in graph.ingots.iter().enumerate() {
+        let mut deps = indexmap! { "std".into() => std };
+        deps.extend(
+            ingot
+                .dependencies
+                .iter()
+                .map(|(name, dep_idx)| (name.clone(), ids[*dep_idx])),
+        );
+        let mode = if idx == root_idx {
+            IngotMode::Main
+        } else {
+            IngotMode::Lib
+        };
+        ids.push(IngotId::from_files(
+            db,
+            &ingot.name,
+            mode,
+            FileKind::Local,
+            &ingot.files,
+            deps,
+        ));
+    }
+    ids[root_idx]
+}
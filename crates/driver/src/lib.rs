@@ -3,25 +3,56 @@
 pub use fe_codegen::db::{CodegenDb, Db};
 
 use fe_analyzer::namespace::items::{ContractId, FunctionId, IngotId, IngotMode, ModuleId};
+use fe_analyzer::namespace::types;
 use fe_common::db::Upcast;
 use fe_common::diagnostics::Diagnostic;
 use fe_common::files::FileKind;
 use fe_parser::ast::SmolStr;
-use fe_test_runner::TestSink;
+use fe_test_runner::{ExpectedRevert, TestFilter, TestSink};
 use indexmap::{indexmap, IndexMap};
+#[cfg(feature = "solc-backend")]
+use rayon::prelude::*;
 use serde_json::Value;
 use std::fmt::Display;
+use std::path::Path;
+
+mod abi_import;
+mod doc;
+mod ingot;
+mod lockfile;
+mod manifest;
+mod natspec;
+mod resolver;
+mod solidity;
+mod storage_layout;
+
+pub use abi_import::{import_abi, ImportedAbi};
+pub use doc::{document_ingot, DocFormat};
+pub use ingot::{ingots_from_graph, load_files_from_dir, load_ingot};
+pub use lockfile::{update_lockfile, LockedIngot, Lockfile, LOCKFILE_NAME};
+pub use manifest::{Dependency, Manifest, MANIFEST_FILE_NAME};
+pub use resolver::{resolve, DependencyGraph, ResolvedIngot};
 
 /// The artifacts of a compiled module.
 pub struct CompiledModule {
     pub src_ast: String,
     pub lowered_ast: String,
     pub contracts: IndexMap<String, CompiledContract>,
+    /// Warnings that didn't stop the compilation.
+    pub warnings: Vec<Diagnostic>,
 }
 
 /// The artifacts of a compiled contract.
 pub struct CompiledContract {
     pub json_abi: String,
+    /// NatSpec user documentation, in the format of solc's `userdoc`.
+    pub userdoc: String,
+    /// NatSpec developer documentation, in the format of solc's `devdoc`.
+    pub devdoc: String,
+    /// The storage location and encoding of each contract field, as JSON.
+    pub storage_layout: String,
+    /// A Solidity interface for calling the contract.
+    pub solidity_interface: String,
     pub yul: String,
     #[cfg(feature = "solc-backend")]
     pub bytecode: String,
@@ -30,17 +61,41 @@ pub struct CompiledContract {
 #[cfg(feature = "solc-backend")]
 pub struct CompiledTest {
     pub name: SmolStr,
+    /// The name of the module that defines the test.
+    pub module: SmolStr,
     bytecode: String,
+    /// The revert expected by a `#test(should_revert)` function.
+    pub expected_revert: Option<ExpectedRevert>,
 }
 
 #[cfg(feature = "solc-backend")]
 impl CompiledTest {
-    pub fn new(name: SmolStr, bytecode: String) -> Self {
-        Self { name, bytecode }
+    pub fn new(
+        name: SmolStr,
+        module: SmolStr,
+        bytecode: String,
+        expected_revert: Option<ExpectedRevert>,
+    ) -> Self {
+        Self {
+            name,
+            module,
+            bytecode,
+            expected_revert,
+        }
+    }
+
+    /// The name of the test, qualified with its module, like `module::test_name`.
+    pub fn qualified_name(&self) -> String {
+        format!("{}::{}", self.module, self.name)
     }
 
     pub fn execute(&self, sink: &mut TestSink) -> bool {
-        fe_test_runner::execute(&self.name, &self.bytecode, sink)
+        fe_test_runner::execute(
+            &self.qualified_name(),
+            &self.bytecode,
+            self.expected_revert.as_ref(),
+            sink,
+        )
     }
 }
 
@@ -62,8 +117,8 @@ pub fn compile_single_file(
     let module = ModuleId::new_standalone(db, path, src);
     let diags = module.diagnostics(db);
 
-    if diags.is_empty() {
-        compile_module(db, module, with_bytecode, optimize)
+    if !diags.iter().any(Diagnostic::is_error) {
+        compile_module(db, module, diags, with_bytecode, optimize)
     } else {
         Err(CompileError(diags))
     }
@@ -75,17 +130,73 @@ pub fn compile_single_file_tests(
     path: &str,
     src: &str,
     optimize: bool,
+    filter: &TestFilter,
 ) -> Result<(SmolStr, Vec<CompiledTest>), CompileError> {
     let module = ModuleId::new_standalone(db, path, src);
     let diags = module.diagnostics(db);
 
-    if diags.is_empty() {
-        Ok((module.name(db), compile_module_tests(db, module, optimize)))
+    if !diags.iter().any(Diagnostic::is_error) {
+        Ok((
+            module.name(db),
+            compile_module_tests(db, module, optimize, filter),
+        ))
+    } else {
+        Err(CompileError(diags))
+    }
+}
+
+/// Returns the qualified names of the tests in a single-file module that match
+/// `filter`, without compiling them.
+pub fn list_single_file_tests(
+    db: &mut Db,
+    path: &str,
+    src: &str,
+    filter: &TestFilter,
+) -> Result<Vec<String>, CompileError> {
+    let module = ModuleId::new_standalone(db, path, src);
+    let diags = module.diagnostics(db);
+
+    if !diags.iter().any(Diagnostic::is_error) {
+        Ok(module_test_names(db, module, filter))
     } else {
         Err(CompileError(diags))
     }
 }
 
+/// Returns the qualified names of the tests in the ingot in the directory `dir`
+/// that match `filter`, without compiling them.
+pub fn list_ingot_dir_tests(
+    db: &mut Db,
+    dir: &Path,
+    locked: bool,
+    filter: &TestFilter,
+) -> Result<Vec<String>, CompileError> {
+    let ingot = load_ingot(db, dir, locked).map_err(CompileError)?;
+    let diags = ingot_diagnostics(db, ingot);
+    if diags.iter().any(Diagnostic::is_error) {
+        return Err(CompileError(diags));
+    }
+
+    Ok(ingot
+        .all_modules(db)
+        .iter()
+        .flat_map(|module| module_test_names(db, *module, filter))
+        .collect())
+}
+
+fn module_test_names(db: &Db, module: ModuleId, filter: &TestFilter) -> Vec<String> {
+    module
+        .tests(db)
+        .iter()
+        .map(|test| qualified_test_name(db, module, *test))
+        .filter(|name| filter.matches(name))
+        .collect()
+}
+
+fn qualified_test_name(db: &Db, module: ModuleId, test: FunctionId) -> String {
+    format!("{}::{}", module.name(db), test.name(db))
+}
+
 // Run analysis with ingot
 // Return vector error,waring...
 pub fn check_ingot(
@@ -93,19 +204,18 @@ pub fn check_ingot(
     name: &str,
     files: &[(impl AsRef<str>, impl AsRef<str>)],
 ) -> Vec<Diagnostic> {
-    let std = IngotId::std_lib(db);
-    let ingot = IngotId::from_files(
-        db,
-        name,
-        IngotMode::Main,
-        FileKind::Local,
-        files,
-        indexmap! { "std".into() => std },
-    );
+    let ingot = main_ingot_from_files(db, name, files);
+    ingot_diagnostics(db, ingot)
+}
 
-    let mut diags = ingot.diagnostics(db);
-    ingot.sink_external_ingot_diagnostics(db, &mut diags);
-    diags
+/// Analyzes the ingot in the directory `dir` and its dependencies.
+///
+/// See [`load_ingot`] for how the ingot and its dependencies are located.
+pub fn check_ingot_dir(db: &mut Db, dir: &Path, locked: bool) -> Vec<Diagnostic> {
+    match load_ingot(db, dir, locked) {
+        Ok(ingot) => ingot_diagnostics(db, ingot),
+        Err(diags) => diags,
+    }
 }
 
 /// Compiles the main module of a project.
@@ -119,25 +229,60 @@ pub fn compile_ingot(
     with_bytecode: bool,
     optimize: bool,
 ) -> Result<CompiledModule, CompileError> {
-    let std = IngotId::std_lib(db);
-    let ingot = IngotId::from_files(
-        db,
-        name,
-        IngotMode::Main,
-        FileKind::Local,
-        files,
-        indexmap! { "std".into() => std },
-    );
+    let ingot = main_ingot_from_files(db, name, files);
+    compile_main_module(db, ingot, with_bytecode, optimize)
+}
 
-    let mut diags = ingot.diagnostics(db);
-    ingot.sink_external_ingot_diagnostics(db, &mut diags);
-    if !diags.is_empty() {
-        return Err(CompileError(diags));
+/// Compiles the main module of the project in the directory `dir`.
+///
+/// See [`load_ingot`] for how the ingot and its dependencies are located.
+pub fn compile_ingot_dir(
+    db: &mut Db,
+    dir: &Path,
+    locked: bool,
+    with_bytecode: bool,
+    optimize: bool,
+) -> Result<CompiledModule, CompileError> {
+    let ingot = load_ingot(db, dir, locked).map_err(CompileError)?;
+    compile_main_module(db, ingot, with_bytecode, optimize)
+}
+
+/// Generates the documentation of a single-file module.
+///
+/// See [`document_ingot`] for the generated pages.
+pub fn document_single_file(
+    db: &mut Db,
+    path: &str,
+    src: &str,
+    format: DocFormat,
+) -> Result<IndexMap<String, String>, CompileError> {
+    let module = ModuleId::new_standalone(db, path, src);
+    let diags = module.diagnostics(db);
+
+    if !diags.iter().any(Diagnostic::is_error) {
+        Ok(document_ingot(db, module.ingot(db), format))
+    } else {
+        Err(CompileError(diags))
+    }
+}
+
+/// Generates the documentation of the ingot in the directory `dir`.
+///
+/// See [`document_ingot`] for the generated pages.
+pub fn document_ingot_dir(
+    db: &mut Db,
+    dir: &Path,
+    locked: bool,
+    format: DocFormat,
+) -> Result<IndexMap<String, String>, CompileError> {
+    let ingot = load_ingot(db, dir, locked).map_err(CompileError)?;
+    let diags = ingot_diagnostics(db, ingot);
+
+    if !diags.iter().any(Diagnostic::is_error) {
+        Ok(document_ingot(db, ingot, format))
+    } else {
+        Err(CompileError(diags))
     }
-    let main_module = ingot
-        .root_module(db)
-        .expect("missing root module, with no diagnostic");
-    compile_module(db, main_module, with_bytecode, optimize)
 }
 
 #[cfg(feature = "solc-backend")]
@@ -146,34 +291,83 @@ pub fn compile_ingot_tests(
     name: &str,
     files: &[(impl AsRef<str>, impl AsRef<str>)],
     optimize: bool,
+    filter: &TestFilter,
 ) -> Result<Vec<(SmolStr, Vec<CompiledTest>)>, CompileError> {
+    let ingot = main_ingot_from_files(db, name, files);
+    compile_all_module_tests(db, ingot, optimize, filter)
+}
+
+#[cfg(feature = "solc-backend")]
+pub fn compile_ingot_dir_tests(
+    db: &mut Db,
+    dir: &Path,
+    locked: bool,
+    optimize: bool,
+    filter: &TestFilter,
+) -> Result<Vec<(SmolStr, Vec<CompiledTest>)>, CompileError> {
+    let ingot = load_ingot(db, dir, locked).map_err(CompileError)?;
+    compile_all_module_tests(db, ingot, optimize, filter)
+}
+
+fn main_ingot_from_files(
+    db: &mut Db,
+    name: &str,
+    files: &[(impl AsRef<str>, impl AsRef<str>)],
+) -> IngotId {
     let std = IngotId::std_lib(db);
-    let ingot = IngotId::from_files(
+    IngotId::from_files(
         db,
         name,
         IngotMode::Main,
         FileKind::Local,
         files,
         indexmap! { "std".into() => std },
-    );
+    )
+}
 
+fn ingot_diagnostics(db: &Db, ingot: IngotId) -> Vec<Diagnostic> {
     let mut diags = ingot.diagnostics(db);
     ingot.sink_external_ingot_diagnostics(db, &mut diags);
-    if !diags.is_empty() {
+    diags
+}
+
+fn compile_main_module(
+    db: &mut Db,
+    ingot: IngotId,
+    with_bytecode: bool,
+    optimize: bool,
+) -> Result<CompiledModule, CompileError> {
+    let diags = ingot_diagnostics(db, ingot);
+    if diags.iter().any(Diagnostic::is_error) {
         return Err(CompileError(diags));
     }
+    let main_module = ingot
+        .root_module(db)
+        .expect("missing root module, with no diagnostic");
+    compile_module(db, main_module, diags, with_bytecode, optimize)
+}
 
-    if diags.is_empty() {
-        Ok(ingot
-            .all_modules(db)
-            .iter()
-            .fold(vec![], |mut accum, module| {
-                accum.push((module.name(db), compile_module_tests(db, *module, optimize)));
-                accum
-            }))
-    } else {
-        Err(CompileError(diags))
+#[cfg(feature = "solc-backend")]
+fn compile_all_module_tests(
+    db: &mut Db,
+    ingot: IngotId,
+    optimize: bool,
+    filter: &TestFilter,
+) -> Result<Vec<(SmolStr, Vec<CompiledTest>)>, CompileError> {
+    let diags = ingot_diagnostics(db, ingot);
+    if diags.iter().any(Diagnostic::is_error) {
+        return Err(CompileError(diags));
     }
+
+    let lowered: Vec<_> = ingot
+        .all_modules(db)
+        .iter()
+        .map(|module| (module.name(db), lower_module_tests(db, *module, filter)))
+        .collect();
+    Ok(lowered
+        .into_par_iter()
+        .map(|(name, tests)| (name, compile_lowered_tests(tests, optimize)))
+        .collect())
 }
 
 /// Returns graphviz string.
@@ -182,7 +376,7 @@ pub fn dump_mir_single_file(db: &mut Db, path: &str, src: &str) -> Result<String
     let module = ModuleId::new_standalone(db, path, src);
 
     let diags = module.diagnostics(db);
-    if !diags.is_empty() {
+    if diags.iter().any(Diagnostic::is_error) {
         return Err(CompileError(diags));
     }
 
@@ -191,21 +385,88 @@ pub fn dump_mir_single_file(db: &mut Db, path: &str, src: &str) -> Result<String
     Ok(String::from_utf8(text).unwrap())
 }
 
+/// A test that has been lowered to Yul, but not compiled to bytecode yet.
+#[cfg(feature = "solc-backend")]
+struct LoweredTest {
+    name: SmolStr,
+    module: SmolStr,
+    yul: String,
+    expected_revert: Option<ExpectedRevert>,
+}
+
 #[cfg(feature = "solc-backend")]
-fn compile_test(db: &mut Db, test: FunctionId, optimize: bool) -> CompiledTest {
-    let yul_test = fe_codegen::yul::isel::lower_test(db, test)
+impl LoweredTest {
+    fn compile(self, optimize: bool) -> CompiledTest {
+        let bytecode = compile_to_evm("test", &self.yul, optimize);
+        CompiledTest::new(self.name, self.module, bytecode, self.expected_revert)
+    }
+}
+
+#[cfg(feature = "solc-backend")]
+fn lower_test(db: &mut Db, module: ModuleId, test: FunctionId) -> LoweredTest {
+    let yul = fe_codegen::yul::isel::lower_test(db, test)
         .to_string()
         .replace('"', "\\\"");
-    let bytecode = compile_to_evm("test", &yul_test, optimize);
-    CompiledTest::new(test.name(db), bytecode)
+    let expected_revert = test
+        .expected_revert(db)
+        .map(|expected| test_expected_revert(db, expected));
+    LoweredTest {
+        name: test.name(db),
+        module: module.name(db),
+        yul,
+        expected_revert,
+    }
 }
 
+/// Compiles the tests to bytecode on the current rayon thread pool. Unlike
+/// lowering, this doesn't need the database, so the tests are compiled in
+/// parallel.
 #[cfg(feature = "solc-backend")]
-fn compile_module_tests(db: &mut Db, module_id: ModuleId, optimize: bool) -> Vec<CompiledTest> {
-    module_id
+fn compile_lowered_tests(tests: Vec<LoweredTest>, optimize: bool) -> Vec<CompiledTest> {
+    tests
+        .into_par_iter()
+        .map(|test| test.compile(optimize))
+        .collect()
+}
+
+#[cfg(feature = "solc-backend")]
+fn test_expected_revert(db: &dyn CodegenDb, expected: types::ExpectedRevert) -> ExpectedRevert {
+    match expected {
+        types::ExpectedRevert::Any => ExpectedRevert::Any,
+        types::ExpectedRevert::Error(id) => {
+            // Struct errors are reverted with the selector of their ABI entry.
+            let ty = db.mir_lowered_type(id.as_type(db.upcast()));
+            ExpectedRevert::Error {
+                name: id.name(db.upcast()).to_string(),
+                selector: db.codegen_abi_error(ty).selector().selector_raw(),
+            }
+        }
+        types::ExpectedRevert::Panic(code) => ExpectedRevert::Panic(code),
+    }
+}
+
+#[cfg(feature = "solc-backend")]
+fn compile_module_tests(
+    db: &mut Db,
+    module_id: ModuleId,
+    optimize: bool,
+    filter: &TestFilter,
+) -> Vec<CompiledTest> {
+    let tests = lower_module_tests(db, module_id, filter);
+    compile_lowered_tests(tests, optimize)
+}
+
+#[cfg(feature = "solc-backend")]
+fn lower_module_tests(db: &mut Db, module_id: ModuleId, filter: &TestFilter) -> Vec<LoweredTest> {
+    let tests: Vec<_> = module_id
         .tests(db)
         .iter()
-        .map(|test| compile_test(db, *test, optimize))
+        .copied()
+        .filter(|test| filter.matches(&qualified_test_name(db, module_id, *test)))
+        .collect();
+    tests
+        .into_iter()
+        .map(|test| lower_test(db, module_id, test))
         .collect()
 }
 
@@ -213,12 +474,13 @@ fn compile_module_tests(db: &mut Db, module_id: ModuleId, optimize: bool) -> Vec
 fn compile_module(
     db: &mut Db,
     module_id: ModuleId,
+    warnings: Vec<Diagnostic>,
     with_bytecode: bool,
     optimize: bool,
 ) -> Result<CompiledModule, CompileError> {
     let mut contracts = IndexMap::default();
 
-    for contract in module_id.all_contracts(db.upcast()) {
+    for contract in deployable_contracts(db, module_id) {
         let name = &contract.data(db.upcast()).name;
         let abi = db.codegen_abi_contract(contract);
         let yul_contract = compile_to_yul(db, contract);
@@ -234,6 +496,13 @@ fn compile_module(
             name.to_string(),
             CompiledContract {
                 json_abi: serde_json::to_string_pretty(&abi).unwrap(),
+                userdoc: serde_json::to_string_pretty(&natspec::userdoc(db, contract)).unwrap(),
+                devdoc: serde_json::to_string_pretty(&natspec::devdoc(db, contract)).unwrap(),
+                storage_layout: serde_json::to_string_pretty(&storage_layout::storage_layout(
+                    db, contract,
+                ))
+                .unwrap(),
+                solidity_interface: solidity::solidity_interface(&format!("I{name}"), &abi),
                 yul: yul_contract,
                 bytecode,
             },
@@ -244,6 +513,7 @@ fn compile_module(
         src_ast: format!("{:#?}", module_id.ast(db)),
         lowered_ast: format!("{:#?}", module_id.ast(db)),
         contracts,
+        warnings,
     })
 }
 
@@ -251,11 +521,12 @@ fn compile_module(
 fn compile_module(
     db: &mut Db,
     module_id: ModuleId,
+    warnings: Vec<Diagnostic>,
     _with_bytecode: bool,
     _optimize: bool,
 ) -> Result<CompiledModule, CompileError> {
     let mut contracts = IndexMap::default();
-    for contract in module_id.all_contracts(db.upcast()) {
+    for contract in deployable_contracts(db, module_id) {
         let name = &contract.data(db.upcast()).name;
         let abi = db.codegen_abi_contract(contract);
         let yul_contract = compile_to_yul(db, contract);
@@ -264,6 +535,13 @@ fn compile_module(
             name.to_string(),
             CompiledContract {
                 json_abi: serde_json::to_string_pretty(&abi).unwrap(),
+                userdoc: serde_json::to_string_pretty(&natspec::userdoc(db, contract)).unwrap(),
+                devdoc: serde_json::to_string_pretty(&natspec::devdoc(db, contract)).unwrap(),
+                storage_layout: serde_json::to_string_pretty(&storage_layout::storage_layout(
+                    db, contract,
+                ))
+                .unwrap(),
+                solidity_interface: solidity::solidity_interface(&format!("I{name}"), &abi),
                 yul: yul_contract,
             },
         );
@@ -273,9 +551,19 @@ fn compile_module(
         src_ast: format!("{:#?}", module_id.ast(db)),
         lowered_ast: format!("{:#?}", module_id.ast(db)),
         contracts,
+        warnings,
     })
 }
 
+/// The contracts of a module that get compiled, which excludes interfaces.
+fn deployable_contracts(db: &Db, module_id: ModuleId) -> Vec<ContractId> {
+    module_id
+        .all_contracts(db.upcast())
+        .into_iter()
+        .filter(|contract| !contract.is_interface(db.upcast()))
+        .collect()
+}
+
 fn compile_to_yul(db: &mut Db, contract: ContractId) -> String {
     let yul_contract = fe_codegen::yul::isel::lower_contract_deployable(db, contract);
     yul_contract.to_string().replace('"', "\\\"")
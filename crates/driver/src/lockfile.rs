@@ -0,0 +1,179 @@
+use crate::resolver::{path_to_slash_string, DependencyGraph};
+use fe_common::diagnostics::Diagnostic;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use std::path::{Component, Path, PathBuf};
+
+/// The name of the lockfile written next to the manifest of the root ingot.
+pub const LOCKFILE_NAME: &str = "fe.lock";
+
+const LOCKFILE_FORMAT_VERSION: u32 = 1;
+
+const LOCKFILE_HEADER: &str =
+    "# This file is automatically generated by the Fe compiler.\n# It is not intended for manual editing.\n";
+
+/// The resolved dependency graph of an ingot, as recorded in `fe.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(rename = "ingot", default)]
+    pub ingots: Vec<LockedIngot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedIngot {
+    pub name: SmolStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Path of the ingot directory, relative to the root ingot's directory.
+    pub path: String,
+    pub checksum: String,
+    /// Maps the name under which a dependency is used to the dependency's
+    /// ingot name.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub dependencies: IndexMap<SmolStr, SmolStr>,
+}
+
+impl Lockfile {
+    pub fn from_graph(graph: &DependencyGraph) -> Self {
+        let root_dir = canonicalize(&graph.root().dir);
+        let ingots = graph
+            .ingots
+            .iter()
+            .map(|ingot| LockedIngot {
+                name: ingot.name.clone(),
+                version: ingot.version.as_ref().map(ToString::to_string),
+                path: relative_path(&root_dir, &canonicalize(&ingot.dir)),
+                checksum: ingot.checksum.clone(),
+                dependencies: ingot
+                    .dependencies
+                    .iter()
+                    .map(|(dep_name, idx)| (dep_name.clone(), graph.ingots[*idx].name.clone()))
+                    .collect(),
+            })
+            .collect();
+
+        Lockfile {
+            version: LOCKFILE_FORMAT_VERSION,
+            ingots,
+        }
+    }
+
+    /// Reads the lockfile in `dir`. Returns `Ok(None)` if there is none.
+    pub fn load(dir: &Path) -> Result<Option<Self>, Diagnostic> {
+        let path = dir.join(LOCKFILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let path_str = path.to_string_lossy();
+        let content = std::fs::read_to_string(&path).map_err(|err| {
+            Diagnostic::error(format!("failed to read lockfile `{path_str}`: {err}"))
+        })?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|err| Diagnostic::error(format!("invalid lockfile `{path_str}`: {err}")))
+    }
+
+    pub fn write(&self, dir: &Path) -> Result<(), Diagnostic> {
+        let path = dir.join(LOCKFILE_NAME);
+        let content = toml::to_string(self).expect("failed to serialize lockfile");
+        std::fs::write(&path, format!("{LOCKFILE_HEADER}{content}")).map_err(|err| {
+            Diagnostic::error(format!(
+                "failed to write lockfile `{}`: {err}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Describes how `self` differs from the `expected` lockfile.
+    pub fn differences(&self, expected: &Lockfile) -> Vec<String> {
+        let mut notes = vec![];
+        for ingot in &expected.ingots {
+            match self.ingots.iter().find(|locked| locked.name == ingot.name) {
+                None => notes.push(format!("ingot `{}` is not locked", ingot.name)),
+                Some(locked) if locked.version != ingot.version => notes.push(format!(
+                    "ingot `{}` is locked at version `{}`, but the current version is `{}`",
+                    ingot.name,
+                    locked.version.as_deref().unwrap_or("none"),
+                    ingot.version.as_deref().unwrap_or("none"),
+                )),
+                Some(locked) if locked.checksum != ingot.checksum => notes.push(format!(
+                    "the sources of ingot `{}` have changed since it was locked",
+                    ingot.name
+                )),
+                Some(locked) if locked != ingot => notes.push(format!(
+                    "the location or dependencies of ingot `{}` have changed since it was locked",
+                    ingot.name
+                )),
+                Some(_) => {}
+            }
+        }
+        for locked in &self.ingots {
+            if !expected
+                .ingots
+                .iter()
+                .any(|ingot| ingot.name == locked.name)
+            {
+                notes.push(format!("ingot `{}` is no longer a dependency", locked.name));
+            }
+        }
+        if notes.is_empty() && self != expected {
+            notes.push("the lockfile format has changed".into());
+        }
+        notes
+    }
+}
+
+/// Writes `fe.lock` for the given dependency graph into the root ingot's
+/// directory, if its content has changed.
+///
+/// If `locked` is set, the lockfile is never written; instead, an error is
+/// returned if it is missing or out of date.
+pub fn update_lockfile(graph: &DependencyGraph, locked: bool) -> Result<(), Vec<Diagnostic>> {
+    let dir = &graph.root().dir;
+    let expected = Lockfile::from_graph(graph);
+    let existing = Lockfile::load(dir).map_err(|diag| vec![diag])?;
+
+    match existing {
+        Some(existing) if existing == expected => Ok(()),
+        Some(existing) if locked => Err(vec![Diagnostic {
+            notes: existing.differences(&expected),
+            ..Diagnostic::error(format!(
+                "`{LOCKFILE_NAME}` of ingot `{}` is out of date, but `--locked` was passed",
+                graph.root().name
+            ))
+        }]),
+        None if locked => Err(vec![Diagnostic::error(format!(
+            "ingot `{}` has no `{LOCKFILE_NAME}`, but `--locked` was passed",
+            graph.root().name
+        ))]),
+        _ => expected.write(dir).map_err(|diag| vec![diag]),
+    }
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Returns `path` relative to `base`, both of which must be absolute.
+fn relative_path(base: &Path, path: &Path) -> String {
+    let base = base.components().collect::<Vec<_>>();
+    let path = path.components().collect::<Vec<_>>();
+    let common = base
+        .iter()
+        .zip(path.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let relative = std::iter::repeat(Component::ParentDir)
+        .take(base.len() - common)
+        .chain(path[common..].iter().copied())
+        .collect::<PathBuf>();
+    if relative.as_os_str().is_empty() {
+        ".".into()
+    } else {
+        path_to_slash_string(&relative)
+    }
+}
@@ -0,0 +1,67 @@
+use fe_common::diagnostics::Diagnostic;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use smol_str::SmolStr;
+use std::path::Path;
+
+/// The name of the manifest file at the root of an ingot directory.
+pub const MANIFEST_FILE_NAME: &str = "fe.toml";
+
+/// The contents of an `fe.toml` file.
+///
+/// ```toml
+/// name = "my_project"
+/// version = "1.0.0"
+///
+/// [dependencies]
+/// my_lib = { path = "../my_lib", version = "^1.2" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub name: SmolStr,
+    pub version: String,
+    #[serde(default)]
+    pub dependencies: IndexMap<SmolStr, Dependency>,
+}
+
+/// A dependency entry in the `[dependencies]` table of a manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Dependency {
+    /// Path to the dependency's ingot directory, relative to the directory
+    /// containing the manifest.
+    pub path: String,
+    /// Semver requirement that the dependency's manifest version must satisfy.
+    pub version: Option<String>,
+}
+
+impl Manifest {
+    /// Parses the given manifest source. `path` is only used for error
+    /// messages.
+    pub fn parse(path: &str, content: &str) -> Result<Self, Diagnostic> {
+        toml::from_str(content)
+            .map_err(|err| Diagnostic::error(format!("invalid manifest `{path}`: {err}")))
+    }
+
+    /// Reads and parses the manifest in the ingot directory `dir`.
+    ///
+    /// Returns `Ok(None)` if the directory has no manifest file.
+    pub fn load(dir: &Path) -> Result<Option<Self>, Diagnostic> {
+        let path = dir.join(MANIFEST_FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let path_str = path.to_string_lossy();
+        let content = std::fs::read_to_string(&path).map_err(|err| {
+            Diagnostic::error(format!("failed to read manifest `{path_str}`: {err}"))
+        })?;
+        Self::parse(&path_str, &content).map(Some)
+    }
+
+    /// Renders a manifest for a new ingot without dependencies.
+    pub fn template(name: &str) -> String {
+        format!("name = \"{name}\"\nversion = \"1.0.0\"\n\n[dependencies]\n")
+    }
+}
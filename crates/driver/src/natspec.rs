@@ -0,0 +1,227 @@
+use fe_analyzer::namespace::items::{ContractId, FunctionId};
+use fe_analyzer::natspec::NatSpec;
+use fe_analyzer::AnalyzerDb;
+use fe_codegen::db::CodegenDb;
+use fe_mir::db::MirDb;
+use serde_json::{Map, Value};
+use smol_str::SmolStr;
+
+use crate::Db;
+
+/// The NatSpec of a documented contract function, event or error.
+struct Entry {
+    /// The ABI signature, or `constructor`.
+    key: String,
+    natspec: NatSpec,
+    /// The names of the ABI parameters or struct fields.
+    params: Vec<SmolStr>,
+}
+
+/// The user documentation of a contract, in the format of solc's `userdoc`
+/// output: the `@notice` of the contract, its public functions, events and
+/// errors.
+pub fn userdoc(db: &Db, contract: ContractId) -> Value {
+    let notice = |natspec: &NatSpec| {
+        let mut doc = Map::new();
+        if let Some(notice) = &natspec.notice {
+            doc.insert("notice".into(), notice.as_str().into());
+        }
+        doc
+    };
+
+    let mut userdoc = Map::new();
+    userdoc.insert("kind".into(), "user".into());
+    userdoc.insert("methods".into(), entries(functions(db, contract), notice));
+    let events = entries(events(db, contract), notice);
+    if events.as_object().is_some_and(|events| !events.is_empty()) {
+        userdoc.insert("events".into(), events);
+    }
+    let errors = error_entries(errors(db, contract), notice);
+    if errors.as_object().is_some_and(|errors| !errors.is_empty()) {
+        userdoc.insert("errors".into(), errors);
+    }
+    userdoc.extend(notice(&contract_natspec(db, contract)));
+    userdoc.insert("version".into(), 1.into());
+    userdoc.into()
+}
+
+/// The developer documentation of a contract, in the format of solc's
+/// `devdoc` output: the `@title`, `@author`, `@dev`, `@param`, `@return` and
+/// `@custom:...` tags of the contract, its public functions, events and
+/// errors.
+pub fn devdoc(db: &Db, contract: ContractId) -> Value {
+    let mut devdoc = Map::new();
+    devdoc.insert("kind".into(), "dev".into());
+    devdoc.insert(
+        "methods".into(),
+        entries(functions(db, contract), |natspec| details(natspec, true)),
+    );
+    let events = entries(events(db, contract), |natspec| details(natspec, false));
+    if events.as_object().is_some_and(|events| !events.is_empty()) {
+        devdoc.insert("events".into(), events);
+    }
+    let errors = error_entries(errors(db, contract), |natspec| details(natspec, false));
+    if errors.as_object().is_some_and(|errors| !errors.is_empty()) {
+        devdoc.insert("errors".into(), errors);
+    }
+
+    let natspec = contract_natspec(db, contract);
+    if let Some(title) = &natspec.title {
+        devdoc.insert("title".into(), title.as_str().into());
+    }
+    if let Some(author) = &natspec.author {
+        devdoc.insert("author".into(), author.as_str().into());
+    }
+    devdoc.extend(details(&natspec, false));
+    devdoc.insert("version".into(), 1.into());
+    devdoc.into()
+}
+
+/// The `@dev`, `@param`, `@return` and custom tags of an item.
+fn details(natspec: &NatSpec, with_returns: bool) -> Map<String, Value> {
+    let mut doc = Map::new();
+    if let Some(dev) = &natspec.dev {
+        doc.insert("details".into(), dev.as_str().into());
+    }
+    if !natspec.params.is_empty() {
+        let params = natspec
+            .params
+            .iter()
+            .map(|(name, text)| (name.to_string(), Value::from(text.as_str())))
+            .collect::<Map<_, _>>();
+        doc.insert("params".into(), params.into());
+    }
+    if with_returns && !natspec.returns.is_empty() {
+        let returns = natspec
+            .returns
+            .iter()
+            .enumerate()
+            .map(|(idx, text)| (format!("_{idx}"), Value::from(text.as_str())))
+            .collect::<Map<_, _>>();
+        doc.insert("returns".into(), returns.into());
+    }
+    for (name, text) in &natspec.custom {
+        doc.insert(format!("custom:{name}"), text.as_str().into());
+    }
+    doc
+}
+
+/// Renders the entries into an object keyed by their signatures, leaving
+/// out the ones without documentation. `@param` tags that don't name an ABI
+/// parameter (e.g. `ctx`) are dropped.
+fn entries(entries: Vec<Entry>, render: impl Fn(&NatSpec) -> Map<String, Value>) -> Value {
+    entries
+        .into_iter()
+        .filter_map(|entry| render_entry(entry, &render))
+        .collect::<Map<_, _>>()
+        .into()
+}
+
+/// Renders the errors like `entries`, except that each signature maps to a
+/// list, as errors of different modules can have the same signature.
+fn error_entries(entries: Vec<Entry>, render: impl Fn(&NatSpec) -> Map<String, Value>) -> Value {
+    let mut errors = Map::new();
+    for (key, doc) in entries
+        .into_iter()
+        .filter_map(|entry| render_entry(entry, &render))
+    {
+        match errors.entry(key).or_insert_with(|| Value::Array(vec![])) {
+            Value::Array(docs) => docs.push(doc),
+            _ => unreachable!(),
+        }
+    }
+    errors.into()
+}
+
+fn render_entry(
+    mut entry: Entry,
+    render: impl Fn(&NatSpec) -> Map<String, Value>,
+) -> Option<(String, Value)> {
+    entry
+        .natspec
+        .params
+        .retain(|(name, _)| entry.params.contains(name));
+    let doc = render(&entry.natspec);
+    (!doc.is_empty()).then(|| (entry.key, Value::from(doc)))
+}
+
+fn contract_natspec(db: &Db, contract: ContractId) -> NatSpec {
+    NatSpec::parse(
+        contract
+            .data(db)
+            .ast
+            .kind
+            .doc
+            .as_deref()
+            .unwrap_or_default(),
+    )
+}
+
+/// The constructor and the public functions of the contract.
+fn functions(db: &Db, contract: ContractId) -> Vec<Entry> {
+    let function_entry = |function: FunctionId| {
+        let mir_func = db.mir_lowered_func_signature(function);
+        let key = if function.is_constructor(db) {
+            "constructor".to_string()
+        } else {
+            db.codegen_abi_function(mir_func)
+                .selector()
+                .selector_signature()
+                .to_string()
+        };
+        Entry {
+            key,
+            natspec: NatSpec::parse(function.doc(db).as_deref().unwrap_or_default()),
+            params: db
+                .codegen_legalized_signature(mir_func)
+                .params
+                .iter()
+                .map(|param| param.name.clone())
+                .collect(),
+        }
+    };
+
+    contract
+        .init_function(db)
+        .into_iter()
+        .chain(contract.public_functions(db).values().copied())
+        .map(function_entry)
+        .collect()
+}
+
+/// The events that can be emitted by the contract. The structs that the
+/// contract reverts with are errors instead.
+fn events(db: &Db, contract: ContractId) -> Vec<Entry> {
+    let errors = contract.error_types(db);
+    db.module_structs(contract.module(db))
+        .iter()
+        .filter(|struct_| struct_.as_type(db).is_emittable(db) && !errors.contains(struct_))
+        .map(|struct_| {
+            let event = db.codegen_abi_event(db.mir_lowered_type(struct_.as_type(db)));
+            Entry {
+                key: event.signature().signature().to_string(),
+                natspec: NatSpec::parse(struct_.doc(db).as_deref().unwrap_or_default()),
+                params: struct_.fields(db).keys().cloned().collect(),
+            }
+        })
+        .collect()
+}
+
+/// The errors that the contract reverts with.
+fn errors(db: &Db, contract: ContractId) -> Vec<Entry> {
+    contract
+        .error_types(db)
+        .iter()
+        .filter_map(|error| {
+            Some(Entry {
+                key: error
+                    .abi_error(db)?
+                    .selector()
+                    .selector_signature()
+                    .to_string(),
+                natspec: NatSpec::parse(error.doc(db).as_deref().unwrap_or_default()),
+                params: error.fields(db).keys().cloned().collect(),
+            })
+        })
+        .collect()
+}
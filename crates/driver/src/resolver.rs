@@ -0,0 +1,264 @@
+use crate::ingot::load_files_from_dir;
+use crate::manifest::Manifest;
+use fe_common::diagnostics::Diagnostic;
+use fe_common::utils::keccak;
+use indexmap::IndexMap;
+use semver::{Version, VersionReq};
+use smol_str::SmolStr;
+use std::path::{Path, PathBuf};
+
+/// The ingot name used for directories that don't have a manifest.
+const DEFAULT_INGOT_NAME: &str = "main";
+
+/// An ingot located on disk, along with its resolved dependencies.
+#[derive(Debug, Clone)]
+pub struct ResolvedIngot {
+    pub name: SmolStr,
+    /// The version declared in the manifest, or `None` if the ingot
+    /// directory doesn't have one.
+    pub version: Option<Version>,
+    pub dir: PathBuf,
+    /// `(file_path, file_content)` of every source file in the ingot.
+    pub files: Vec<(String, String)>,
+    /// Hash of the ingot's source files.
+    pub checksum: String,
+    /// Maps the name under which a dependency is used to the index of the
+    /// dependency in [`DependencyGraph::ingots`].
+    pub dependencies: IndexMap<SmolStr, usize>,
+}
+
+impl ResolvedIngot {
+    pub fn has_manifest(&self) -> bool {
+        self.version.is_some()
+    }
+}
+
+/// The set of ingots reachable from a root ingot.
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    /// Ingots in dependency order: every ingot comes after the ingots it
+    /// depends on, so the root ingot is always last.
+    pub ingots: Vec<ResolvedIngot>,
+}
+
+impl DependencyGraph {
+    pub fn root(&self) -> &ResolvedIngot {
+        self.ingots.last().expect("empty dependency graph")
+    }
+
+    pub fn root_index(&self) -> usize {
+        self.ingots.len() - 1
+    }
+}
+
+/// Locates the ingot in `dir` and everything it depends on.
+///
+/// Dependencies are read from the `[dependencies]` table of each ingot's
+/// manifest. Every version requirement is checked against the version in the
+/// dependency's own manifest, and dependency cycles are rejected. An ingot
+/// that is reached through several paths (a "diamond") is only resolved once,
+/// but two distinct ingot directories may not share a name.
+pub fn resolve(dir: &Path) -> Result<DependencyGraph, Vec<Diagnostic>> {
+    let mut resolver = Resolver::default();
+    resolver.visit(dir, DEFAULT_INGOT_NAME);
+    if resolver.diags.is_empty() {
+        Ok(DependencyGraph {
+            ingots: resolver.ingots,
+        })
+    } else {
+        Err(resolver.diags)
+    }
+}
+
+/// Hashes the given source files. The result doesn't depend on the order of
+/// the files, nor on the directory that contains the ingot.
+pub fn checksum(src_dir: &Path, files: &[(String, String)]) -> String {
+    let mut entries = files
+        .iter()
+        .map(|(path, content)| {
+            let path = Path::new(path)
+                .strip_prefix(src_dir)
+                .unwrap_or(Path::new(path));
+            (path_to_slash_string(path), content.as_str())
+        })
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    let mut bytes = vec![];
+    for (path, content) in entries {
+        bytes.extend_from_slice(path.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(content.as_bytes());
+        bytes.push(0);
+    }
+    keccak::full(&bytes)
+}
+
+pub(crate) fn path_to_slash_string(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[derive(Default)]
+struct Resolver {
+    ingots: Vec<ResolvedIngot>,
+    /// Maps canonical ingot directories to indices into `ingots`.
+    visited: IndexMap<PathBuf, usize>,
+    /// `(canonical_dir, name)` of the ingots that are currently being resolved.
+    stack: Vec<(PathBuf, SmolStr)>,
+    diags: Vec<Diagnostic>,
+}
+
+impl Resolver {
+    fn visit(&mut self, dir: &Path, default_name: &str) -> Option<usize> {
+        let display = dir.display();
+        if !dir.is_dir() {
+            self.error(format!("ingot directory does not exist: `{display}`"));
+            return None;
+        }
+        let canonical = match dir.canonicalize() {
+            Ok(path) => path,
+            Err(err) => {
+                self.error(format!(
+                    "failed to resolve ingot directory `{display}`: {err}"
+                ));
+                return None;
+            }
+        };
+
+        if let Some(idx) = self.visited.get(&canonical) {
+            return Some(*idx);
+        }
+        if let Some(pos) = self.stack.iter().position(|(dir, _)| *dir == canonical) {
+            let cycle = self.stack[pos..]
+                .iter()
+                .map(|(_, name)| format!("`{name}`"))
+                .chain([format!("`{}`", self.stack[pos].1)])
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            self.error(format!("dependency cycle detected: {cycle}"));
+            return None;
+        }
+
+        let manifest = match Manifest::load(dir) {
+            Ok(manifest) => manifest,
+            Err(diag) => {
+                self.diags.push(diag);
+                return None;
+            }
+        };
+        let (name, version, src_dir): (SmolStr, _, _) = match &manifest {
+            Some(manifest) => match Version::parse(&manifest.version) {
+                Ok(version) => (manifest.name.clone(), Some(version), dir.join("src")),
+                Err(err) => {
+                    self.error(format!(
+                        "invalid version `{}` in the manifest of ingot `{}`: {err}",
+                        manifest.version, manifest.name
+                    ));
+                    return None;
+                }
+            },
+            None => (default_name.into(), None, dir.to_path_buf()),
+        };
+
+        self.stack.push((canonical.clone(), name.clone()));
+        let mut dependencies = IndexMap::default();
+        for (dep_name, dep) in manifest.iter().flat_map(|m| m.dependencies.iter()) {
+            let dep_dir = dir.join(&dep.path);
+            if let Some(idx) = self.visit(&dep_dir, dep_name) {
+                if let Some(req) = &dep.version {
+                    self.check_version(&name, dep_name, req, idx);
+                }
+                dependencies.insert(dep_name.clone(), idx);
+            }
+        }
+        self.stack.pop();
+
+        let files = match load_files_from_dir(&src_dir) {
+            Ok(files) if files.is_empty() => {
+                self.error(format!(
+                    "directory is not an ingot: `{}`",
+                    src_dir.display()
+                ));
+                return None;
+            }
+            Ok(files) => files,
+            Err(err) => {
+                self.error(format!("failed to load files of ingot `{name}`: {err}"));
+                return None;
+            }
+        };
+
+        if let Some(other) = self.ingots.iter().find(|ingot| ingot.name == name) {
+            self.diags.push(Diagnostic {
+                notes: vec![
+                    format!("first ingot: `{}`", other.dir.display()),
+                    format!("second ingot: `{display}`"),
+                ],
+                ..Diagnostic::error(format!(
+                    "the dependency graph contains two different ingots named `{name}`"
+                ))
+            });
+            return None;
+        }
+
+        let idx = self.ingots.len();
+        self.ingots.push(ResolvedIngot {
+            name,
+            version,
+            dir: dir.to_path_buf(),
+            checksum: checksum(&src_dir, &files),
+            files,
+            dependencies,
+        });
+        self.visited.insert(canonical, idx);
+        Some(idx)
+    }
+
+    fn check_version(&mut self, dependent: &str, dep_name: &str, req: &str, dep_idx: usize) {
+        let req = match VersionReq::parse(req) {
+            Ok(req) => req,
+            Err(err) => {
+                self.error(format!(
+                    "invalid version requirement `{req}` for dependency `{dep_name}` of ingot `{dependent}`: {err}"
+                ));
+                return;
+            }
+        };
+
+        let dep = &self.ingots[dep_idx];
+        match &dep.version {
+            Some(version) if req.matches(version) => {}
+            Some(version) => {
+                let message = format!(
+                    "ingot `{dependent}` requires `{dep_name}` version `{req}`, but `{}` has version `{version}`",
+                    dep.dir.display()
+                );
+                self.error(message)
+            }
+            None => {
+                let message = format!(
+                    "ingot `{dependent}` requires `{dep_name}` version `{req}`, but `{}` has no manifest",
+                    dep.dir.display()
+                );
+                self.error(message)
+            }
+        }
+    }
+
+    fn error(&mut self, message: String) {
+        let mut diag = Diagnostic::error(message);
+        if !self.stack.is_empty() {
+            let chain = self
+                .stack
+                .iter()
+                .map(|(_, name)| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            diag.notes.push(format!("required by {chain}"));
+        }
+        self.diags.push(diag);
+    }
+}
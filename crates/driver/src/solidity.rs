@@ -0,0 +1,298 @@
+use fe_abi::{
+    contract::AbiContract,
+    function::{AbiFunction, AbiFunctionType, StateMutability},
+    types::{AbiTupleField, AbiType},
+};
+use indexmap::IndexSet;
+
+use crate::abi_import::struct_name;
+
+/// Words that can name a Fe variable or field, but not a Solidity one.
+const RESERVED: &[&str] = &[
+    "abstract",
+    "address",
+    "after",
+    "alias",
+    "anonymous",
+    "apply",
+    "assembly",
+    "auto",
+    "bool",
+    "byte",
+    "bytes",
+    "calldata",
+    "case",
+    "catch",
+    "constant",
+    "constructor",
+    "copyof",
+    "days",
+    "default",
+    "define",
+    "delete",
+    "do",
+    "emit",
+    "ether",
+    "event",
+    "external",
+    "fallback",
+    "final",
+    "function",
+    "gwei",
+    "hex",
+    "hours",
+    "immutable",
+    "implements",
+    "indexed",
+    "inline",
+    "internal",
+    "is",
+    "library",
+    "macro",
+    "mapping",
+    "memory",
+    "minutes",
+    "modifier",
+    "mutable",
+    "new",
+    "null",
+    "of",
+    "override",
+    "partial",
+    "payable",
+    "private",
+    "promise",
+    "public",
+    "pure",
+    "receive",
+    "reference",
+    "relocatable",
+    "returns",
+    "sealed",
+    "seconds",
+    "sizeof",
+    "static",
+    "storage",
+    "string",
+    "super",
+    "supports",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typedef",
+    "typeof",
+    "unchecked",
+    "var",
+    "view",
+    "virtual",
+    "weeks",
+    "wei",
+    "years",
+];
+
+/// Renders a Solidity interface named `name` with the functions, events and
+/// errors of a contract's ABI.
+///
+/// The ABI doesn't keep the names of struct types, so a struct is named after
+/// the first parameter or field that has its type.
+pub fn solidity_interface(name: &str, abi: &AbiContract) -> String {
+    let mut interface = Interface::default();
+    // Struct names mustn't clash with the events and errors.
+    let item_names = abi.events.iter().map(|event| &event.name);
+    let item_names = item_names.chain(abi.errors.iter().map(|error| &error.name));
+    interface.names.extend(item_names.cloned());
+
+    let events = abi
+        .events
+        .iter()
+        .map(|event| {
+            let fields = event
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let indexed = if field.indexed { " indexed" } else { "" };
+                    let ty = interface.type_name(&field.ty, &field.name);
+                    format!("{ty}{indexed}{}", param_name(&field.name, i))
+                })
+                .collect::<Vec<_>>();
+            let anonymous = if event.anonymous { " anonymous" } else { "" };
+            format!("event {}({}){anonymous};", event.name, fields.join(", "))
+        })
+        .collect::<Vec<_>>();
+
+    let errors = abi
+        .errors
+        .iter()
+        .map(|error| {
+            let fields = error
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let ty = interface.type_name(&field.ty, &field.name);
+                    format!("{ty}{}", param_name(&field.name, i))
+                })
+                .collect::<Vec<_>>();
+            let decl = format!("error {}({});", error.name, fields.join(", "));
+            if matches!(error.name.as_str(), "Error" | "Panic") {
+                // Solidity reserves the names of its built-in errors.
+                format!("// {decl}")
+            } else {
+                decl
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let functions = abi
+        .funcs
+        .iter()
+        .filter_map(|func| interface.function(func))
+        .collect::<Vec<_>>();
+
+    let mut src = format!(
+        "// SPDX-License-Identifier: UNLICENSED\npragma solidity ^0.8.4;\n\ninterface {name} {{\n"
+    );
+    let sections = [interface.structs, events, errors, functions];
+    for (i, section) in sections
+        .iter()
+        .filter(|section| !section.is_empty())
+        .enumerate()
+    {
+        if i > 0 {
+            src += "\n";
+        }
+        for item in section {
+            for line in item.lines() {
+                src += &format!("    {line}\n");
+            }
+        }
+    }
+    src += "}\n";
+    src
+}
+
+#[derive(Default)]
+struct Interface {
+    names: IndexSet<String>,
+    /// The name of the struct declared for each tuple type.
+    tuples: Vec<(AbiType, String)>,
+    structs: Vec<String>,
+}
+
+impl Interface {
+    fn function(&mut self, func: &AbiFunction) -> Option<String> {
+        let mutability = match func.state_mutability {
+            StateMutability::Pure => " pure",
+            StateMutability::View => " view",
+            StateMutability::Nonpayable => "",
+            StateMutability::Payable => " payable",
+        };
+        match func.func_type {
+            AbiFunctionType::Function | AbiFunctionType::Payable => {}
+            AbiFunctionType::Receive => return Some("receive() external payable;".into()),
+            AbiFunctionType::Fallback => return Some(format!("fallback() external{mutability};")),
+            // Interfaces can't declare a constructor.
+            AbiFunctionType::Constructor => return None,
+        }
+
+        let params = func
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let ty = self.type_name(&param.ty, &param.name);
+                format!(
+                    "{ty}{}{}",
+                    location(&param.ty, "calldata"),
+                    param_name(&param.name, i)
+                )
+            })
+            .collect::<Vec<_>>();
+        let returns = func
+            .outputs
+            .iter()
+            .map(|output| {
+                let ty = self.type_name(&output.ty, &output.name);
+                format!("{ty}{}", location(&output.ty, "memory"))
+            })
+            .collect::<Vec<_>>();
+
+        let mut sig = format!(
+            "function {}({}) external{mutability}",
+            func.name,
+            params.join(", ")
+        );
+        if !returns.is_empty() {
+            sig += &format!(" returns ({})", returns.join(", "));
+        }
+        sig += ";";
+        Some(sig)
+    }
+
+    /// The Solidity type of an ABI type. `hint` names the struct of a tuple type.
+    fn type_name(&mut self, ty: &AbiType, hint: &str) -> String {
+        match ty {
+            AbiType::Tuple(fields) => self.tuple_struct(ty, fields, hint),
+            AbiType::Array { elem_ty, len } if **elem_ty != AbiType::UInt(8) => {
+                format!("{}[{len}]", self.type_name(elem_ty, hint))
+            }
+            AbiType::DynArray { elem_ty } => format!("{}[]", self.type_name(elem_ty, hint)),
+            AbiType::Function => unreachable!("Fe has no function types"),
+            _ => ty.selector_type_name(),
+        }
+    }
+
+    fn tuple_struct(&mut self, ty: &AbiType, fields: &[AbiTupleField], hint: &str) -> String {
+        if let Some((_, name)) = self.tuples.iter().find(|(tuple, _)| tuple == ty) {
+            return name.clone();
+        }
+
+        let base = struct_name(hint);
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while self.names.contains(&name) {
+            suffix += 1;
+            name = format!("{base}{suffix}");
+        }
+        self.names.insert(name.clone());
+
+        let mut def = format!("struct {name} {{\n");
+        for (i, field) in fields.iter().enumerate() {
+            let ty = self.type_name(&field.ty, &field.name);
+            def += &format!("    {ty}{};\n", param_name(&field.name, i));
+        }
+        def += "}";
+
+        self.tuples.push((ty.clone(), name.clone()));
+        self.structs.push(def);
+        name
+    }
+}
+
+/// The data location of a parameter, which is required for reference types.
+fn location(ty: &AbiType, location: &str) -> String {
+    match ty {
+        AbiType::Bytes
+        | AbiType::String
+        | AbiType::Array { .. }
+        | AbiType::DynArray { .. }
+        | AbiType::Tuple(_) => format!(" {location}"),
+        _ => String::new(),
+    }
+}
+
+/// The name of a parameter or field with a leading space, or nothing if it's
+/// unnamed. Names that Solidity doesn't accept get an underscore.
+fn param_name(name: &str, position: usize) -> String {
+    if name.is_empty() {
+        format!(" _{position}")
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!(" _{name}")
+    } else if RESERVED.contains(&name) {
+        format!(" {name}_")
+    } else {
+        format!(" {name}")
+    }
+}
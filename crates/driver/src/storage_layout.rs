@@ -0,0 +1,35 @@
+use fe_analyzer::{display::Displayable, namespace::items::ContractId};
+use fe_codegen::db::CodegenDb;
+use fe_mir::ir::TypeId;
+use serde_json::{json, Value};
+
+use crate::Db;
+
+/// The storage layout of a contract as JSON, with an entry per field.
+pub fn storage_layout(db: &Db, contract: ContractId) -> Value {
+    let layout = db.codegen_contract_storage_layout(contract);
+
+    let storage: Vec<_> = layout
+        .fields
+        .iter()
+        .map(|field| {
+            json!({
+                "name": field.name.as_str(),
+                "type": type_name(db, field.ty),
+                "slot": field.slot,
+                "offset": field.offset,
+                "size": field.size,
+                "encoding": field.encoding.as_str(),
+            })
+        })
+        .collect();
+    json!({ "storage": storage })
+}
+
+/// The name of a field type as it is written in Fe.
+fn type_name(db: &Db, ty: TypeId) -> String {
+    match ty.data(db).analyzer_ty {
+        Some(analyzer_ty) => analyzer_ty.display(db).to_string(),
+        None => ty.as_string(db),
+    }
+}
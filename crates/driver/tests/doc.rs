@@ -0,0 +1,16 @@
+use fe_driver::DocFormat;
+use std::path::Path;
+
+#[test]
+fn markdown_docs() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../test-files/fixtures/ingots/doc_ingot");
+    let mut db = fe_driver::Db::default();
+    let pages = fe_driver::document_ingot_dir(&mut db, &dir, true, DocFormat::Markdown)
+        .unwrap_or_else(|err| panic!("{:?}", err.0));
+
+    let output: String = pages
+        .iter()
+        .map(|(path, page)| format!("==> {path} <==\n{page}\n"))
+        .collect();
+    insta::assert_snapshot!(output);
+}
@@ -0,0 +1,83 @@
+use fe_common::diagnostics::Diagnostic;
+use std::path::{Path, PathBuf};
+
+fn ingot_dir(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../test-files/fixtures/ingots")
+        .join(name)
+}
+
+/// Checks the ingot with `--locked`, so that the fixtures' lockfiles are never
+/// written.
+fn check_locked(name: &str) -> Vec<Diagnostic> {
+    let mut db = fe_driver::Db::default();
+    fe_driver::check_ingot_dir(&mut db, &ingot_dir(name), true)
+}
+
+#[test]
+fn locked_ingot() {
+    let diags = check_locked("manifest_ingot");
+    assert!(diags.is_empty(), "{diags:?}");
+}
+
+#[test]
+fn version_requirement_mismatch() {
+    let diags = fe_driver::resolve(&ingot_dir("version_mismatch_ingot")).unwrap_err();
+    assert_eq!(diags.len(), 1);
+    assert!(
+        diags[0].message.starts_with(
+            "ingot `version_mismatch_ingot` requires `counters` version `^2.0`, but `"
+        ),
+        "{}",
+        diags[0].message
+    );
+    assert!(diags[0].message.ends_with("` has version `1.0.0`"));
+    assert_eq!(diags[0].notes, ["required by `version_mismatch_ingot`"]);
+}
+
+#[test]
+fn dependency_cycle() {
+    let diags = fe_driver::resolve(&ingot_dir("cycle_ingot_a")).unwrap_err();
+    assert_eq!(diags.len(), 1);
+    assert_eq!(
+        diags[0].message,
+        "dependency cycle detected: `cycle_ingot_a` -> `cycle_ingot_b` -> `cycle_ingot_a`"
+    );
+}
+
+#[test]
+fn duplicate_ingot_names() {
+    let diags = fe_driver::resolve(&ingot_dir("duplicate_name_ingot")).unwrap_err();
+    assert_eq!(diags.len(), 1);
+    assert_eq!(
+        diags[0].message,
+        "the dependency graph contains two different ingots named `manifest_lib`"
+    );
+    assert!(diags[0].notes[0].ends_with("manifest_lib`"));
+    assert!(diags[0].notes[1].ends_with("duplicate_name_lib`"));
+}
+
+#[test]
+fn missing_lockfile() {
+    let diags = check_locked("missing_lockfile_ingot");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(
+        diags[0].message,
+        "ingot `missing_lockfile_ingot` has no `fe.lock`, but `--locked` was passed"
+    );
+    assert!(!ingot_dir("missing_lockfile_ingot").join("fe.lock").exists());
+}
+
+#[test]
+fn stale_lockfile() {
+    let diags = check_locked("stale_lockfile_ingot");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(
+        diags[0].message,
+        "`fe.lock` of ingot `stale_lockfile_ingot` is out of date, but `--locked` was passed"
+    );
+    assert_eq!(
+        diags[0].notes,
+        ["the sources of ingot `stale_lockfile_ingot` have changed since it was locked"]
+    );
+}
@@ -0,0 +1,11 @@
+#[test]
+fn natspec_json() {
+    let src = include_str!("../../test-files/fixtures/features/natspec.fe");
+    let mut db = fe_driver::Db::default();
+    let module = fe_driver::compile_single_file(&mut db, "natspec.fe", src, false, false)
+        .unwrap_or_else(|err| panic!("{:?}", err.0));
+    let contract = &module.contracts["Vault"];
+
+    insta::assert_snapshot!("userdoc", contract.userdoc);
+    insta::assert_snapshot!("devdoc", contract.devdoc);
+}
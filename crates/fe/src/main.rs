@@ -17,12 +17,21 @@ fn main() {
     let cli = FelangCli::parse();
 
     match cli.command {
+        Commands::AbiImport(arg) => {
+            task::abi_import(arg);
+        }
         Commands::Build(arg) => {
             task::build(arg);
         }
         Commands::Check(arg) => {
             task::check(arg);
         }
+        Commands::Doc(arg) => {
+            task::doc(arg);
+        }
+        Commands::Fmt(arg) => {
+            task::fmt(arg);
+        }
         Commands::New(arg) => {
             task::create_new_project(arg);
         }
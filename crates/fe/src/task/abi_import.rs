@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+
+use super::build::write_output;
+
+#[derive(Args)]
+#[clap(about = "Generate a Fe interface from a contract's ABI JSON")]
+pub struct AbiImportArgs {
+    input_path: String,
+    /// The name of the interface, by default the name of the input file
+    #[clap(long)]
+    name: Option<String>,
+    /// The `.fe` file to write, by default next to the input file
+    #[clap(short, long)]
+    output: Option<String>,
+    /// The capacity of the `String` and `Array<u8, N>` types used for
+    /// `string` and `bytes`
+    #[clap(long, default_value = "256")]
+    max_len: usize,
+    #[clap(long)]
+    overwrite: bool,
+}
+
+pub fn abi_import(args: AbiImportArgs) {
+    let input_path = Path::new(&args.input_path);
+    let content = match fs::read_to_string(input_path) {
+        Err(err) => {
+            eprintln!("Failed to load file: `{}`. Error: {err}", args.input_path);
+            std::process::exit(1)
+        }
+        Ok(content) => content,
+    };
+
+    let name = args.name.unwrap_or_else(|| {
+        input_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+    let imported = match fe_driver::import_abi(&name, &content, args.max_len) {
+        Ok(imported) => imported,
+        Err(err) => {
+            eprintln!("Unable to import {}. Error: {err}", args.input_path);
+            std::process::exit(1)
+        }
+    };
+    for skipped in &imported.skipped {
+        eprintln!("Skipped {skipped}");
+    }
+
+    let output_path = args
+        .output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| input_path.with_extension("fe"));
+    if output_path.exists() && !args.overwrite {
+        eprintln!(
+            "File '{}' already exists. Use --overwrite to overwrite.",
+            output_path.display()
+        );
+        std::process::exit(1)
+    }
+    match write_output(&output_path, &imported.src) {
+        Ok(_) => eprintln!("Wrote interface `{name}` to `{}`", output_path.display()),
+        Err(err) => {
+            eprintln!(
+                "Failed to write output to `{}`. Error: {err}",
+                output_path.display()
+            );
+            std::process::exit(1)
+        }
+    }
+}
@@ -3,22 +3,25 @@ use std::io::{Error, Write};
 use std::path::Path;
 
 use clap::{ArgEnum, Args};
-use fe_common::diagnostics::print_diagnostics;
+use fe_common::diagnostics::{print_diagnostics, print_diagnostics_in_format};
 use fe_common::files::SourceFileId;
 use fe_driver::CompiledModule;
 
-const DEFAULT_OUTPUT_DIR_NAME: &str = "output";
-const DEFAULT_INGOT: &str = "main";
+use super::MessageFormat;
 
-use super::utils::load_files_from_dir;
+const DEFAULT_OUTPUT_DIR_NAME: &str = "output";
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum, Debug)]
 enum Emit {
     Abi,
     Ast,
+    Devdoc,
     LoweredAst,
     Bytecode,
+    SolidityInterface,
+    StorageLayout,
     Tokens,
+    Userdoc,
     Yul,
 }
 
@@ -42,6 +45,11 @@ pub struct BuildArgs {
     overwrite: bool,
     #[clap(long, takes_value(true))]
     optimize: Option<bool>,
+    /// Require `fe.lock` to be up to date instead of updating it
+    #[clap(long)]
+    locked: bool,
+    #[clap(arg_enum, long, default_value = "human")]
+    message_format: MessageFormat,
 }
 
 fn build_single_file(compile_arg: &BuildArgs) -> (String, CompiledModule) {
@@ -66,10 +74,15 @@ fn build_single_file(compile_arg: &BuildArgs) -> (String, CompiledModule) {
         with_bytecode,
         optimize,
     ) {
-        Ok(module) => module,
+        Ok(module) => {
+            // Warnings are reported on success too, and machine-readable formats
+            // expect a (possibly empty) report.
+            print_diagnostics_in_format(&db, &module.warnings, compile_arg.message_format.into());
+            module
+        }
         Err(error) => {
             eprintln!("Unable to compile {input_path}.");
-            print_diagnostics(&db, &error.0);
+            print_diagnostics_in_format(&db, &error.0, compile_arg.message_format.into());
             std::process::exit(1)
         }
     };
@@ -87,30 +100,23 @@ fn build_ingot(compile_arg: &BuildArgs) -> (String, CompiledModule) {
         std::process::exit(1)
     }
 
-    let files = match load_files_from_dir(input_path) {
-        Ok(files) if files.is_empty() => {
-            eprintln!("Input directory is not an ingot: `{input_path}`");
-            std::process::exit(1)
-        }
-        Ok(files) => files,
-        Err(err) => {
-            eprintln!("Failed to load project files. Error: {err}");
-            std::process::exit(1)
-        }
-    };
-
     let mut db = fe_driver::Db::default();
-    let compiled_module = match fe_driver::compile_ingot(
+    let compiled_module = match fe_driver::compile_ingot_dir(
         &mut db,
-        DEFAULT_INGOT, // TODO: real ingot name
-        &files,
+        Path::new(input_path),
+        compile_arg.locked,
         with_bytecode,
         optimize,
     ) {
-        Ok(module) => module,
+        Ok(module) => {
+            // Warnings are reported on success too, and machine-readable formats
+            // expect a (possibly empty) report.
+            print_diagnostics_in_format(&db, &module.warnings, compile_arg.message_format.into());
+            module
+        }
         Err(error) => {
             eprintln!("Unable to compile {input_path}.");
-            print_diagnostics(&db, &error.0);
+            print_diagnostics_in_format(&db, &error.0, compile_arg.message_format.into());
             std::process::exit(1)
         }
     };
@@ -197,6 +203,32 @@ fn write_compiled_module(
             write_output(&contract_output_dir.join(file_name), &contract.json_abi)?;
         }
 
+        if targets.contains(&Emit::Userdoc) {
+            let file_name = format!("{}_userdoc.json", &name);
+            write_output(&contract_output_dir.join(file_name), &contract.userdoc)?;
+        }
+
+        if targets.contains(&Emit::Devdoc) {
+            let file_name = format!("{}_devdoc.json", &name);
+            write_output(&contract_output_dir.join(file_name), &contract.devdoc)?;
+        }
+
+        if targets.contains(&Emit::SolidityInterface) {
+            let file_name = format!("I{}.sol", &name);
+            write_output(
+                &contract_output_dir.join(file_name),
+                &contract.solidity_interface,
+            )?;
+        }
+
+        if targets.contains(&Emit::StorageLayout) {
+            let file_name = format!("{}_storage_layout.json", &name);
+            write_output(
+                &contract_output_dir.join(file_name),
+                &contract.storage_layout,
+            )?;
+        }
+
         if targets.contains(&Emit::Yul) {
             let file_name = format!("{}_ir.yul", &name);
             write_output(&contract_output_dir.join(file_name), &contract.yul)?;
@@ -212,7 +244,7 @@ fn write_compiled_module(
     Ok(())
 }
 
-fn write_output(path: &Path, content: &str) -> Result<(), String> {
+pub(super) fn write_output(path: &Path, content: &str) -> Result<(), String> {
     let mut file = fs::OpenOptions::new()
         .write(true)
         .create(true)
@@ -224,11 +256,11 @@ fn write_output(path: &Path, content: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn ioerr_to_string(error: Error) -> String {
+pub(super) fn ioerr_to_string(error: Error) -> String {
     format!("{error}")
 }
 
-fn verify_nonexistent_or_empty(dir: &Path) -> Result<(), String> {
+pub(super) fn verify_nonexistent_or_empty(dir: &Path) -> Result<(), String> {
     if !dir.exists() || dir.read_dir().map_err(ioerr_to_string)?.next().is_none() {
         Ok(())
     } else {
@@ -1,17 +1,20 @@
 use std::path::Path;
 
 use clap::Args;
-use fe_common::diagnostics::{print_diagnostics, Diagnostic};
+use fe_common::diagnostics::{print_diagnostics_in_format, Diagnostic};
 use fe_driver::Db;
 
-use super::utils::load_files_from_dir;
-
-const DEFAULT_INGOT_NAME: &str = "main";
+use super::MessageFormat;
 
 #[derive(Args)]
 #[clap(about = "Analyze the current project and report errors, but don't build artifacts")]
 pub struct CheckArgs {
     input_path: String,
+    /// Require `fe.lock` to be up to date instead of updating it
+    #[clap(long)]
+    locked: bool,
+    #[clap(arg_enum, long, default_value = "human")]
+    message_format: MessageFormat,
 }
 
 fn check_single_file(db: &mut Db, input_path: &str) -> Vec<Diagnostic> {
@@ -26,25 +29,13 @@ fn check_single_file(db: &mut Db, input_path: &str) -> Vec<Diagnostic> {
     fe_driver::check_single_file(db, input_path, &content)
 }
 
-fn check_ingot(db: &mut Db, input_path: &str) -> Vec<Diagnostic> {
+fn check_ingot(db: &mut Db, input_path: &str, locked: bool) -> Vec<Diagnostic> {
     if !Path::new(input_path).exists() {
         eprintln!("Input directory does not exist: `{input_path}`.");
         std::process::exit(1)
     }
 
-    let files = match load_files_from_dir(input_path) {
-        Ok(files) if files.is_empty() => {
-            eprintln!("Input directory is not an ingot: `{input_path}`");
-            std::process::exit(1)
-        }
-        Ok(files) => files,
-        Err(err) => {
-            eprintln!("Failed to load project files. Error: {err}");
-            std::process::exit(1)
-        }
-    };
-
-    fe_driver::check_ingot(db, DEFAULT_INGOT_NAME, &files)
+    fe_driver::check_ingot_dir(db, Path::new(input_path), locked)
 }
 
 pub fn check(args: CheckArgs) {
@@ -55,11 +46,11 @@ pub fn check(args: CheckArgs) {
     let diags = if Path::new(&input_path).is_file() {
         check_single_file(&mut db, &input_path)
     } else {
-        check_ingot(&mut db, &input_path)
+        check_ingot(&mut db, &input_path, args.locked)
     };
 
-    if !diags.is_empty() {
-        print_diagnostics(&db, &diags);
+    print_diagnostics_in_format(&db, &diags, args.message_format.into());
+    if diags.iter().any(Diagnostic::is_error) {
         std::process::exit(1);
     }
 
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+
+use clap::{ArgEnum, Args};
+use fe_common::diagnostics::print_diagnostics;
+use fe_driver::DocFormat;
+use indexmap::IndexMap;
+
+use super::build::{ioerr_to_string, verify_nonexistent_or_empty, write_output};
+
+const DEFAULT_OUTPUT_DIR_NAME: &str = "doc";
+
+#[derive(Copy, Clone, PartialEq, Eq, ArgEnum, Debug)]
+enum Format {
+    Html,
+    Markdown,
+}
+
+#[derive(Args)]
+#[clap(about = "Generate documentation from `///` doc comments")]
+pub struct DocArgs {
+    input_path: String,
+    #[clap(short, long, default_value = DEFAULT_OUTPUT_DIR_NAME)]
+    output_dir: String,
+    #[clap(arg_enum, long, default_value = "html")]
+    format: Format,
+    #[clap(long)]
+    overwrite: bool,
+    /// Require `fe.lock` to be up to date instead of updating it
+    #[clap(long)]
+    locked: bool,
+}
+
+pub fn doc(args: DocArgs) {
+    let input_path = &args.input_path;
+    let format = match args.format {
+        Format::Html => DocFormat::Html,
+        Format::Markdown => DocFormat::Markdown,
+    };
+
+    let mut db = fe_driver::Db::default();
+    let path = Path::new(input_path);
+    let pages = if path.is_file() {
+        let content = match fs::read_to_string(path) {
+            Err(err) => {
+                eprintln!("Failed to load file: `{input_path}`. Error: {err}");
+                std::process::exit(1)
+            }
+            Ok(content) => content,
+        };
+        fe_driver::document_single_file(&mut db, input_path, &content, format)
+    } else if path.exists() {
+        fe_driver::document_ingot_dir(&mut db, path, args.locked, format)
+    } else {
+        eprintln!("Input path does not exist: `{input_path}`.");
+        std::process::exit(1)
+    };
+
+    let pages = match pages {
+        Ok(pages) => pages,
+        Err(error) => {
+            eprintln!("Unable to document {input_path}.");
+            print_diagnostics(&db, &error.0);
+            std::process::exit(1)
+        }
+    };
+
+    let output_dir = &args.output_dir;
+    match write_pages(&pages, output_dir, args.overwrite) {
+        Ok(_) => eprintln!("Documented {input_path}. Outputs in `{output_dir}`"),
+        Err(err) => {
+            eprintln!("Failed to write output to directory: `{output_dir}`. Error: {err}");
+            std::process::exit(1)
+        }
+    }
+}
+
+fn write_pages(
+    pages: &IndexMap<String, String>,
+    output_dir: &str,
+    overwrite: bool,
+) -> Result<(), String> {
+    let output_dir = Path::new(output_dir);
+    if output_dir.is_file() {
+        return Err(format!(
+            "A file exists at path `{}`, the location of the output directory. Refusing to overwrite.",
+            output_dir.display()
+        ));
+    }
+
+    if !overwrite {
+        verify_nonexistent_or_empty(output_dir)?;
+    }
+
+    fs::create_dir_all(output_dir).map_err(ioerr_to_string)?;
+    for (name, content) in pages {
+        write_output(&output_dir.join(name), content)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use clap::Args;
+use fe_common::diagnostics::print_diagnostics;
+use fe_common::files::SourceFileId;
+use fe_driver::Db;
+
+#[derive(Args)]
+#[clap(about = "Format Fe source files")]
+pub struct FmtArgs {
+    /// A source file, or a directory whose `.fe` files are formatted
+    input_path: String,
+    /// Don't write the files, but list the files that aren't formatted and
+    /// exit with an error if there are any
+    #[clap(long)]
+    check: bool,
+}
+
+fn load_files(input_path: &str) -> Vec<(String, String)> {
+    let path = Path::new(input_path);
+    if path.is_file() {
+        match std::fs::read_to_string(path) {
+            Ok(content) => vec![(input_path.to_string(), content)],
+            Err(err) => {
+                eprintln!("Failed to load file: `{input_path}`. Error: {err}");
+                std::process::exit(1)
+            }
+        }
+    } else if path.is_dir() {
+        match fe_driver::load_files_from_dir(path) {
+            Ok(files) => files,
+            Err(err) => {
+                eprintln!("Failed to load files from `{input_path}`. Error: {err}");
+                std::process::exit(1)
+            }
+        }
+    } else {
+        eprintln!("Input path does not exist: `{input_path}`.");
+        std::process::exit(1)
+    }
+}
+
+pub fn fmt(args: FmtArgs) {
+    let mut db = Db::default();
+    let mut has_errors = false;
+    let mut unformatted = vec![];
+
+    for (path, content) in load_files(&args.input_path) {
+        let file_id = SourceFileId::new_local(&mut db, &path, content.as_str().into());
+        let formatted = match fe_parser::format_file(file_id, &content) {
+            Ok(formatted) => formatted,
+            Err(diags) => {
+                print_diagnostics(&db, &diags);
+                has_errors = true;
+                continue;
+            }
+        };
+        if formatted == content {
+            continue;
+        }
+
+        if args.check {
+            println!("{path}");
+        } else if let Err(err) = std::fs::write(&path, formatted) {
+            eprintln!("Failed to write file: `{path}`. Error: {err}");
+            has_errors = true;
+        }
+        unformatted.push(path);
+    }
+
+    if has_errors || (args.check && !unformatted.is_empty()) {
+        std::process::exit(1);
+    }
+}
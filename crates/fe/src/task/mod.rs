@@ -1,22 +1,49 @@
+mod abi_import;
 mod build;
 mod check;
+mod doc;
+mod fmt;
 mod new;
 #[cfg(feature = "solc-backend")]
 mod test;
-mod utils;
 
+pub use abi_import::{abi_import, AbiImportArgs};
 pub use build::{build, BuildArgs};
 pub use check::{check, CheckArgs};
-use clap::Subcommand;
+use clap::{ArgEnum, Subcommand};
+pub use doc::{doc, DocArgs};
+use fe_common::diagnostics::DiagnosticFormat;
+pub use fmt::{fmt, FmtArgs};
 pub use new::{create_new_project, NewProjectArgs};
 #[cfg(feature = "solc-backend")]
 pub use test::{test, TestArgs};
 
 #[derive(Subcommand)]
 pub enum Commands {
+    AbiImport(AbiImportArgs),
     Build(BuildArgs),
     Check(CheckArgs),
+    Doc(DocArgs),
+    Fmt(FmtArgs),
     New(NewProjectArgs),
     #[cfg(feature = "solc-backend")]
     Test(TestArgs),
 }
+
+/// The format of the diagnostics printed by `fe check` and `fe build`.
+#[derive(Copy, Clone, PartialEq, Eq, ArgEnum, Debug)]
+pub enum MessageFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+impl From<MessageFormat> for DiagnosticFormat {
+    fn from(format: MessageFormat) -> Self {
+        match format {
+            MessageFormat::Human => DiagnosticFormat::Human,
+            MessageFormat::Json => DiagnosticFormat::Json,
+            MessageFormat::Sarif => DiagnosticFormat::Sarif,
+        }
+    }
+}
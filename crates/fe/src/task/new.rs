@@ -1,4 +1,5 @@
 use clap::Args;
+use fe_driver::{Manifest, MANIFEST_FILE_NAME};
 use include_dir::{include_dir, Dir};
 use std::{fs, path::Path};
 
@@ -17,6 +18,14 @@ fn create_project(p: &Path) {
     }
 }
 
+fn create_manifest(p: &Path) {
+    let name = p
+        .file_name()
+        .expect("project path has no file name")
+        .to_string_lossy();
+    fs::write(p.join(MANIFEST_FILE_NAME), Manifest::template(&name)).unwrap();
+}
+
 pub fn create_new_project(args: NewProjectArgs) {
     let project_path = Path::new(&args.name);
 
@@ -31,7 +40,10 @@ pub fn create_new_project(args: NewProjectArgs) {
     let source_path = project_path.join("src");
 
     match fs::create_dir_all(source_path.as_path()) {
-        Ok(_) => create_project(source_path.as_path()),
+        Ok(_) => {
+            create_project(source_path.as_path());
+            create_manifest(project_path);
+        }
         Err(err) => {
             eprintln!("{err}");
             std::process::exit(1);
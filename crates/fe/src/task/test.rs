@@ -1,55 +1,131 @@
 #![cfg(feature = "solc-backend")]
+use std::fmt::Display;
 use std::path::Path;
 
-use clap::Args;
+use clap::{ArgEnum, Args};
 use colored::Colorize;
 use fe_common::diagnostics::print_diagnostics;
 use fe_driver::CompiledTest;
-use fe_test_runner::TestSink;
-
-use crate::task::utils::load_files_from_dir;
+use fe_test_runner::{TestFilter, TestSink};
+use rayon::prelude::*;
+
+/// The format of the report printed by `fe test`.
+#[derive(Copy, Clone, PartialEq, Eq, ArgEnum, Debug)]
+enum TestFormat {
+    Pretty,
+    Json,
+    Junit,
+}
 
 #[derive(Args)]
 #[clap(about = "Execute tests in the current project")]
 pub struct TestArgs {
     input_path: String,
+    /// Only run the tests whose names, like `module::test_name`, contain this
+    /// filter. It can also be a pattern with `*` and `?` wildcards
+    filter: Option<String>,
+    /// Only run the test whose name is exactly the filter
+    #[clap(long, requires = "filter")]
+    exact: bool,
+    /// List the tests instead of running them
+    #[clap(long)]
+    list: bool,
+    #[clap(arg_enum, long, default_value = "pretty")]
+    format: TestFormat,
+    /// The number of tests to compile and execute in parallel. Defaults to the
+    /// number of CPUs
+    #[clap(short, long)]
+    jobs: Option<usize>,
     #[clap(long, takes_value(true))]
     optimize: Option<bool>,
+    /// Require `fe.lock` to be up to date instead of updating it
+    #[clap(long)]
+    locked: bool,
 }
 
 pub fn test(args: TestArgs) {
     let path = &args.input_path;
+    let filter = TestFilter::new(args.filter.clone(), args.exact);
+
+    if args.list {
+        list_tests(&args, &filter);
+        return;
+    }
+
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .expect("failed to start the test threads");
+    }
 
     let test_sink = if Path::new(path).is_file() {
-        test_single_file(&args)
+        test_single_file(&args, &filter)
     } else {
-        test_ingot(&args)
+        test_ingot(&args, &filter)
     };
 
-    println!("{test_sink}");
+    match args.format {
+        TestFormat::Pretty => println!("{test_sink}"),
+        TestFormat::Json => println!("{}", test_sink.to_json()),
+        TestFormat::Junit => print!("{}", test_sink.to_junit()),
+    }
     if test_sink.failure_count() != 0 {
         std::process::exit(1)
     }
 }
 
-fn test_single_file(args: &TestArgs) -> TestSink {
+fn list_tests(args: &TestArgs, filter: &TestFilter) {
     let input_path = &args.input_path;
 
     let mut db = fe_driver::Db::default();
-    let content = match std::fs::read_to_string(input_path) {
+    let names = if Path::new(input_path).is_file() {
+        let content = read_input_file(input_path);
+        fe_driver::list_single_file_tests(&mut db, input_path, &content, filter)
+    } else {
+        check_input_dir(input_path);
+        fe_driver::list_ingot_dir_tests(&mut db, Path::new(input_path), args.locked, filter)
+    };
+
+    match names {
+        Ok(names) => {
+            for name in names {
+                println!("{name}")
+            }
+        }
+        Err(error) => {
+            eprintln!("Unable to compile {input_path}.");
+            print_diagnostics(&db, &error.0);
+            std::process::exit(1)
+        }
+    }
+}
+
+fn read_input_file(input_path: &str) -> String {
+    match std::fs::read_to_string(input_path) {
         Err(err) => {
             eprintln!("Failed to load file: `{input_path}`. Error: {err}");
             std::process::exit(1)
         }
         Ok(content) => content,
-    };
+    }
+}
 
-    match fe_driver::compile_single_file_tests(&mut db, input_path, &content, true) {
-        Ok((name, tests)) => {
-            let mut sink = TestSink::default();
-            execute_tests(&name, &tests, &mut sink);
-            sink
-        }
+fn check_input_dir(input_path: &str) {
+    if !Path::new(input_path).exists() {
+        eprintln!("Input directory does not exist: `{input_path}`.");
+        std::process::exit(1)
+    }
+}
+
+fn test_single_file(args: &TestArgs, filter: &TestFilter) -> TestSink {
+    let input_path = &args.input_path;
+
+    let mut db = fe_driver::Db::default();
+    let content = read_input_file(input_path);
+
+    match fe_driver::compile_single_file_tests(&mut db, input_path, &content, true, filter) {
+        Ok((name, tests)) => execute_tests(&[(name, tests)], args.format),
         Err(error) => {
             eprintln!("Unable to compile {input_path}.");
             print_diagnostics(&db, &error.0);
@@ -58,57 +134,74 @@ fn test_single_file(args: &TestArgs) -> TestSink {
     }
 }
 
-pub fn execute_tests(module_name: &str, tests: &[CompiledTest], sink: &mut TestSink) {
+/// Executes the tests of each module on the rayon thread pool. Every test runs
+/// against its own EVM state, so their results only need to be put back in
+/// order.
+fn execute_tests(
+    test_batches: &[(impl Display + Sync, Vec<CompiledTest>)],
+    format: TestFormat,
+) -> TestSink {
+    let batch_sinks: Vec<Vec<TestSink>> = test_batches
+        .par_iter()
+        .map(|(_, tests)| {
+            tests
+                .par_iter()
+                .map(|test| {
+                    let mut sink = TestSink::default();
+                    test.execute(&mut sink);
+                    sink
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut sink = TestSink::default();
+    for ((module_name, tests), test_sinks) in test_batches.iter().zip(batch_sinks) {
+        // The JSON and JUnit reports are meant to be parsed, so only the pretty
+        // format reports progress.
+        if format == TestFormat::Pretty && !tests.is_empty() {
+            print_module_results(module_name, tests, &test_sinks);
+        }
+        for test_sink in test_sinks {
+            sink.merge(test_sink);
+        }
+    }
+    sink
+}
+
+fn print_module_results(module_name: &impl Display, tests: &[CompiledTest], sinks: &[TestSink]) {
     if tests.len() == 1 {
         println!("executing 1 test in {module_name}:");
     } else {
         println!("executing {} tests in {}:", tests.len(), module_name);
     }
 
-    for test in tests {
-        print!("  {} ...", test.name);
-        let test_passed = test.execute(sink);
-
-        if test_passed {
-            println!(" {}", "passed".green())
+    for (test, sink) in tests.iter().zip(sinks) {
+        if sink.failure_count() == 0 {
+            println!("  {} ... {}", test.name, "passed".green())
         } else {
-            println!(" {}", "failed".red())
+            println!("  {} ... {}", test.name, "failed".red())
         }
     }
     println!();
 }
 
-fn test_ingot(args: &TestArgs) -> TestSink {
+fn test_ingot(args: &TestArgs, filter: &TestFilter) -> TestSink {
     let input_path = &args.input_path;
     let optimize = args.optimize.unwrap_or(true);
 
-    if !Path::new(input_path).exists() {
-        eprintln!("Input directory does not exist: `{input_path}`.");
-        std::process::exit(1)
-    }
-
-    let content = match load_files_from_dir(input_path) {
-        Ok(files) if files.is_empty() => {
-            eprintln!("Input directory is not an ingot: `{input_path}`");
-            std::process::exit(1)
-        }
-        Ok(files) => files,
-        Err(err) => {
-            eprintln!("Failed to load project files. Error: {err}");
-            std::process::exit(1)
-        }
-    };
+    check_input_dir(input_path);
 
     let mut db = fe_driver::Db::default();
 
-    match fe_driver::compile_ingot_tests(&mut db, input_path, &content, optimize) {
-        Ok(test_batches) => {
-            let mut sink = TestSink::default();
-            for (module_name, tests) in test_batches {
-                execute_tests(&module_name, &tests, &mut sink);
-            }
-            sink
-        }
+    match fe_driver::compile_ingot_dir_tests(
+        &mut db,
+        Path::new(input_path),
+        args.locked,
+        optimize,
+        filter,
+    ) {
+        Ok(test_batches) => execute_tests(&test_batches, args.format),
         Err(error) => {
             eprintln!("Unable to compile {input_path}.");
             print_diagnostics(&db, &error.0);
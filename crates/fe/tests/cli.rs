@@ -0,0 +1,61 @@
+#![cfg(feature = "solc-backend")]
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn fe(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_fe"))
+        .args(args)
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/fixtures/files"))
+        .output()
+        .expect("failed to run fe")
+}
+
+fn listed_tests(args: &[&str]) -> Vec<String> {
+    let output = fe(args);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .expect("output isn't valid UTF-8")
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn test_list() {
+    assert_eq!(
+        listed_tests(&["test", "math.fe", "--list"]),
+        ["math::test_sqrt", "math::test_min"]
+    );
+}
+
+#[test]
+fn test_list_filtered() {
+    assert_eq!(
+        listed_tests(&["test", "math.fe", "sqrt", "--list"]),
+        ["math::test_sqrt"]
+    );
+    assert_eq!(
+        listed_tests(&["test", "math.fe", "math::test_m?n", "--list"]),
+        ["math::test_min"]
+    );
+    assert!(listed_tests(&["test", "math.fe", "test_min", "--exact", "--list"]).is_empty());
+    assert_eq!(
+        listed_tests(&["test", "math.fe", "math::test_min", "--exact", "--list"]),
+        ["math::test_min"]
+    );
+}
+
+#[test]
+fn test_list_compile_error() {
+    let output = fe(&[
+        "test",
+        "../../../test-files/fixtures/compile_errors/_test_fn_call.fe",
+        "--list",
+    ]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
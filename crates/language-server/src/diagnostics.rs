@@ -0,0 +1,81 @@
+use crate::util::{file_url, span_to_location, span_to_range};
+use fe_common::db::SourceDb;
+use fe_common::diagnostics::{Diagnostic, LabelStyle, Severity};
+use fe_common::SourceFileId;
+use indexmap::IndexMap;
+use lsp_types::{DiagnosticRelatedInformation, DiagnosticSeverity, Position, Range, Url};
+
+/// Converts the given diagnostics to LSP diagnostics, grouped by the file of
+/// their primary label. Every file in `files` gets an entry, so that stale
+/// diagnostics are cleared.
+///
+/// Diagnostics without labels (e.g. a missing `main.fe`) are reported at the
+/// beginning of the first file.
+pub fn lsp_diagnostics(
+    db: &dyn SourceDb,
+    files: &[SourceFileId],
+    diagnostics: &[Diagnostic],
+) -> IndexMap<Url, Vec<lsp_types::Diagnostic>> {
+    let mut by_file = files
+        .iter()
+        .filter_map(|file| file_url(db, *file))
+        .map(|url| (url, vec![]))
+        .collect::<IndexMap<_, _>>();
+
+    for diag in diagnostics {
+        let primary = diag
+            .labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary && !label.span.file_id.is_dummy());
+        let (url, range) = match primary {
+            Some(label) => match file_url(db, label.span.file_id) {
+                Some(url) => (url, span_to_range(db, label.span)),
+                None => continue,
+            },
+            None => match by_file.keys().next() {
+                Some(url) => (
+                    url.clone(),
+                    Range::new(Position::new(0, 0), Position::new(0, 0)),
+                ),
+                None => continue,
+            },
+        };
+
+        let message = std::iter::once(diag.message.as_str())
+            .chain(primary.map(|label| label.message.as_str()))
+            .chain(diag.notes.iter().map(String::as_str))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let related_information = diag
+            .labels
+            .iter()
+            .filter(|label| label.style == LabelStyle::Secondary)
+            .filter_map(|label| {
+                Some(DiagnosticRelatedInformation {
+                    location: span_to_location(db, label.span)?,
+                    message: label.message.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        by_file.entry(url).or_default().push(lsp_types::Diagnostic {
+            range,
+            severity: Some(lsp_severity(diag.severity)),
+            source: Some("fe".into()),
+            message,
+            related_information: (!related_information.is_empty()).then_some(related_information),
+            ..lsp_types::Diagnostic::default()
+        });
+    }
+    by_file
+}
+
+fn lsp_severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Bug | Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Note => DiagnosticSeverity::INFORMATION,
+        Severity::Help => DiagnosticSeverity::HINT,
+    }
+}
@@ -0,0 +1,126 @@
+use crate::util::{file_url, position_to_offset, span_to_location, url_to_path};
+use crate::workspace::Workspace;
+use fe_analyzer::context::{CallType, NamedThing};
+use fe_analyzer::namespace::items::{FunctionSigId, Item, ModuleId, ModuleSource};
+use fe_analyzer::AnalyzerDb;
+use fe_common::Span;
+use fe_parser::node::Node;
+use lsp_types::{GotoDefinitionParams, GotoDefinitionResponse, Location, Position, Range};
+use smol_str::SmolStr;
+
+pub fn goto_definition(
+    workspace: &mut Workspace,
+    params: GotoDefinitionParams,
+) -> Option<GotoDefinitionResponse> {
+    let position = params.text_document_position_params;
+    let path = url_to_path(&position.text_document.uri)?;
+    let module = workspace.module(&path)?;
+    let file = workspace.file(&path)?;
+    let text = file.content(&workspace.db);
+    let offset = position_to_offset(&text, position.position);
+    let segments = path_at(&text, offset)?;
+
+    // Calls are resolved by the analyzer, which also takes care of methods
+    // and associated functions.
+    if let Some(function) = workspace.function_at(module, offset) {
+        let location = workspace.with_module(module, |db| {
+            let body = function.body(db);
+            let (_, call) = body
+                .calls
+                .iter()
+                .filter_map(|(id, call)| Some((*body.spans.get(id)?, call)))
+                .filter(|(span, _)| span.start <= offset && offset <= span.end)
+                .min_by_key(|(span, _)| span.end - span.start)?;
+            let sig = called_function(db, call)?;
+            span_to_location(db, sig.name_span(db))
+        });
+        if location.is_some() {
+            return location.map(GotoDefinitionResponse::Scalar);
+        }
+    }
+
+    workspace
+        .with_module(module, |db| resolve_path(db, module, &segments))
+        .map(GotoDefinitionResponse::Scalar)
+}
+
+/// The function called by a call expression, if it's defined in Fe source.
+pub fn called_function(db: &dyn AnalyzerDb, call: &CallType) -> Option<FunctionSigId> {
+    match call {
+        CallType::AssociatedFunction { function, .. }
+        | CallType::ValueMethod {
+            method: function, ..
+        }
+        | CallType::External { function, .. }
+        | CallType::Pure(function) => Some(function.sig(db)),
+        CallType::TraitValueMethod { method, .. } => Some(*method),
+        _ => None,
+    }
+}
+
+/// Resolves a path like `foo::Bar` in the scope of the module.
+fn resolve_path(db: &dyn AnalyzerDb, module: ModuleId, segments: &[SmolStr]) -> Option<Location> {
+    let (first, rest) = segments.split_first()?;
+    let item = *module.items(db).get(first)?;
+    let rest = rest
+        .iter()
+        .map(|segment| Node::new(segment.clone(), Span::dummy()))
+        .collect::<Vec<_>>();
+    let named_thing = item.resolve_path_segments(db, &rest).value?;
+
+    match named_thing {
+        NamedThing::Item(Item::Module(module)) => match module.data(db).source {
+            ModuleSource::File(file) => Some(Location::new(
+                file_url(db.upcast(), file)?,
+                Range::new(Position::new(0, 0), Position::new(0, 0)),
+            )),
+            ModuleSource::Dir(_) => None,
+        },
+        named_thing => span_to_location(db.upcast(), named_thing.name_span(db)?),
+    }
+}
+
+/// Returns the `::` separated path under the cursor, up to and including the
+/// segment the cursor is on.
+fn path_at(text: &str, offset: usize) -> Option<Vec<SmolStr>> {
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_';
+    let is_path = |ch: char| is_ident(ch) || ch == ':';
+
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| is_path(*ch))
+        .last()
+        .map_or(offset, |(idx, _)| idx);
+    let end = text[offset..]
+        .char_indices()
+        .find(|(_, ch)| !is_ident(*ch))
+        .map_or(text.len(), |(idx, _)| offset + idx);
+
+    let segments = text[start..end]
+        .trim_start_matches(':')
+        .split("::")
+        .map(SmolStr::new)
+        .collect::<Vec<_>>();
+    if segments
+        .iter()
+        .any(|segment| segment.is_empty() || !segment.chars().all(is_ident))
+    {
+        return None;
+    }
+    Some(segments)
+}
+
+#[test]
+fn test_path_at() {
+    let text = "let x: u256 = foo::Bar::baz(y)";
+    let path = |offset| path_at(text, offset);
+    assert_eq!(path(5), Some(vec!["x".into()]));
+    assert_eq!(path(15), Some(vec!["foo".into()]));
+    assert_eq!(path(20), Some(vec!["foo".into(), "Bar".into()]));
+    assert_eq!(
+        path(26),
+        Some(vec!["foo".into(), "Bar".into(), "baz".into()])
+    );
+    assert_eq!(path(6), None);
+}
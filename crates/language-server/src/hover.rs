@@ -0,0 +1,63 @@
+use crate::goto::called_function;
+use crate::util::{position_to_offset, span_to_range, url_to_path};
+use crate::workspace::Workspace;
+use fe_analyzer::display::Displayable;
+use fe_analyzer::namespace::items::FunctionSigId;
+use fe_analyzer::AnalyzerDb;
+use fe_common::Span;
+use lsp_types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind};
+
+/// Shows the type of the innermost expression under the cursor. For calls,
+/// the signature of the called function is shown instead.
+pub fn hover(workspace: &mut Workspace, params: HoverParams) -> Option<Hover> {
+    let position = params.text_document_position_params;
+    let path = url_to_path(&position.text_document.uri)?;
+    let module = workspace.module(&path)?;
+    let file = workspace.file(&path)?;
+    let offset = position_to_offset(&file.content(&workspace.db), position.position);
+    let function = workspace.function_at(module, offset)?;
+
+    workspace.with_module(module, |db| {
+        let body = function.body(db);
+        let innermost = |ids: Vec<_>| {
+            ids.into_iter()
+                .filter_map(|id| Some((id, *body.spans.get(&id)?)))
+                .filter(|(_, span): &(_, Span)| span.start <= offset && offset <= span.end)
+                .min_by_key(|(_, span)| span.end - span.start)
+        };
+
+        let called = innermost(body.calls.keys().copied().collect()).and_then(|(id, span)| {
+            Some((
+                signature_text(db, called_function(db, &body.calls[&id])?),
+                span,
+            ))
+        });
+        let (text, span) = match called {
+            Some(called) => called,
+            None => {
+                let (id, span) = innermost(body.expressions.keys().copied().collect())?;
+                (body.expressions[&id].typ.display(db).to_string(), span)
+            }
+        };
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```fe\n{text}\n```"),
+            }),
+            range: Some(span_to_range(db, span)),
+        })
+    })
+}
+
+/// The signature of the function as written in the source.
+fn signature_text(db: &dyn AnalyzerDb, sig: FunctionSigId) -> String {
+    let Span {
+        file_id,
+        start,
+        end,
+    } = sig.data(db).ast.span;
+    file_id.content(db.upcast())[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,15 @@
+mod diagnostics;
+mod goto;
+mod hover;
+mod server;
+mod util;
+mod workspace;
+
+use lsp_server::Connection;
+
+fn main() -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+    server::run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
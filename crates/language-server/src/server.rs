@@ -0,0 +1,369 @@
+use crate::diagnostics::lsp_diagnostics;
+use crate::goto::goto_definition;
+use crate::hover::hover;
+use crate::util::url_to_path;
+use crate::workspace::Workspace;
+use lsp_server::{Connection, ExtractError, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as NotificationTrait, PublishDiagnostics,
+};
+use lsp_types::request::{GotoDefinition, HoverRequest};
+use lsp_types::{
+    HoverProviderCapability, InitializeParams, OneOf, PublishDiagnosticsParams, SaveOptions,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions,
+};
+use serde::de::DeserializeOwned;
+use std::error::Error;
+
+type ServerResult<T> = Result<T, Box<dyn Error + Sync + Send>>;
+
+/// Runs the server until the client asks it to shut down. The connection is
+/// dropped afterwards, which lets its IO threads finish.
+pub fn run(connection: Connection) -> ServerResult<()> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                    include_text: Some(false),
+                })),
+                ..TextDocumentSyncOptions::default()
+            },
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    };
+    let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    #[allow(deprecated)]
+    let root = params
+        .workspace_folders
+        .and_then(|folders| folders.into_iter().next())
+        .map(|folder| folder.uri)
+        .or(params.root_uri)
+        .and_then(|uri| url_to_path(&uri));
+    let mut workspace = Workspace::new(root);
+    publish_diagnostics(&connection, &mut workspace)?;
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                handle_request(&connection, &mut workspace, request)?;
+            }
+            Message::Notification(notification) => {
+                handle_notification(&connection, &mut workspace, notification)?;
+            }
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn handle_request(
+    connection: &Connection,
+    workspace: &mut Workspace,
+    request: Request,
+) -> ServerResult<()> {
+    let response = match request.method.as_str() {
+        <HoverRequest as lsp_types::request::Request>::METHOD => {
+            let (id, params) = extract_request::<HoverRequest>(request)?;
+            Response::new_ok(id, hover(workspace, params))
+        }
+        <GotoDefinition as lsp_types::request::Request>::METHOD => {
+            let (id, params) = extract_request::<GotoDefinition>(request)?;
+            Response::new_ok(id, goto_definition(workspace, params))
+        }
+        _ => Response::new_err(
+            request.id,
+            lsp_server::ErrorCode::MethodNotFound as i32,
+            format!("unsupported request: {}", request.method),
+        ),
+    };
+    connection.sender.send(Message::Response(response))?;
+    Ok(())
+}
+
+fn handle_notification(
+    connection: &Connection,
+    workspace: &mut Workspace,
+    notification: Notification,
+) -> ServerResult<()> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params = extract_notification::<DidOpenTextDocument>(notification)?;
+            if let Some(path) = url_to_path(&params.text_document.uri) {
+                workspace.add_ingot_file(&path);
+                workspace.set_file_content(&path, &params.text_document.text);
+            }
+        }
+        DidChangeTextDocument::METHOD => {
+            let params = extract_notification::<DidChangeTextDocument>(notification)?;
+            // The server only asks for full document syncs.
+            let change = params.content_changes.into_iter().last();
+            match (url_to_path(&params.text_document.uri), change) {
+                (Some(path), Some(change)) => workspace.set_file_content(&path, &change.text),
+                _ => return Ok(()),
+            }
+        }
+        DidSaveTextDocument::METHOD => {
+            let params = extract_notification::<DidSaveTextDocument>(notification)?;
+            let uri = &params.text_document.uri;
+            // A manifest change can add or remove dependencies.
+            if uri.path().ends_with(fe_driver::MANIFEST_FILE_NAME) {
+                workspace.load_ingot();
+            } else if !url_to_path(uri).is_some_and(|path| workspace.add_ingot_file(&path)) {
+                return Ok(());
+            }
+        }
+        _ => return Ok(()),
+    }
+    publish_diagnostics(connection, workspace)
+}
+
+fn publish_diagnostics(connection: &Connection, workspace: &mut Workspace) -> ServerResult<()> {
+    let diagnostics = workspace.diagnostics();
+    let files = workspace.files();
+    for (uri, diagnostics) in lsp_diagnostics(&workspace.db, &files, &diagnostics) {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                PublishDiagnostics::METHOD.to_string(),
+                params,
+            )))?;
+    }
+    Ok(())
+}
+
+fn extract_request<R>(request: Request) -> ServerResult<(RequestId, R::Params)>
+where
+    R: lsp_types::request::Request,
+    R::Params: DeserializeOwned,
+{
+    request.extract(R::METHOD).map_err(extract_error)
+}
+
+fn extract_notification<N>(notification: Notification) -> ServerResult<N::Params>
+where
+    N: NotificationTrait,
+    N::Params: DeserializeOwned,
+{
+    notification.extract(N::METHOD).map_err(extract_error)
+}
+
+fn extract_error<T: std::fmt::Debug>(err: ExtractError<T>) -> Box<dyn Error + Sync + Send> {
+    format!("invalid message: {err:?}").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::util::offset_to_position;
+    use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+    use lsp_types::notification::{Exit, Initialized, Notification as _, PublishDiagnostics};
+    use lsp_types::request::{GotoDefinition, HoverRequest, Initialize, Shutdown};
+    use lsp_types::{
+        DiagnosticSeverity, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+        HoverParams, InitializeParams, Position, PublishDiagnosticsParams, TextDocumentIdentifier,
+        TextDocumentPositionParams, Url,
+    };
+    use std::path::{Path, PathBuf};
+
+    fn fixture_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../test-files/fixtures/ingots/language_server_ingot")
+            .canonicalize()
+            .unwrap()
+    }
+
+    fn file_url(name: &str) -> Url {
+        Url::from_file_path(fixture_dir().join("src").join(name)).unwrap()
+    }
+
+    /// The position of the first occurrence of `needle` in the file, plus
+    /// `offset` bytes.
+    fn position_of(name: &str, needle: &str, offset: usize) -> Position {
+        let text = std::fs::read_to_string(fixture_dir().join("src").join(name)).unwrap();
+        offset_to_position(&text, text.find(needle).unwrap() + offset)
+    }
+
+    fn position_params(name: &str, position: Position) -> TextDocumentPositionParams {
+        TextDocumentPositionParams::new(TextDocumentIdentifier::new(file_url(name)), position)
+    }
+
+    /// A client connected to a server that runs on another thread.
+    struct Client {
+        connection: Connection,
+        server: Option<std::thread::JoinHandle<()>>,
+        next_id: i32,
+    }
+
+    impl Client {
+        fn start(root: &Path) -> Self {
+            let (connection, server_connection) = Connection::memory();
+            let server = std::thread::spawn(move || run(server_connection).unwrap());
+            let mut client = Client {
+                connection,
+                server: Some(server),
+                next_id: 0,
+            };
+
+            #[allow(deprecated)]
+            let params = InitializeParams {
+                root_uri: Some(Url::from_file_path(root).unwrap()),
+                ..InitializeParams::default()
+            };
+            client.request::<Initialize>(params);
+            client.notify::<Initialized>(lsp_types::InitializedParams {});
+            client
+        }
+
+        fn request<R: lsp_types::request::Request>(&mut self, params: R::Params) -> R::Result {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+            let request = Request::new(id.clone(), R::METHOD.to_string(), params);
+            self.connection
+                .sender
+                .send(Message::Request(request))
+                .unwrap();
+            loop {
+                match self.connection.receiver.recv().unwrap() {
+                    Message::Response(Response {
+                        id: response_id,
+                        result: Some(result),
+                        ..
+                    }) if response_id == id => return serde_json::from_value(result).unwrap(),
+                    Message::Response(response) if response.id == id => {
+                        panic!("request failed: {:?}", response.error)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        fn notify<N: lsp_types::notification::Notification>(&self, params: N::Params) {
+            let notification = Notification::new(N::METHOD.to_string(), params);
+            self.connection
+                .sender
+                .send(Message::Notification(notification))
+                .unwrap();
+        }
+
+        /// Receives diagnostics until every file in `files` has been published.
+        fn published_diagnostics(&self, files: &[Url]) -> Vec<PublishDiagnosticsParams> {
+            let mut published: Vec<PublishDiagnosticsParams> = vec![];
+            while !files
+                .iter()
+                .all(|file| published.iter().any(|params| params.uri == *file))
+            {
+                if let Message::Notification(notification) =
+                    self.connection.receiver.recv().unwrap()
+                {
+                    if notification.method == PublishDiagnostics::METHOD {
+                        published.push(serde_json::from_value(notification.params).unwrap());
+                    }
+                }
+            }
+            published
+        }
+
+        fn shutdown(mut self) {
+            self.request::<Shutdown>(());
+            self.notify::<Exit>(());
+            self.server.take().unwrap().join().unwrap();
+        }
+    }
+
+    fn hover_text(hover: Option<Hover>) -> String {
+        match hover.expect("no hover").contents {
+            HoverContents::Markup(content) => content.value,
+            contents => panic!("unexpected hover contents: {contents:?}"),
+        }
+    }
+
+    #[test]
+    fn test_publish_diagnostics() {
+        let client = Client::start(&fixture_dir());
+        let main = file_url("main.fe");
+        let token = file_url("token.fe");
+        let published = client.published_diagnostics(&[main.clone(), token.clone()]);
+
+        let main_diags = &published
+            .iter()
+            .find(|p| p.uri == main)
+            .unwrap()
+            .diagnostics;
+        assert_eq!(main_diags.len(), 1, "{main_diags:?}");
+        assert_eq!(main_diags[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(main_diags[0].source.as_deref(), Some("fe"));
+        assert!(main_diags[0]
+            .message
+            .starts_with("expected function to return `u256` but was `bool`"));
+        assert_eq!(
+            main_diags[0].range.start,
+            position_of("main.fe", "return true", 0)
+        );
+        assert!(published
+            .iter()
+            .find(|p| p.uri == token)
+            .unwrap()
+            .diagnostics
+            .is_empty());
+        client.shutdown();
+    }
+
+    #[test]
+    fn test_hover() {
+        let mut client = Client::start(&fixture_dir());
+
+        let call = position_of("main.fe", "total()", 1);
+        let hover = client.request::<HoverRequest>(HoverParams {
+            text_document_position_params: position_params("main.fe", call),
+            work_done_progress_params: Default::default(),
+        });
+        assert_eq!(hover_text(hover), "```fe\npub fn total(self) -> u256\n```");
+
+        let literal = position_of("main.fe", "100", 1);
+        let hover = client.request::<HoverRequest>(HoverParams {
+            text_document_position_params: position_params("main.fe", literal),
+            work_done_progress_params: Default::default(),
+        });
+        assert_eq!(hover_text(hover), "```fe\nu256\n```");
+        client.shutdown();
+    }
+
+    #[test]
+    fn test_goto_definition() {
+        let mut client = Client::start(&fixture_dir());
+        let goto = |client: &mut Client, position| {
+            let response = client.request::<GotoDefinition>(GotoDefinitionParams {
+                text_document_position_params: position_params("main.fe", position),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            });
+            match response {
+                Some(GotoDefinitionResponse::Scalar(location)) => location,
+                response => panic!("unexpected response: {response:?}"),
+            }
+        };
+
+        // A method call.
+        let location = goto(&mut client, position_of("main.fe", "total()", 1));
+        assert_eq!(location.uri, file_url("token.fe"));
+        assert_eq!(location.range.start, position_of("token.fe", "total", 0));
+
+        // A type in a `use` path.
+        let location = goto(&mut client, position_of("main.fe", "use token::Token", 12));
+        assert_eq!(location.uri, file_url("token.fe"));
+        assert_eq!(location.range.start, position_of("token.fe", "Token", 0));
+        client.shutdown();
+    }
+}
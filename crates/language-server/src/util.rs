@@ -0,0 +1,94 @@
+use fe_common::db::SourceDb;
+use fe_common::{SourceFileId, Span};
+use lsp_types::{Location, Position, Range, Url};
+use std::path::PathBuf;
+
+/// Converts a byte offset into an LSP position. LSP columns are counted in
+/// UTF-16 code units.
+pub fn offset_to_position(text: &str, offset: usize) -> Position {
+    let offset = floor_char_boundary(text, offset);
+    let line_start = text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+    let line = text[..line_start].matches('\n').count();
+    let character = text[line_start..offset].encode_utf16().count();
+    Position::new(line as u32, character as u32)
+}
+
+/// Converts an LSP position into a byte offset. Positions past the end of a
+/// line or of the text are clamped.
+pub fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(idx) => line_start += idx + 1,
+            None => return text.len(),
+        }
+    }
+
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |idx| line_start + idx);
+    let mut utf16_count = 0;
+    for (idx, ch) in text[line_start..line_end].char_indices() {
+        if utf16_count >= position.character as usize {
+            return line_start + idx;
+        }
+        utf16_count += ch.len_utf16();
+    }
+    line_end
+}
+
+pub fn span_to_range(db: &dyn SourceDb, span: Span) -> Range {
+    let text = span.file_id.content(db);
+    Range::new(
+        offset_to_position(&text, span.start),
+        offset_to_position(&text, span.end),
+    )
+}
+
+/// Returns the location of the span, if it points into a file on disk.
+pub fn span_to_location(db: &dyn SourceDb, span: Span) -> Option<Location> {
+    if span.file_id.is_dummy() {
+        return None;
+    }
+    let uri = file_url(db, span.file_id)?;
+    Some(Location::new(uri, span_to_range(db, span)))
+}
+
+pub fn file_url(db: &dyn SourceDb, file: SourceFileId) -> Option<Url> {
+    let path = PathBuf::from(file.path(db).as_str());
+    let path = path.canonicalize().unwrap_or(path);
+    Url::from_file_path(path).ok()
+}
+
+/// Returns the canonical file system path of a `file://` url.
+pub fn url_to_path(url: &Url) -> Option<PathBuf> {
+    let path = url.to_file_path().ok()?;
+    Some(path.canonicalize().unwrap_or(path))
+}
+
+fn floor_char_boundary(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+#[test]
+fn test_position_conversion() {
+    let text = "contract Foo {\n    x: ü8 // 𝔣\n}\n";
+    for (offset, line, character) in [
+        (0, 0, 0),
+        (15, 1, 0),
+        (22, 1, 7),
+        (24, 1, 8),
+        (29, 1, 13),
+        (33, 1, 15),
+    ] {
+        let position = Position::new(line, character);
+        assert_eq!(offset_to_position(text, offset), position);
+        assert_eq!(position_to_offset(text, position), offset);
+    }
+    assert_eq!(position_to_offset(text, Position::new(1, 100)), 33);
+    assert_eq!(position_to_offset(text, Position::new(10, 0)), text.len());
+}
@@ -0,0 +1,303 @@
+use fe_analyzer::namespace::items::{
+    FunctionId, IngotId, IngotMode, Item, ModuleId, ModuleSource, TypeDef,
+};
+use fe_analyzer::AnalyzerDb;
+use fe_common::db::SourceDb;
+use fe_common::diagnostics::Diagnostic;
+use fe_common::files::FileKind;
+use fe_common::SourceFileId;
+use fe_driver::Db;
+use indexmap::{IndexMap, IndexSet};
+use std::path::{Path, PathBuf};
+
+/// The state of the files the server knows about.
+///
+/// The database lives as long as the server, so that the analysis of files
+/// that didn't change is reused between requests.
+pub struct Workspace {
+    pub db: Db,
+    root: Option<PathBuf>,
+    /// The ingot in the workspace root directory, if there is one.
+    ingot: Option<IngotId>,
+    /// Files that aren't part of the workspace ingot are analyzed as
+    /// standalone modules, each in its own ingot.
+    standalone: IndexMap<PathBuf, ModuleId>,
+    /// Canonical paths of all local source files of the workspace ingot
+    /// and its dependencies.
+    files: IndexMap<PathBuf, SourceFileId>,
+    /// Diagnostics that aren't associated with any source file, e.g. an
+    /// invalid `fe.toml`.
+    pub load_diagnostics: Vec<Diagnostic>,
+}
+
+impl Workspace {
+    pub fn new(root: Option<PathBuf>) -> Self {
+        let mut workspace = Workspace {
+            db: Db::default(),
+            root,
+            ingot: None,
+            standalone: IndexMap::default(),
+            files: IndexMap::default(),
+            load_diagnostics: vec![],
+        };
+        workspace.load_ingot();
+        workspace
+    }
+
+    /// (Re)loads the ingot in the workspace root and its dependencies from
+    /// disk. Contents of files that are open in the editor are kept.
+    pub fn load_ingot(&mut self) {
+        let root = match &self.root {
+            Some(root) if contains_fe_sources(root) => root.clone(),
+            _ => return,
+        };
+
+        let open_files = self
+            .files
+            .iter()
+            .map(|(path, file)| (path.clone(), file.content(&self.db)))
+            .collect::<IndexMap<_, _>>();
+
+        match fe_driver::resolve(&root) {
+            Ok(graph) => {
+                let ingot = fe_driver::ingots_from_graph(&mut self.db, &graph);
+                self.ingot = Some(ingot);
+                self.load_diagnostics.clear();
+            }
+            Err(diags) => {
+                self.load_diagnostics = diags;
+                return;
+            }
+        }
+
+        self.files = self
+            .ingots()
+            .iter()
+            .flat_map(|ingot| ingot.all_modules(&self.db).to_vec())
+            .filter_map(|module| self.module_file(module))
+            .filter_map(|file| Some((canonical_file_path(&self.db, file)?, file)))
+            .collect();
+
+        // Files that are being edited have to keep the editor's content.
+        for (path, content) in open_files {
+            if let Some(file) = self.files.get(&path) {
+                if file.content(&self.db) != content {
+                    self.db.set_file_content(*file, content);
+                }
+            }
+        }
+    }
+
+    /// Reloads the workspace ingot if `path` is under the root directory but
+    /// isn't part of the ingot yet, e.g. because the file was just created.
+    /// This is only done when a file is opened or saved, as reloading reads
+    /// the whole ingot from disk. Returns `true` if the file was added.
+    pub fn add_ingot_file(&mut self, path: &Path) -> bool {
+        if self.files.contains_key(path) || !self.is_in_root(path) || self.ingot.is_none() {
+            return false;
+        }
+        self.load_ingot();
+        if self.files.contains_key(path) {
+            self.standalone.shift_remove(path);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sets the content of a file. Files that aren't part of the workspace
+    /// ingot are analyzed as standalone modules.
+    pub fn set_file_content(&mut self, path: &Path, content: &str) {
+        if let Some(file) = self.files.get(path) {
+            self.db.set_file_content(*file, content.into());
+            return;
+        }
+
+        match self.standalone.get(path) {
+            Some(module) => {
+                let file = self
+                    .module_file(*module)
+                    .expect("standalone module without a file");
+                self.db.set_file_content(file, content.into());
+            }
+            None => {
+                let module =
+                    ModuleId::new_standalone(&mut self.db, &path.to_string_lossy(), content);
+                self.standalone.insert(path.to_path_buf(), module);
+                self.restore_root_ingot();
+            }
+        }
+    }
+
+    /// Returns the module of the given source file.
+    pub fn module(&self, path: &Path) -> Option<ModuleId> {
+        if let Some(module) = self.standalone.get(path) {
+            return Some(*module);
+        }
+        let file = *self.files.get(path)?;
+        self.ingots()
+            .iter()
+            .flat_map(|ingot| ingot.all_modules(&self.db).to_vec())
+            .find(|module| self.module_file(*module) == Some(file))
+    }
+
+    pub fn file(&self, path: &Path) -> Option<SourceFileId> {
+        self.files
+            .get(path)
+            .copied()
+            .or_else(|| self.module_file(*self.standalone.get(path)?))
+    }
+
+    /// All files for which diagnostics are published.
+    pub fn files(&self) -> Vec<SourceFileId> {
+        self.files
+            .values()
+            .copied()
+            .chain(
+                self.standalone
+                    .values()
+                    .filter_map(|module| self.module_file(*module)),
+            )
+            .collect()
+    }
+
+    /// Runs `f` with the root ingot of the database set to the ingot of the
+    /// given module. Some queries (e.g. trait impl lookup) depend on it.
+    pub fn with_module<T>(&mut self, module: ModuleId, f: impl FnOnce(&Db) -> T) -> T {
+        let ingot = module.ingot(&self.db);
+        if self.db.root_ingot() != ingot {
+            self.db.set_root_ingot(ingot);
+        }
+        let result = f(&self.db);
+        self.restore_root_ingot();
+        result
+    }
+
+    /// Diagnostics of the workspace ingot, its dependencies and all
+    /// standalone modules.
+    pub fn diagnostics(&mut self) -> Vec<Diagnostic> {
+        let mut diags = self.load_diagnostics.clone();
+        if let Some(ingot) = self.ingot {
+            ingot.sink_diagnostics(&self.db, &mut diags);
+            ingot.sink_external_ingot_diagnostics(&self.db, &mut diags);
+        }
+        for module in self.standalone.values().copied().collect::<Vec<_>>() {
+            diags.extend(self.with_module(module, |db| module.diagnostics(db)));
+        }
+        diags
+    }
+
+    /// All functions defined in the module, including contract, struct, enum
+    /// and impl functions.
+    pub fn module_functions(&self, module: ModuleId) -> Vec<FunctionId> {
+        let db = &self.db;
+        module
+            .all_items(db)
+            .iter()
+            .flat_map(|item| match item {
+                Item::Function(function) => vec![*function],
+                Item::Type(TypeDef::Contract(contract)) => contract.all_functions(db).to_vec(),
+                Item::Type(TypeDef::Struct(struct_)) => struct_.all_functions(db).to_vec(),
+                Item::Type(TypeDef::Enum(enum_)) => enum_.all_functions(db).to_vec(),
+                Item::Impl(impl_) => impl_.all_functions(db).to_vec(),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    /// The innermost function of the module whose definition contains the
+    /// byte offset.
+    pub fn function_at(&self, module: ModuleId, offset: usize) -> Option<FunctionId> {
+        self.module_functions(module)
+            .into_iter()
+            .filter(|function| {
+                let span = function.span(&self.db);
+                span.start <= offset && offset <= span.end
+            })
+            .min_by_key(|function| function.span(&self.db).end - function.span(&self.db).start)
+    }
+
+    pub fn module_file(&self, module: ModuleId) -> Option<SourceFileId> {
+        match module.data(&self.db).source {
+            ModuleSource::File(file) => Some(file),
+            ModuleSource::Dir(_) => None,
+        }
+    }
+
+    /// The workspace ingot and every ingot it depends on.
+    fn ingots(&self) -> Vec<IngotId> {
+        let mut ingots = IndexSet::new();
+        let mut stack: Vec<_> = self.ingot.into_iter().collect();
+        while let Some(ingot) = stack.pop() {
+            if ingot.data(&self.db).mode != IngotMode::StandaloneModule && ingots.insert(ingot) {
+                stack.extend(ingot.external_ingots(&self.db).values().copied());
+            }
+        }
+        ingots.into_iter().collect()
+    }
+
+    fn restore_root_ingot(&mut self) {
+        if let Some(ingot) = self.ingot {
+            if self.db.root_ingot() != ingot {
+                self.db.set_root_ingot(ingot);
+            }
+        }
+    }
+
+    fn is_in_root(&self, path: &Path) -> bool {
+        self.root
+            .as_ref()
+            .is_some_and(|root| path.starts_with(root))
+    }
+}
+
+fn canonical_file_path(db: &dyn SourceDb, file: SourceFileId) -> Option<PathBuf> {
+    if db.lookup_intern_file(file).kind != FileKind::Local {
+        return None;
+    }
+    Path::new(file.path(db).as_str()).canonicalize().ok()
+}
+
+fn contains_fe_sources(dir: &Path) -> bool {
+    dir.join(fe_driver::MANIFEST_FILE_NAME).is_file()
+        || fe_driver::load_files_from_dir(dir).is_ok_and(|files| !files.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Workspace;
+    use fe_analyzer::namespace::items::IngotMode;
+    use std::path::Path;
+
+    #[test]
+    fn test_new_file_joins_ingot_when_opened() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../test-files/fixtures/ingots/language_server_ingot/src");
+        let root = std::env::temp_dir().join(format!("fe-ls-workspace-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        for name in ["main.fe", "token.fe"] {
+            std::fs::copy(fixture.join(name), root.join("src").join(name)).unwrap();
+        }
+        let root = root.canonicalize().unwrap();
+        let mut workspace = Workspace::new(Some(root.clone()));
+
+        let path = root.join("src/extra.fe");
+        let content = "pub fn extra() -> u256 {\n    return 1\n}\n";
+        std::fs::write(&path, content).unwrap();
+        let mode = |workspace: &Workspace| {
+            let module = workspace.module(&path).unwrap();
+            module.ingot(&workspace.db).data(&workspace.db).mode
+        };
+
+        // Edits don't reload the ingot.
+        workspace.set_file_content(&path, content);
+        assert_eq!(mode(&workspace), IngotMode::StandaloneModule);
+
+        assert!(workspace.add_ingot_file(&path));
+        workspace.set_file_content(&path, content);
+        assert_eq!(mode(&workspace), IngotMode::Main);
+        assert!(!workspace.add_ingot_file(&path));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -114,6 +114,11 @@ impl ir::FunctionId {
                 );
                 format!("{class_name}::{func_name}").into()
             }
+            Some(Item::Type(def)) => {
+                let class_ty = def.type_id(db.upcast()).unwrap();
+                let class_name = class_ty.display(db.upcast());
+                format!("{class_name}::{func_name}").into()
+            }
             Some(class) => {
                 let class_name = class.name(db.upcast());
                 format!("{class_name}::{func_name}").into()
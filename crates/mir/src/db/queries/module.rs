@@ -16,14 +16,24 @@ pub fn mir_lower_module_all_functions(
             functions.push(db.mir_lowered_func_signature(*func))
         }
 
+        // Interfaces have no bodies to lower.
+        analyzer_items::Item::Type(TypeDef::Contract(contract))
+            if contract.is_interface(db.upcast()) => {}
+
         analyzer_items::Item::Type(TypeDef::Contract(contract)) => {
             functions.extend_from_slice(&db.mir_lower_contract_all_functions(*contract))
         }
 
+        // Generic definitions are only lowered for their instances.
+        analyzer_items::Item::Type(TypeDef::Struct(struct_)) if struct_.is_generic(db.upcast()) => {
+        }
+
         analyzer_items::Item::Type(TypeDef::Struct(struct_)) => {
             functions.extend_from_slice(&db.mir_lower_struct_all_functions(*struct_))
         }
 
+        analyzer_items::Item::Type(TypeDef::Enum(enum_)) if enum_.is_generic(db.upcast()) => {}
+
         analyzer_items::Item::Type(TypeDef::Enum(enum_)) => {
             functions.extend_from_slice(&db.mir_lower_enum_all_functions(*enum_))
         }
@@ -8,7 +8,7 @@ use num_traits::ToPrimitive;
 use crate::{
     db::MirDb,
     ir::{
-        types::{ArrayDef, TupleDef, TypeKind},
+        types::{ArrayDef, TupleDef, TypeKind, VecDef},
         Type, TypeId, Value,
     },
     lower::types::lower_type,
@@ -30,6 +30,10 @@ impl TypeId {
     pub fn projection_ty(self, db: &dyn MirDb, access: &Value) -> TypeId {
         let ty = self.deref(db);
         let pty = match &ty.data(db).kind {
+            // The elements of a memory `Vec` live in memory regardless of how the
+            // `Vec` itself is referred to.
+            TypeKind::Vec(def) if !self.is_sptr(db) => return def.elem_ty.make_mptr(db),
+            TypeKind::Vec(def) => def.elem_ty,
             TypeKind::Array(ArrayDef { elem_ty, .. }) => *elem_ty,
             TypeKind::Tuple(def) => {
                 let index = expect_projection_index(access);
@@ -235,11 +239,12 @@ impl TypeId {
             | TypeKind::SPtr(..)
             | TypeKind::I256
             | TypeKind::U256
-            | TypeKind::Map(_) => 32,
+            | TypeKind::Map(_)
+            | TypeKind::Vec(_) => 32,
             TypeKind::Address => 20,
             TypeKind::Unit => 0,
 
-            TypeKind::Array(def) => array_elem_size_imp(db, def, slot_size) * def.len,
+            TypeKind::Array(def) => array_elem_size_imp(db, def.elem_ty, slot_size) * def.len,
 
             TypeKind::Tuple(def) => {
                 if def.items.is_empty() {
@@ -302,7 +307,7 @@ impl TypeId {
         }
 
         match &self.data(db).kind {
-            TypeKind::Array(def) => array_elem_size_imp(db, def, slot_size) * elem_idx,
+            TypeKind::Array(def) => array_elem_size_imp(db, def.elem_ty, slot_size) * elem_idx,
             TypeKind::Enum(_) => self.enum_data_offset(db, slot_size),
             _ => {
                 let mut offset = self.aggregate_elem_offset(db, elem_idx - 1, slot_size)
@@ -362,16 +367,24 @@ impl TypeId {
         matches!(self.data(db).kind, TypeKind::Map(_))
     }
 
+    pub fn is_vec(self, db: &dyn MirDb) -> bool {
+        matches!(self.data(db).kind, TypeKind::Vec(_))
+    }
+
     pub fn is_contract(self, db: &dyn MirDb) -> bool {
         matches!(self.data(db).kind, TypeKind::Contract(_))
     }
 
     pub fn array_elem_size(self, db: &dyn MirDb, slot_size: usize) -> usize {
         let data = self.data(db);
-        if let TypeKind::Array(def) = &data.kind {
-            array_elem_size_imp(db, def, slot_size)
-        } else {
-            panic!("expected `Array` type; but got {:?}", data.as_ref())
+        match &data.kind {
+            TypeKind::Array(ArrayDef { elem_ty, .. }) | TypeKind::Vec(VecDef { elem_ty }) => {
+                array_elem_size_imp(db, *elem_ty, slot_size)
+            }
+            _ => panic!(
+                "expected `Array` or `Vec` type; but got {:?}",
+                data.as_ref()
+            ),
         }
     }
 
@@ -428,6 +441,11 @@ impl TypeId {
                 def.value_ty.print(db, w)?;
                 write!(w, ">")
             }
+            TypeKind::Vec(def) => {
+                write!(w, "Vec<")?;
+                def.elem_ty.print(db, w)?;
+                write!(w, ">")
+            }
             TypeKind::MPtr(inner) => {
                 write!(w, "*@m ")?;
                 inner.print(db, w)
@@ -446,8 +464,7 @@ impl TypeId {
     }
 }
 
-fn array_elem_size_imp(db: &dyn MirDb, arr: &ArrayDef, slot_size: usize) -> usize {
-    let elem_ty = arr.elem_ty;
+fn array_elem_size_imp(db: &dyn MirDb, elem_ty: TypeId, slot_size: usize) -> usize {
     let elem = elem_ty.size_of(db, slot_size);
     let align = if elem_ty.is_address(db) {
         slot_size
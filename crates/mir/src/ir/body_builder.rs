@@ -213,6 +213,30 @@ impl BodyBuilder {
         self.insert_inst(inst)
     }
 
+    pub fn vec_new(&mut self, ty: TypeId, source: SourceInfo) -> InstId {
+        let kind = InstKind::VecNew { ty };
+        let inst = Inst::new(kind, source);
+        self.insert_inst(inst)
+    }
+
+    pub fn vec_push(&mut self, vec: ValueId, value: ValueId, source: SourceInfo) -> InstId {
+        let kind = InstKind::VecPush { vec, value };
+        let inst = Inst::new(kind, source);
+        self.insert_inst(inst)
+    }
+
+    pub fn vec_pop(&mut self, vec: ValueId, source: SourceInfo) -> InstId {
+        let kind = InstKind::VecPop { vec };
+        let inst = Inst::new(kind, source);
+        self.insert_inst(inst)
+    }
+
+    pub fn vec_len(&mut self, vec: ValueId, source: SourceInfo) -> InstId {
+        let kind = InstKind::VecLen { vec };
+        let inst = Inst::new(kind, source);
+        self.insert_inst(inst)
+    }
+
     pub fn call(
         &mut self,
         func: FunctionId,
@@ -241,8 +265,18 @@ impl BodyBuilder {
         self.insert_inst(inst)
     }
 
-    pub fn create(&mut self, value: ValueId, contract: ContractId, source: SourceInfo) -> InstId {
-        let kind = InstKind::Create { value, contract };
+    pub fn create(
+        &mut self,
+        value: ValueId,
+        contract: ContractId,
+        args: Vec<ValueId>,
+        source: SourceInfo,
+    ) -> InstId {
+        let kind = InstKind::Create {
+            value,
+            contract,
+            args,
+        };
         let inst = Inst::new(kind, source);
         self.insert_inst(inst)
     }
@@ -252,12 +286,14 @@ impl BodyBuilder {
         value: ValueId,
         salt: ValueId,
         contract: ContractId,
+        args: Vec<ValueId>,
         source: SourceInfo,
     ) -> InstId {
         let kind = InstKind::Create2 {
             value,
             salt,
             contract,
+            args,
         };
         let inst = Inst::new(kind, source);
         self.insert_inst(inst)
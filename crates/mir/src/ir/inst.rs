@@ -78,6 +78,27 @@ pub enum InstKind {
         value: ValueId,
     },
 
+    /// Creates an empty `Vec` in memory.
+    VecNew {
+        ty: TypeId,
+    },
+
+    /// Appends `value` to the end of `vec`.
+    VecPush {
+        vec: ValueId,
+        value: ValueId,
+    },
+
+    /// Removes the last element of `vec` and returns a pointer to it.
+    VecPop {
+        vec: ValueId,
+    },
+
+    /// Returns the number of elements in `vec`.
+    VecLen {
+        vec: ValueId,
+    },
+
     Call {
         func: FunctionId,
         args: Vec<ValueId>,
@@ -124,15 +145,18 @@ pub enum InstKind {
 
     Nop,
 
+    /// Deploys `contract`, passing `args` to its `__init__` function.
     Create {
         value: ValueId,
         contract: ContractId,
+        args: Vec<ValueId>,
     },
 
     Create2 {
         value: ValueId,
         salt: ValueId,
         contract: ContractId,
+        args: Vec<ValueId>,
     },
 
     YulIntrinsic {
@@ -232,7 +256,8 @@ impl Inst {
             | Emit { arg }
             | Keccak256 { arg }
             | AbiEncode { arg }
-            | Create { value: arg, .. }
+            | VecPop { vec: arg }
+            | VecLen { vec: arg }
             | Branch { cond: arg, .. } => ValueIter::one(*arg),
 
             Switch { disc, table, .. } => {
@@ -244,15 +269,24 @@ impl Inst {
                 value: lhs,
                 key: rhs,
             }
-            | Create2 {
-                value: lhs,
-                salt: rhs,
-                ..
+            | VecPush {
+                vec: lhs,
+                value: rhs,
             } => ValueIter::one(*lhs).chain(ValueIter::one(*rhs)),
 
+            Create { value, args, .. } => {
+                ValueIter::one(*value).chain(ValueIter::Slice(args.iter()))
+            }
+
+            Create2 {
+                value, salt, args, ..
+            } => ValueIter::one(*value)
+                .chain(ValueIter::one(*salt))
+                .chain(ValueIter::Slice(args.iter())),
+
             Revert { arg } | Return { arg } => ValueIter::One(*arg),
 
-            Nop | Jump { .. } => ValueIter::Zero,
+            Nop | Jump { .. } | VecNew { .. } => ValueIter::Zero,
 
             AggregateAccess { value, indices } => {
                 ValueIter::one(*value).chain(ValueIter::Slice(indices.iter()))
@@ -276,7 +310,8 @@ impl Inst {
             | Emit { arg }
             | Keccak256 { arg }
             | AbiEncode { arg }
-            | Create { value: arg, .. }
+            | VecPop { vec: arg }
+            | VecLen { vec: arg }
             | Branch { cond: arg, .. } => ValueIterMut::one(arg),
 
             Switch { disc, table, .. } => {
@@ -288,15 +323,24 @@ impl Inst {
                 value: lhs,
                 key: rhs,
             }
-            | Create2 {
-                value: lhs,
-                salt: rhs,
-                ..
+            | VecPush {
+                vec: lhs,
+                value: rhs,
             } => ValueIterMut::one(lhs).chain(ValueIterMut::one(rhs)),
 
+            Create { value, args, .. } => {
+                ValueIterMut::one(value).chain(ValueIterMut::Slice(args.iter_mut()))
+            }
+
+            Create2 {
+                value, salt, args, ..
+            } => ValueIterMut::one(value)
+                .chain(ValueIterMut::one(salt))
+                .chain(ValueIterMut::Slice(args.iter_mut())),
+
             Revert { arg } | Return { arg } => ValueIterMut::One(arg.as_mut()),
 
-            Nop | Jump { .. } => ValueIterMut::Zero,
+            Nop | Jump { .. } | VecNew { .. } => ValueIterMut::Zero,
 
             AggregateAccess { value, indices } => {
                 ValueIterMut::one(value).chain(ValueIterMut::Slice(indices.iter_mut()))
@@ -40,6 +40,7 @@ pub enum TypeKind {
     Enum(EnumDef),
     Contract(StructDef),
     Map(MapDef),
+    Vec(VecDef),
     MPtr(TypeId),
     SPtr(TypeId),
 }
@@ -56,6 +57,12 @@ pub struct ArrayDef {
     pub len: usize,
 }
 
+/// A dynamically sized vector type definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VecDef {
+    pub elem_ty: TypeId,
+}
+
 /// A tuple type definition.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TupleDef {
@@ -1,7 +1,7 @@
 use std::{collections::BTreeMap, rc::Rc, vec};
 
 use fe_analyzer::{
-    builtins::{ContractTypeMethod, GlobalFunction, ValueMethod},
+    builtins::{ContractTypeMethod, GlobalFunction, ValueMethod, VecMethod},
     constants::{EMITTABLE_TRAIT_NAME, EMIT_FN_NAME},
     context::{Adjustment, AdjustmentKind, CallType as AnalyzerCallType, NamedThing},
     namespace::{
@@ -23,11 +23,12 @@ use crate::{
         body_builder::BodyBuilder,
         constant::ConstantValue,
         function::Linkage,
-        inst::{CallType, InstKind},
+        inst::{CallType, InstKind, SwitchTable},
         value::{AssignableValue, Local},
         BasicBlockId, Constant, FunctionBody, FunctionId, FunctionParam, FunctionSignature, InstId,
         SourceInfo, TypeId, Value, ValueId,
     },
+    lower::types::resolve_generics,
 };
 
 type ScopeId = Id<Scope>;
@@ -57,16 +58,25 @@ pub fn lower_monomorphized_func_signature(
             if let Type::Generic(generic) = param.typ.clone().unwrap().deref_typ(db.upcast()) {
                 *resolved_generics.get(&generic.name).unwrap()
             } else {
-                param.typ.clone().unwrap()
+                resolve_generics(db, param.typ.clone().unwrap(), &resolved_generics)
             };
 
         params.push(make_param(db, param.clone().name, param_type, source))
     }
 
-    let return_type = db.mir_lowered_type(analyzer_signature.return_type.clone().unwrap());
+    let return_type = db.mir_lowered_type(resolve_generics(
+        db,
+        analyzer_signature.return_type.clone().unwrap(),
+        &resolved_generics,
+    ));
 
     let linkage = if func.is_public(db.upcast()) {
-        if func.is_contract_func(db.upcast()) && !func.is_constructor(db.upcast()) {
+        // The dispatcher calls `receive` and `fallback` without a selector.
+        if func.is_contract_func(db.upcast())
+            && !func.is_constructor(db.upcast())
+            && !func.is_receive(db.upcast())
+            && !func.is_fallback(db.upcast())
+        {
             Linkage::Export
         } else {
             Linkage::Public
@@ -403,7 +413,7 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
 
             ast::Expr::Subscript { value, index } => {
                 let value_ty = self.expr_ty(value).deref(self.db);
-                if value_ty.is_aggregate(self.db) {
+                if value_ty.is_aggregate(self.db) || value_ty.is_vec(self.db) {
                     let mut indices = vec![];
                     let value = self.lower_aggregate_access(expr, &mut indices);
                     self.builder.aggregate_access(value, indices, expr.into())
@@ -461,7 +471,7 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
             }
 
             ast::Expr::Path(path) => {
-                let value = self.resolve_path(path, expr.into());
+                let value = self.resolve_path(path, expr);
                 self.builder.bind(value, expr.into())
             }
 
@@ -486,6 +496,8 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 let value = self.make_unit();
                 self.builder.bind(value, expr.into())
             }
+
+            ast::Expr::Try { value } => self.lower_try(value, expr.into()),
         };
 
         for Adjustment { into, kind } in &self.analyzer_body.expressions[&expr.id].type_adjustments
@@ -512,6 +524,83 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
         (inst, ty)
     }
 
+    /// Lowers `value?` to a match on `value`: `None` or `Err(..)` is returned
+    /// from the function, and the `Some` or `Ok` value is the result.
+    fn lower_try(&mut self, value: &Node<ast::Expr>, source: SourceInfo) -> InstId {
+        let value = self.lower_expr_to_value(value);
+        let value_ty = self.builder.value_ty(value);
+        let enum_ = match value_ty
+            .deref(self.db)
+            .data(self.db)
+            .analyzer_ty
+            .map(|ty| ty.typ(self.db.upcast()))
+        {
+            Some(Type::Enum(id)) => id,
+            _ => unreachable!(),
+        };
+        let is_option = enum_.is_std_enum(self.db.upcast(), "Option");
+        let (ok_name, err_name) = if is_option {
+            ("Some", "None")
+        } else {
+            ("Ok", "Err")
+        };
+        let ok_variant = enum_.variant(self.db.upcast(), ok_name).unwrap();
+        let err_variant = enum_.variant(self.db.upcast(), err_name).unwrap();
+
+        let disc_ty = value_ty.enum_disc_type(self.db);
+        let disc_index = self.make_u256_imm(0);
+        let inst = self
+            .builder
+            .aggregate_access(value, vec![disc_index], SourceInfo::dummy());
+        let disc = self.map_to_tmp(inst, disc_ty);
+
+        let ok_bb = self.builder.make_block();
+        let err_bb = self.builder.make_block();
+        let mut table = SwitchTable::default();
+        let err_disc = self.make_imm(err_variant.disc(self.db.upcast()), disc_ty);
+        table.add_arm(err_disc, err_bb);
+        self.builder
+            .switch(disc, table, Some(ok_bb), SourceInfo::dummy());
+
+        self.builder.move_to_block(err_bb);
+        let ret_ty = self.func.signature(self.db).return_type.unwrap();
+        let ret_disc_ty = ret_ty.enum_disc_type(self.db);
+        let ret_disc = self.make_imm(err_variant.disc(self.db.upcast()), ret_disc_ty);
+        let err_data = if is_option {
+            self.make_unit()
+        } else {
+            self.variant_data(value, value_ty, err_variant)
+        };
+        let inst =
+            self.builder
+                .aggregate_construct(ret_ty, vec![ret_disc, err_data], source.clone());
+        let ret_value = self.map_to_tmp(inst, ret_ty);
+        self.builder.ret(ret_value, source.clone());
+
+        self.builder.move_to_block(ok_bb);
+        let ok_value = self.variant_data(value, value_ty, ok_variant);
+        self.builder.bind(ok_value, source)
+    }
+
+    /// The value held by the single-field `variant` of the enum `value`.
+    fn variant_data(
+        &mut self,
+        value: ValueId,
+        value_ty: TypeId,
+        variant: analyzer_items::EnumVariantId,
+    ) -> ValueId {
+        let variant_ty = value_ty.enum_variant_type(self.db, variant);
+        let cast = self
+            .builder
+            .untag_cast(value, variant_ty, SourceInfo::dummy());
+        let variant_value = self.map_to_tmp(cast, variant_ty);
+        let index = self.make_u256_imm(0);
+        let inst = self
+            .builder
+            .aggregate_access(variant_value, vec![index], SourceInfo::dummy());
+        self.map_to_tmp(inst, variant_ty.projection_ty_imm(self.db, 0))
+    }
+
     fn inst_result_or_tmp(&mut self, inst: InstId, ty: TypeId) -> ValueId {
         self.builder
             .inst_result(inst)
@@ -600,7 +689,9 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
             return self.db.mir_lowered_type(resolved_type);
         }
 
-        self.db.mir_lowered_type(analyzer_ty)
+        let resolved_generics = &self.func.signature(self.db).resolved_generics;
+        self.db
+            .mir_lowered_type(resolve_generics(self.db, analyzer_ty, resolved_generics))
     }
 
     fn lower(mut self) -> FunctionBody {
@@ -738,10 +829,14 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
 
         // Create maximum loop count.
         let maximum_iter_count = match &iter_ty.deref(self.db).data(self.db).kind {
-            ir::TypeKind::Array(ir::types::ArrayDef { len, .. }) => *len,
+            ir::TypeKind::Array(ir::types::ArrayDef { len, .. }) => self.make_u256_imm(*len),
+            ir::TypeKind::Vec(_) => {
+                let len = self.builder.vec_len(iter, SourceInfo::dummy());
+                let u256_ty = self.u256_ty();
+                self.map_to_tmp(len, u256_ty)
+            }
             _ => unreachable!(),
         };
-        let maximum_iter_count = self.make_u256_imm(maximum_iter_count);
         self.branch_eq(
             loop_idx,
             maximum_iter_count,
@@ -796,7 +891,7 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 let lhs = self.lower_assignable_value(value).into();
                 let attr = self.lower_expr_to_value(index);
                 let value_ty = self.expr_ty(value).deref(self.db);
-                if value_ty.is_aggregate(self.db) {
+                if value_ty.is_aggregate(self.db) || value_ty.is_vec(self.db) {
                     AssignableValue::Aggregate { lhs, idx: attr }
                 } else if value_ty.is_map(self.db) {
                     AssignableValue::Map { lhs, key: attr }
@@ -805,7 +900,7 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 }
             }
             ast::Expr::Name(name) => self.resolve_name(name).into(),
-            ast::Expr::Path(path) => self.resolve_path(path, expr.into()).into(),
+            ast::Expr::Path(path) => self.resolve_path(path, expr).into(),
             _ => self.lower_expr_to_value(expr).into(),
         }
     }
@@ -983,11 +1078,24 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 }
             }
 
+            AnalyzerCallType::BuiltinVecMethod { method, .. } => {
+                let vec = self.lower_method_receiver(func);
+                match method {
+                    VecMethod::Push => self.builder.vec_push(vec, args[0], source),
+                    VecMethod::Pop => self.builder.vec_pop(vec, source),
+                    VecMethod::Len => self.builder.vec_len(vec, source),
+                }
+            }
+
             // We ignores `args[0]', which represents `context` and not used for now.
             AnalyzerCallType::BuiltinAssociatedFunction { contract, function } => match function {
-                ContractTypeMethod::Create => self.builder.create(args[1], *contract, source),
+                ContractTypeMethod::Create => {
+                    self.builder
+                        .create(args[1], *contract, args[2..].to_vec(), source)
+                }
                 ContractTypeMethod::Create2 => {
-                    self.builder.create2(args[1], args[2], *contract, source)
+                    self.builder
+                        .create2(args[1], args[2], *contract, args[3..].to_vec(), source)
                 }
             },
 
@@ -1070,6 +1178,8 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                     }
                 } else if ty.is_aggregate(self.db) {
                     self.builder.aggregate_construct(ty, args, source)
+                } else if ty.is_vec(self.db) {
+                    self.builder.vec_new(ty, source)
                 } else {
                     unreachable!()
                 }
@@ -1111,7 +1221,8 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
             }
 
             ast::Expr::Subscript { value, index }
-                if self.expr_ty(value).deref(self.db).is_aggregate(self.db) =>
+                if self.expr_ty(value).deref(self.db).is_aggregate(self.db)
+                    || self.expr_ty(value).deref(self.db).is_vec(self.db) =>
             {
                 let value = self.lower_aggregate_access(value, indices);
                 indices.push(self.lower_expr_to_value(index));
@@ -1190,7 +1301,8 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
 
     /// Resolve a path appeared in an expression.
     /// NOTE: Don't call this to resolve method receiver.
-    fn resolve_path(&mut self, path: &ast::Path, source: SourceInfo) -> ValueId {
+    fn resolve_path(&mut self, path: &ast::Path, expr: &Node<ast::Expr>) -> ValueId {
+        let source = SourceInfo::from(expr);
         let func_id = self.builder.func_id();
         let module = func_id.module(self.db);
         match module.resolve_path(self.db.upcast(), path).value.unwrap() {
@@ -1200,9 +1312,9 @@ impl<'db, 'a> BodyLowerHelper<'db, 'a> {
                 self.builder.make_constant(constant, ty)
             }
             NamedThing::EnumVariant(variant) => {
-                let enum_ty = self
-                    .db
-                    .mir_lowered_type(variant.parent(self.db.upcast()).as_type(self.db.upcast()));
+                // The type of the expression, rather than the parent of the variant, is
+                // the instance of a generic enum.
+                let enum_ty = self.expr_ty(expr);
                 let tag_type = enum_ty.enum_disc_type(self.db);
                 let tag = self.make_imm(variant.disc(self.db.upcast()), tag_type);
                 let data = self.make_unit();
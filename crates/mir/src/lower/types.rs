@@ -1,7 +1,7 @@
 use crate::{
     db::MirDb,
     ir::{
-        types::{ArrayDef, EnumDef, EnumVariant, MapDef, StructDef, TupleDef},
+        types::{ArrayDef, EnumDef, EnumVariant, MapDef, StructDef, TupleDef, VecDef},
         Type, TypeId, TypeKind,
     },
 };
@@ -10,6 +10,8 @@ use fe_analyzer::namespace::{
     items as analyzer_items,
     types::{self as analyzer_types, TraitOrType},
 };
+use smol_str::SmolStr;
+use std::collections::BTreeMap;
 
 pub fn lower_type(db: &dyn MirDb, analyzer_ty: analyzer_types::TypeId) -> TypeId {
     let ty_kind = match analyzer_ty.typ(db.upcast()) {
@@ -22,6 +24,7 @@ pub fn lower_type(db: &dyn MirDb, analyzer_ty: analyzer_types::TypeId) -> TypeId
             analyzer_types::Type::SPtr(t) => TypeKind::SPtr(lower_type(db, t)),
             analyzer_types::Type::Base(t) => lower_base(t),
             analyzer_types::Type::Contract(_) => TypeKind::Address,
+            analyzer_types::Type::Vec(elem) => lower_vec(db, elem),
             _ => TypeKind::MPtr(lower_type(db, inner)),
         },
         analyzer_types::Type::SelfType(inner) => match inner {
@@ -31,6 +34,7 @@ pub fn lower_type(db: &dyn MirDb, analyzer_ty: analyzer_types::TypeId) -> TypeId
         analyzer_types::Type::Base(base) => lower_base(base),
         analyzer_types::Type::Array(arr) => lower_array(db, &arr),
         analyzer_types::Type::Map(map) => lower_map(db, &map),
+        analyzer_types::Type::Vec(elem) => lower_vec(db, elem),
         analyzer_types::Type::Tuple(tup) => lower_tuple(db, &tup),
         analyzer_types::Type::String(string) => TypeKind::String(string.max_size),
         analyzer_types::Type::Contract(_) => TypeKind::Address,
@@ -45,6 +49,63 @@ pub fn lower_type(db: &dyn MirDb, analyzer_ty: analyzer_types::TypeId) -> TypeId
     intern_type(db, ty_kind, Some(analyzer_ty.deref(db.upcast())))
 }
 
+/// Replaces the generic parameters in `ty` with their resolved types. This
+/// monomorphizes instances of generic structs and enums whose generic
+/// arguments are generic parameters of a function, e.g. `Pair<T>` in
+/// `fn foo<T: Compute>(pair: Pair<T>)`.
+pub fn resolve_generics(
+    db: &dyn MirDb,
+    ty: analyzer_types::TypeId,
+    resolved_generics: &BTreeMap<SmolStr, analyzer_types::TypeId>,
+) -> analyzer_types::TypeId {
+    if resolved_generics.is_empty() {
+        return ty;
+    }
+
+    let resolve = |ty| resolve_generics(db, ty, resolved_generics);
+    let resolved = match ty.typ(db.upcast()) {
+        analyzer_types::Type::Generic(generic) => match resolved_generics.get(&generic.name) {
+            Some(resolved) => return *resolved,
+            None => return ty,
+        },
+        analyzer_types::Type::SPtr(inner) => analyzer_types::Type::SPtr(resolve(inner)),
+        analyzer_types::Type::Mut(inner) => analyzer_types::Type::Mut(resolve(inner)),
+        analyzer_types::Type::SelfType(TraitOrType::TypeId(inner)) => {
+            analyzer_types::Type::SelfType(TraitOrType::TypeId(resolve(inner)))
+        }
+        analyzer_types::Type::Array(arr) => analyzer_types::Type::Array(analyzer_types::Array {
+            inner: resolve(arr.inner),
+            size: arr.size,
+        }),
+        analyzer_types::Type::Map(map) => analyzer_types::Type::Map(analyzer_types::Map {
+            key: resolve(map.key),
+            value: resolve(map.value),
+        }),
+        analyzer_types::Type::Vec(elem) => analyzer_types::Type::Vec(resolve(elem)),
+        analyzer_types::Type::Tuple(tup) => analyzer_types::Type::Tuple(analyzer_types::Tuple {
+            items: tup.items.iter().map(|item| resolve(*item)).collect(),
+        }),
+        analyzer_types::Type::Struct(id) => {
+            let args: Vec<_> = id
+                .generic_args(db.upcast())
+                .iter()
+                .map(|arg| resolve(*arg))
+                .collect();
+            analyzer_types::Type::Struct(id.instantiate(db.upcast(), &args))
+        }
+        analyzer_types::Type::Enum(id) => {
+            let args: Vec<_> = id
+                .generic_args(db.upcast())
+                .iter()
+                .map(|arg| resolve(*arg))
+                .collect();
+            analyzer_types::Type::Enum(id.instantiate(db.upcast(), &args))
+        }
+        _ => return ty,
+    };
+    resolved.id(db.upcast())
+}
+
 fn lower_base(base: analyzer_types::Base) -> TypeKind {
     use analyzer_types::{Base, Integer};
 
@@ -86,6 +147,12 @@ fn lower_map(db: &dyn MirDb, map: &analyzer_types::Map) -> TypeKind {
     TypeKind::Map(def)
 }
 
+fn lower_vec(db: &dyn MirDb, elem: analyzer_types::TypeId) -> TypeKind {
+    let elem_ty = db.mir_lowered_type(elem);
+
+    TypeKind::Vec(VecDef { elem_ty })
+}
+
 fn lower_tuple(db: &dyn MirDb, tup: &analyzer_types::Tuple) -> TypeKind {
     let items = tup
         .items
@@ -2,7 +2,7 @@ use std::fmt::{self, Write};
 
 use crate::{
     db::MirDb,
-    ir::{function::BodyDataStore, inst::InstKind, InstId},
+    ir::{function::BodyDataStore, inst::InstKind, InstId, ValueId},
 };
 
 use super::PrettyPrint;
@@ -99,6 +99,28 @@ impl PrettyPrint for InstId {
                 write!(w, "}}")
             }
 
+            InstKind::VecNew { ty } => {
+                write!(w, "vec_new ")?;
+                ty.pretty_print(db, store, w)
+            }
+
+            InstKind::VecPush { vec, value } => {
+                write!(w, "vec_push ")?;
+                vec.pretty_print(db, store, w)?;
+                write!(w, " ")?;
+                value.pretty_print(db, store, w)
+            }
+
+            InstKind::VecPop { vec } => {
+                write!(w, "vec_pop ")?;
+                vec.pretty_print(db, store, w)
+            }
+
+            InstKind::VecLen { vec } => {
+                write!(w, "vec_len ")?;
+                vec.pretty_print(db, store, w)
+            }
+
             InstKind::Call {
                 func,
                 args,
@@ -176,24 +198,31 @@ impl PrettyPrint for InstId {
                 write!(w, "nop")
             }
 
-            InstKind::Create { value, contract } => {
+            InstKind::Create {
+                value,
+                contract,
+                args,
+            } => {
                 write!(w, "create ")?;
                 let contract_name = contract.name(db.upcast());
                 write!(w, "{contract_name} ")?;
-                value.pretty_print(db, store, w)
+                value.pretty_print(db, store, w)?;
+                pretty_print_create_args(args, db, store, w)
             }
 
             InstKind::Create2 {
                 value,
                 salt,
                 contract,
+                args,
             } => {
                 write!(w, "create2 ")?;
                 let contract_name = contract.name(db.upcast());
                 write!(w, "{contract_name} ")?;
                 value.pretty_print(db, store, w)?;
                 write!(w, " ")?;
-                salt.pretty_print(db, store, w)
+                salt.pretty_print(db, store, w)?;
+                pretty_print_create_args(args, db, store, w)
             }
 
             InstKind::YulIntrinsic { op, args } => {
@@ -204,3 +233,17 @@ impl PrettyPrint for InstId {
         }
     }
 }
+
+fn pretty_print_create_args<W: Write>(
+    args: &[ValueId],
+    db: &dyn MirDb,
+    store: &BodyDataStore,
+    w: &mut W,
+) -> fmt::Result {
+    if args.is_empty() {
+        return Ok(());
+    }
+    write!(w, " (")?;
+    args.pretty_print(db, store, w)?;
+    write!(w, ")")
+}
@@ -107,3 +107,7 @@ test_lowering! { mir_tuple_stress, "stress/tuple_stress.fe"}
 test_lowering! { mir_type_aliases, "features/type_aliases.fe"}
 test_lowering! { mir_const_generics, "features/const_generics.fe" }
 test_lowering! { mir_const_local, "features/const_local.fe" }
+test_lowering! { mir_generic_structs, "features/generic_structs.fe" }
+test_lowering! { mir_option_result, "features/option_result.fe" }
+test_lowering! { mir_vec, "features/vec.fe" }
+test_lowering! { mir_storage_vec, "features/storage_vec.fe" }
@@ -19,16 +19,25 @@ pub enum ModuleStmt {
     Use(Node<Use>),
     TypeAlias(Node<TypeAlias>),
     Contract(Node<Contract>),
+    Interface(Node<Interface>),
     Constant(Node<ConstantDecl>),
     Struct(Node<Struct>),
     Enum(Node<Enum>),
     Trait(Node<Trait>),
     Impl(Node<Impl>),
     Function(Node<Function>),
-    Attribute(Node<SmolStr>),
+    Attribute(Node<Attribute>),
     ParseError(Span),
 }
 
+/// A module-level attribute, like `#test` or `#test(should_revert)`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Attribute {
+    pub name: SmolStr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Node<Vec<Node<CallArg>>>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Pragma {
     pub version_requirement: Node<SmolStr>,
@@ -73,6 +82,8 @@ pub struct ConstantDecl {
     pub typ: Node<TypeDesc>,
     pub value: Node<Expr>,
     pub pub_qual: Option<Span>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
@@ -80,6 +91,8 @@ pub struct TypeAlias {
     pub name: Node<SmolStr>,
     pub typ: Node<TypeDesc>,
     pub pub_qual: Option<Span>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
@@ -88,22 +101,49 @@ pub struct Contract {
     pub fields: Vec<Node<Field>>,
     pub body: Vec<ContractStmt>,
     pub pub_qual: Option<Span>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
+}
+
+/// A contract declaration without function bodies, used to call contracts
+/// whose source isn't available.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Interface {
+    pub name: Node<SmolStr>,
+    pub functions: Vec<Node<FunctionSignature>>,
+    pub pub_qual: Option<Span>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Struct {
     pub name: Node<SmolStr>,
+    #[serde(
+        default = "GenericParameter::empty_list",
+        skip_serializing_if = "GenericParameter::is_empty_list"
+    )]
+    pub generic_params: Node<Vec<GenericParameter>>,
     pub fields: Vec<Node<Field>>,
     pub functions: Vec<Node<Function>>,
     pub pub_qual: Option<Span>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Enum {
     pub name: Node<SmolStr>,
+    #[serde(
+        default = "GenericParameter::empty_list",
+        skip_serializing_if = "GenericParameter::is_empty_list"
+    )]
+    pub generic_params: Node<Vec<GenericParameter>>,
     pub variants: Vec<Node<Variant>>,
     pub functions: Vec<Node<Function>>,
     pub pub_qual: Option<Span>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
@@ -111,6 +151,8 @@ pub struct Trait {
     pub name: Node<SmolStr>,
     pub functions: Vec<Node<FunctionSignature>>,
     pub pub_qual: Option<Span>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
@@ -118,6 +160,8 @@ pub struct Impl {
     pub impl_trait: Node<SmolStr>,
     pub receiver: Node<TypeDesc>,
     pub functions: Vec<Node<Function>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
@@ -167,6 +211,15 @@ pub enum GenericParameter {
 }
 
 impl GenericParameter {
+    /// The (empty) generic parameter list of a non-generic item.
+    fn empty_list() -> Node<Vec<GenericParameter>> {
+        Node::new(vec![], Span::dummy())
+    }
+
+    fn is_empty_list(params: &Node<Vec<GenericParameter>>) -> bool {
+        params.kind.is_empty()
+    }
+
     pub fn name(&self) -> SmolStr {
         self.name_node().kind
     }
@@ -197,6 +250,8 @@ pub struct Field {
     pub name: Node<SmolStr>,
     pub typ: Node<TypeDesc>,
     pub value: Option<Node<Expr>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 /// Enum variant definition.
@@ -204,6 +259,8 @@ pub struct Field {
 pub struct Variant {
     pub name: Node<SmolStr>,
     pub kind: VariantKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 /// Enum variant kind.
@@ -247,6 +304,11 @@ pub struct FunctionSignature {
     pub generic_params: Node<Vec<GenericParameter>>,
     pub args: Vec<Node<FunctionArg>>,
     pub return_type: Option<Node<TypeDesc>>,
+    /// Attributes like `#view` that precede the function definition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<Node<SmolStr>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<SmolStr>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
@@ -431,6 +493,11 @@ pub enum Expr {
         value: Box<Node<Expr>>,
         index: Box<Node<Expr>>,
     },
+    /// The propagation operator `value?`, which returns early from the
+    /// enclosing function if `value` is `Option::None` or `Result::Err`.
+    Try {
+        value: Box<Node<Expr>>,
+    },
     Call {
         func: Box<Node<Expr>>,
         generic_args: Option<Node<Vec<GenericArg>>>,
@@ -522,6 +589,12 @@ impl Node<Variant> {
     }
 }
 
+impl Node<Interface> {
+    pub fn name(&self) -> &str {
+        &self.kind.name.kind
+    }
+}
+
 impl Node<Trait> {
     pub fn name(&self) -> &str {
         &self.kind.name.kind
@@ -571,6 +644,7 @@ impl Spanned for ModuleStmt {
             ModuleStmt::Impl(inner) => inner.span,
             ModuleStmt::TypeAlias(inner) => inner.span,
             ModuleStmt::Contract(inner) => inner.span,
+            ModuleStmt::Interface(inner) => inner.span,
             ModuleStmt::Constant(inner) => inner.span,
             ModuleStmt::Struct(inner) => inner.span,
             ModuleStmt::Enum(inner) => inner.span,
@@ -619,6 +693,7 @@ impl fmt::Display for ModuleStmt {
             ModuleStmt::Impl(node) => write!(f, "{}", node.kind),
             ModuleStmt::TypeAlias(node) => write!(f, "{}", node.kind),
             ModuleStmt::Contract(node) => write!(f, "{}", node.kind),
+            ModuleStmt::Interface(node) => write!(f, "{}", node.kind),
             ModuleStmt::Constant(node) => write!(f, "{}", node.kind),
             ModuleStmt::Struct(node) => write!(f, "{}", node.kind),
             ModuleStmt::Enum(node) => write!(f, "{}", node.kind),
@@ -683,6 +758,7 @@ impl fmt::Display for ConstantDecl {
             typ,
             value,
             pub_qual,
+            doc: _,
         } = self;
         if pub_qual.is_some() {
             write!(f, "pub ")?;
@@ -717,6 +793,7 @@ impl fmt::Display for TypeAlias {
             name,
             typ,
             pub_qual,
+            doc: _,
         } = self;
         if pub_qual.is_some() {
             write!(f, "pub ")?;
@@ -732,6 +809,7 @@ impl fmt::Display for Contract {
             fields,
             body,
             pub_qual,
+            doc: _,
         } = self;
 
         if pub_qual.is_some() {
@@ -755,19 +833,49 @@ impl fmt::Display for Contract {
     }
 }
 
+impl fmt::Display for Interface {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Interface {
+            name,
+            functions,
+            pub_qual,
+            doc: _,
+        } = self;
+
+        if pub_qual.is_some() {
+            write!(f, "pub ")?;
+        }
+        write!(f, "interface {} {{", name.kind)?;
+        if !functions.is_empty() {
+            for sig in functions {
+                writeln!(f)?;
+                write!(indented(f), "{};", sig.kind)?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "}}")
+    }
+}
+
 impl fmt::Display for Struct {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let Struct {
             name,
+            generic_params,
             fields,
             functions,
             pub_qual,
+            doc: _,
         } = self;
 
         if pub_qual.is_some() {
             write!(f, "pub ")?;
         }
-        write!(f, "struct {} ", name.kind)?;
+        write!(f, "struct {}", name.kind)?;
+        if !generic_params.kind.is_empty() {
+            write!(f, "<{}>", comma_joined(generic_params.kind.iter()))?;
+        }
+        write!(f, " ")?;
         write!(f, "{{")?;
         write_nodes_line_wrapped(&mut indented(f), fields)?;
 
@@ -785,16 +893,22 @@ impl fmt::Display for Enum {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let Enum {
             name,
+            generic_params,
             variants,
             functions,
             pub_qual,
+            doc: _,
         } = self;
 
         if pub_qual.is_some() {
             write!(f, "pub ")?;
         }
 
-        write!(f, "enum {} ", name.kind)?;
+        write!(f, "enum {}", name.kind)?;
+        if !generic_params.kind.is_empty() {
+            write!(f, "<{}>", comma_joined(generic_params.kind.iter()))?;
+        }
+        write!(f, " ")?;
         write!(f, "{{")?;
         write_nodes_line_wrapped(&mut indented(f), variants)?;
 
@@ -883,7 +997,7 @@ impl fmt::Display for Node<Function> {
     }
 }
 
-impl fmt::Display for Function {
+impl fmt::Display for FunctionSignature {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let FunctionSignature {
             pub_,
@@ -892,8 +1006,13 @@ impl fmt::Display for Function {
             generic_params,
             args,
             return_type,
-        } = &self.sig.kind;
+            attributes,
+            doc: _,
+        } = self;
 
+        for attr in attributes {
+            writeln!(f, "#{}", attr.kind)?;
+        }
         if pub_.is_some() {
             write!(f, "pub ")?;
         }
@@ -907,9 +1026,16 @@ impl fmt::Display for Function {
         write!(f, "({})", node_comma_joined(args))?;
 
         if let Some(return_type) = return_type.as_ref() {
-            write!(f, " -> {}", return_type.kind)?;
+            write!(f, " -> {}", return_type.kind)
+        } else {
+            Ok(())
         }
-        write!(f, " {{")?;
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {{", self.sig.kind)?;
         write_nodes_line_wrapped(&mut indented(f), &self.body)?;
         write!(f, "}}")
     }
@@ -1101,6 +1227,7 @@ impl fmt::Display for Expr {
             }
             Expr::Attribute { value, attr } => write!(f, "{}.{}", value.kind, attr.kind),
             Expr::Subscript { value, index } => write!(f, "{}[{}]", value.kind, index.kind),
+            Expr::Try { value } => write!(f, "{}?", value.kind),
             Expr::Call {
                 func,
                 generic_args,
@@ -1131,6 +1258,16 @@ impl fmt::Display for Expr {
     }
 }
 
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(args) = &self.args {
+            write!(f, "({})", node_comma_joined(&args.kind))?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for CallArg {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if let Some(label) = &self.label {
@@ -1296,11 +1433,11 @@ fn double_line_joined(items: &[impl fmt::Display]) -> String {
         .join("\n\n")
 }
 
-trait InfixBindingPower {
+pub(crate) trait InfixBindingPower {
     fn infix_binding_power(&self) -> (u8, u8);
 }
 
-trait PrefixBindingPower {
+pub(crate) trait PrefixBindingPower {
     fn prefix_binding_power(&self) -> u8;
 }
 
@@ -1372,17 +1509,19 @@ fn maybe_fmt_operand_with_parens(op: &impl PrefixBindingPower, expr: &Expr) -> S
     }
 }
 
-fn expr_left_binding_power(expr: &Expr) -> u8 {
+pub(crate) fn expr_left_binding_power(expr: &Expr) -> u8 {
     let max_power = u8::MAX;
 
     match expr {
-        Expr::Ternary { .. } => max_power,
+        // The ternary `if` is parsed like a postfix operator with binding power 35.
+        Expr::Ternary { .. } => 35,
         Expr::BoolOperation { op, .. } => op.kind.infix_binding_power().0,
         Expr::BinOperation { op, .. } => op.kind.infix_binding_power().0,
         Expr::UnaryOperation { op, .. } => op.kind.prefix_binding_power(),
         Expr::CompOperation { op, .. } => op.kind.infix_binding_power().0,
         Expr::Attribute { .. } => max_power,
         Expr::Subscript { .. } => max_power,
+        Expr::Try { .. } => max_power,
         Expr::Call { .. } => max_power,
         Expr::List { .. } => max_power,
         Expr::Repeat { .. } => max_power,
@@ -1396,17 +1535,19 @@ fn expr_left_binding_power(expr: &Expr) -> u8 {
     }
 }
 
-fn expr_right_binding_power(expr: &Expr) -> u8 {
+pub(crate) fn expr_right_binding_power(expr: &Expr) -> u8 {
     let max_power = u8::MAX;
 
     match expr {
-        Expr::Ternary { .. } => max_power,
+        // The ternary `if` is parsed like a postfix operator with binding power 35.
+        Expr::Ternary { .. } => 35,
         Expr::BoolOperation { op, .. } => op.kind.infix_binding_power().1,
         Expr::BinOperation { op, .. } => op.kind.infix_binding_power().1,
         Expr::UnaryOperation { op, .. } => op.kind.prefix_binding_power(),
         Expr::CompOperation { op, .. } => op.kind.infix_binding_power().1,
         Expr::Attribute { .. } => max_power,
         Expr::Subscript { .. } => max_power,
+        Expr::Try { .. } => max_power,
         Expr::Call { .. } => max_power,
         Expr::List { .. } => max_power,
         Expr::Repeat { .. } => max_power,
@@ -0,0 +1,997 @@
+use crate::ast::{expr_left_binding_power, expr_right_binding_power, ConstantDecl};
+use crate::ast::{
+    CallArg, Contract, Enum, Expr, Field, FuncStmt, Function, FunctionSignature, GenericArg,
+    GenericParameter, Impl, InfixBindingPower, Interface, Module, ModuleStmt, Pattern,
+    PrefixBindingPower, Struct, Trait, UnaryOperator,
+};
+use crate::lexer::{Lexer, Token, TokenKind};
+use crate::node::{Node, Span};
+use crate::parse_file;
+use fe_common::diagnostics::{Diagnostic, Label, Severity};
+use fe_common::files::SourceFileId;
+use fe_common::Spanned;
+
+/// Lines that would be longer than this are broken up where possible.
+const MAX_WIDTH: usize = 100;
+const INDENT: usize = 4;
+
+/// Formats a Fe source file.
+///
+/// Comments are kept in place, and so is the grouping of statements, fields
+/// and one-line items into blank line separated paragraphs. Redundant
+/// parentheses are removed, and call arguments that don't fit on a line are
+/// written one per line.
+///
+/// Returns the parser diagnostics if the file can't be parsed, and an error
+/// for each comment that can't be kept in place, i.e. a comment inside an
+/// expression that isn't between call arguments.
+pub fn format_file(file_id: SourceFileId, src: &str) -> Result<String, Vec<Diagnostic>> {
+    let (module, diags) = parse_file(file_id, src);
+    if diags
+        .iter()
+        .any(|diag| matches!(diag.severity, Severity::Error | Severity::Bug))
+    {
+        return Err(diags);
+    }
+
+    let mut formatter = Formatter::new(file_id, src);
+    formatter.module(&module);
+    if !formatter.misplaced_comments.is_empty() {
+        return Err(formatter
+            .misplaced_comments
+            .into_iter()
+            .map(|span| Diagnostic {
+                severity: Severity::Error,
+                message: "can't format a comment inside this expression".into(),
+                labels: vec![Label::primary(span, "comment would be moved")],
+                notes: vec!["comments are only kept in place between call arguments".into()],
+            })
+            .collect());
+    }
+    Ok(formatter.finish())
+}
+
+struct Formatter<'a> {
+    src: &'a str,
+    /// All tokens except for comments and newlines, used to find the braces
+    /// of blocks (node spans don't always include them).
+    tokens: Vec<Token<'a>>,
+    comments: Vec<Token<'a>>,
+    emitted: Vec<bool>,
+    /// Comments inside expressions that couldn't be written in place.
+    misplaced_comments: Vec<Span>,
+    out: String,
+    indent: usize,
+    /// The end of the source that has been formatted so far.
+    last_pos: usize,
+    /// Whether nothing has been written since the beginning of the current
+    /// block (or file).
+    block_start: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(file_id: SourceFileId, src: &'a str) -> Self {
+        let (comments, tokens): (Vec<_>, Vec<_>) = Lexer::with_comments(file_id, src)
+            .filter(|tok| tok.kind != TokenKind::Newline)
+            .partition(|tok| tok.kind == TokenKind::Comment);
+        Formatter {
+            src,
+            tokens,
+            emitted: vec![false; comments.len()],
+            comments,
+            misplaced_comments: vec![],
+            out: String::new(),
+            indent: 0,
+            last_pos: 0,
+            block_start: true,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.leading(self.src.len(), false);
+        let out = self.out.trim_end();
+        if out.is_empty() {
+            String::new()
+        } else {
+            format!("{out}\n")
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        self.out.push_str(&spaces(self.indent));
+        self.out.push_str(text);
+        self.out.push('\n');
+        self.block_start = false;
+    }
+
+    fn blank_line(&mut self) {
+        if !self.block_start && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn has_blank_line(&self, start: usize, end: usize) -> bool {
+        start < end && self.src[start..end].matches('\n').count() > 1
+    }
+
+    fn has_comment_in(&self, start: usize, end: usize) -> bool {
+        self.find_comment(|span| (start..end).contains(&span.start))
+            .is_some()
+    }
+
+    /// Returns the first comment that hasn't been written yet and satisfies
+    /// the predicate.
+    fn find_comment(&self, pred: impl Fn(Span) -> bool) -> Option<usize> {
+        (0..self.comments.len()).find(|idx| !self.emitted[*idx] && pred(self.comments[*idx].span))
+    }
+
+    fn take_comment(&mut self, idx: usize) -> &'a str {
+        self.emitted[idx] = true;
+        self.comments[idx].text.trim_end()
+    }
+
+    /// Writes the comments that start before `pos`, each on its own line.
+    fn comments_before(&mut self, pos: usize, force_blank: bool) -> bool {
+        let mut force_blank = force_blank;
+        while let Some(idx) = self.find_comment(|span| span.start < pos) {
+            let span = self.comments[idx].span;
+            if force_blank || self.has_blank_line(self.last_pos, span.start) {
+                self.blank_line();
+            }
+            force_blank = false;
+            let text = self.take_comment(idx);
+            self.line(text);
+            self.last_pos = self.last_pos.max(span.end);
+        }
+        force_blank
+    }
+
+    /// Prepares the output for a node starting at `pos`: writes the comments
+    /// preceding it and keeps blank lines that separate it from the previous
+    /// node. With `force_blank`, the node (or its leading comments) is always
+    /// separated by a blank line.
+    fn leading(&mut self, pos: usize, force_blank: bool) {
+        let force_blank = self.comments_before(pos, force_blank);
+        if force_blank || self.has_blank_line(self.last_pos, pos) {
+            self.blank_line();
+        }
+    }
+
+    /// Marks the source up to `end` as formatted. A comment that follows on
+    /// the same source line is appended to the last output line.
+    fn trailing(&mut self, end: usize) {
+        self.last_pos = self.last_pos.max(end);
+        let next = self.find_comment(|span| span.start >= end);
+        if let Some(idx) = next {
+            let span = self.comments[idx].span;
+            if !self.src[end..span.start].contains('\n') {
+                let text = self.take_comment(idx);
+                self.out.pop();
+                self.out.push(' ');
+                self.out.push_str(text);
+                self.out.push('\n');
+                self.last_pos = span.end;
+            }
+        }
+    }
+
+    /// Returns the positions of the braces of the first block that starts at
+    /// or after `from`.
+    fn braces(&self, from: usize) -> (usize, usize) {
+        let first = self.tokens.partition_point(|tok| tok.span.start < from);
+        let open = first
+            + self.tokens[first..]
+                .iter()
+                .position(|tok| tok.kind == TokenKind::BraceOpen)
+                .expect("block without `{`");
+
+        let mut depth = 0;
+        for tok in &self.tokens[open..] {
+            match tok.kind {
+                TokenKind::BraceOpen => depth += 1,
+                TokenKind::BraceClose => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return (self.tokens[open].span.start, tok.span.start);
+                    }
+                }
+                _ => {}
+            }
+        }
+        unreachable!("unbalanced braces in parsed source")
+    }
+
+    fn token_kinds_after(&self, pos: usize) -> impl Iterator<Item = TokenKind> + '_ {
+        let first = self.tokens.partition_point(|tok| tok.span.start < pos);
+        self.tokens[first..].iter().map(|tok| tok.kind)
+    }
+
+    /// Writes `{header} {` and the content of the block, and returns the
+    /// position of the closing brace, which is left to the caller.
+    fn open_block(&mut self, header: &str, from: usize, content: impl FnOnce(&mut Self)) -> usize {
+        let (open, close) = self.braces(from);
+        self.line(&format!("{header} {{"));
+        self.trailing(open + 1);
+        self.indent += INDENT;
+        self.block_start = true;
+        content(self);
+        self.comments_before(close, false);
+        self.indent -= INDENT;
+        close
+    }
+
+    /// Writes a block, e.g. the body of a function. Empty blocks are written
+    /// as `{header} {}`.
+    fn block(
+        &mut self,
+        header: &str,
+        from: usize,
+        is_empty: bool,
+        content: impl FnOnce(&mut Self),
+    ) {
+        let (open, close) = self.braces(from);
+        if is_empty && !self.has_comment_in(open, close) {
+            self.line(&format!("{header} {{}}"));
+        } else {
+            self.open_block(header, from, content);
+            self.line("}");
+        }
+        self.trailing(close + 1);
+    }
+
+    fn module(&mut self, module: &Module) {
+        let mut prev: Option<&ModuleStmt> = None;
+        for stmt in &module.body {
+            let start = stmt.span().start;
+            match prev {
+                // Attributes stick to the item they belong to.
+                Some(ModuleStmt::Attribute(_)) => {
+                    self.comments_before(start, false);
+                }
+                Some(prev) => {
+                    let force_blank = is_block_item(prev)
+                        || (is_block_item(stmt) || matches!(stmt, ModuleStmt::Attribute(_)));
+                    self.leading(start, force_blank);
+                }
+                None => self.leading(start, false),
+            }
+            self.module_stmt(stmt);
+            prev = Some(stmt);
+        }
+    }
+
+    fn module_stmt(&mut self, stmt: &ModuleStmt) {
+        match stmt {
+            ModuleStmt::Pragma(node) => self.one_line(&node.kind.to_string(), node.span.end),
+            ModuleStmt::Use(node) => self.one_line(&node.kind.to_string(), node.span.end),
+            ModuleStmt::TypeAlias(node) => self.one_line(&node.kind.to_string(), node.span.end),
+            ModuleStmt::Constant(node) => self.constant(node),
+            ModuleStmt::Contract(node) => self.contract(node),
+            ModuleStmt::Interface(node) => self.interface(node),
+            ModuleStmt::Struct(node) => self.struct_def(node),
+            ModuleStmt::Enum(node) => self.enum_def(node),
+            ModuleStmt::Trait(node) => self.trait_def(node),
+            ModuleStmt::Impl(node) => self.impl_def(node),
+            ModuleStmt::Function(node) => self.function(node),
+            ModuleStmt::Attribute(node) => self.one_line(&format!("#{}", node.kind), node.span.end),
+            ModuleStmt::ParseError(_) => unreachable!("formatting a module with parse errors"),
+        }
+    }
+
+    fn one_line(&mut self, text: &str, end: usize) {
+        self.line(text);
+        self.trailing(end);
+    }
+
+    fn constant(&mut self, node: &Node<ConstantDecl>) {
+        let ConstantDecl {
+            name,
+            typ,
+            value,
+            pub_qual,
+            doc: _,
+        } = &node.kind;
+        let prefix = format!(
+            "{}const {}: {} = ",
+            if pub_qual.is_some() { "pub " } else { "" },
+            name.kind,
+            typ.kind
+        );
+        let value = self.expr(value, self.indent, self.indent + prefix.len());
+        self.one_line(&format!("{prefix}{value}"), node.span.end);
+    }
+
+    fn contract(&mut self, node: &Node<Contract>) {
+        let Contract {
+            name,
+            fields,
+            body,
+            pub_qual,
+            doc: _,
+        } = &node.kind;
+        let header = format!(
+            "{}contract {}",
+            if pub_qual.is_some() { "pub " } else { "" },
+            name.kind
+        );
+        let is_empty = fields.is_empty() && body.is_empty();
+        self.block(&header, name.span.end, is_empty, |f| {
+            f.fields(fields);
+            for (idx, stmt) in body.iter().enumerate() {
+                match stmt {
+                    crate::ast::ContractStmt::Function(function) => {
+                        f.leading(function_start(function), idx > 0 || !fields.is_empty());
+                        f.function(function);
+                    }
+                }
+            }
+        });
+    }
+
+    fn struct_def(&mut self, node: &Node<Struct>) {
+        let Struct {
+            name,
+            generic_params,
+            fields,
+            functions,
+            pub_qual,
+            doc: _,
+        } = &node.kind;
+        let header = format!(
+            "{}struct {}{}",
+            if pub_qual.is_some() { "pub " } else { "" },
+            name.kind,
+            generics(generic_params)
+        );
+        let is_empty = fields.is_empty() && functions.is_empty();
+        self.block(&header, generic_params.span.end, is_empty, |f| {
+            f.fields(fields);
+            f.functions(functions, !fields.is_empty());
+        });
+    }
+
+    fn enum_def(&mut self, node: &Node<Enum>) {
+        let Enum {
+            name,
+            generic_params,
+            variants,
+            functions,
+            pub_qual,
+            doc: _,
+        } = &node.kind;
+        let header = format!(
+            "{}enum {}{}",
+            if pub_qual.is_some() { "pub " } else { "" },
+            name.kind,
+            generics(generic_params)
+        );
+        let is_empty = variants.is_empty() && functions.is_empty();
+        self.block(&header, generic_params.span.end, is_empty, |f| {
+            for variant in variants {
+                f.leading(variant.span.start, false);
+                f.one_line(&variant.kind.to_string(), variant.span.end);
+            }
+            f.functions(functions, !variants.is_empty());
+        });
+    }
+
+    fn interface(&mut self, node: &Node<Interface>) {
+        let Interface {
+            name,
+            functions,
+            pub_qual,
+            doc: _,
+        } = &node.kind;
+        let header = format!(
+            "{}interface {}",
+            if pub_qual.is_some() { "pub " } else { "" },
+            name.kind
+        );
+        self.block(&header, name.span.end, functions.is_empty(), |f| {
+            for sig in functions {
+                f.leading(sig.span.start, false);
+                let sig_text = f.fn_sig(&sig.kind);
+                f.one_line(&format!("{sig_text};"), sig.span.end);
+            }
+        });
+    }
+
+    fn trait_def(&mut self, node: &Node<Trait>) {
+        let Trait {
+            name,
+            functions,
+            pub_qual,
+            doc: _,
+        } = &node.kind;
+        let header = format!(
+            "{}trait {}",
+            if pub_qual.is_some() { "pub " } else { "" },
+            name.kind
+        );
+        self.block(&header, name.span.end, functions.is_empty(), |f| {
+            for sig in functions {
+                f.leading(sig.span.start, false);
+                let sig_text = f.fn_sig(&sig.kind);
+                f.one_line(&format!("{sig_text};"), sig.span.end);
+            }
+        });
+    }
+
+    fn impl_def(&mut self, node: &Node<Impl>) {
+        let Impl {
+            impl_trait,
+            receiver,
+            functions,
+            doc: _,
+        } = &node.kind;
+        let header = format!("impl {} for {}", impl_trait.kind, receiver.kind);
+        self.block(&header, receiver.span.end, functions.is_empty(), |f| {
+            f.functions(functions, false);
+        });
+    }
+
+    fn fields(&mut self, fields: &[Node<Field>]) {
+        for field in fields {
+            let Field {
+                is_pub,
+                is_const,
+                attributes,
+                name,
+                typ,
+                value,
+                doc: _,
+            } = &field.kind;
+            let start = attributes
+                .first()
+                .map_or(field.span.start, |attr| attr.span.start);
+            self.leading(start, false);
+            for attr in attributes {
+                self.line(&format!("#{}", attr.kind));
+            }
+
+            let mut text = format!(
+                "{}{}{}: {}",
+                if *is_pub { "pub " } else { "" },
+                if *is_const { "const " } else { "" },
+                name.kind,
+                typ.kind
+            );
+            if let Some(value) = value {
+                text += " = ";
+                text += &self.expr(value, self.indent, self.indent + text.len());
+            }
+            let end = value
+                .as_ref()
+                .map_or(field.span.end, |value| value.span.end);
+            self.one_line(&text, end);
+        }
+    }
+
+    /// Writes the functions of a contract, struct, enum or impl block, which
+    /// are separated by blank lines.
+    fn functions(&mut self, functions: &[Node<Function>], after_members: bool) {
+        for (idx, function) in functions.iter().enumerate() {
+            self.leading(function_start(function), idx > 0 || after_members);
+            self.function(function);
+        }
+    }
+
+    fn function(&mut self, node: &Node<Function>) {
+        let Function { sig, body } = &node.kind;
+        for attr in &sig.kind.attributes {
+            self.line(&format!("#{}", attr.kind));
+        }
+        let header = self.fn_sig(&sig.kind);
+        self.block(&header, sig.span.end, body.is_empty(), |f| f.stmts(body));
+    }
+
+    fn fn_sig(&self, sig: &FunctionSignature) -> String {
+        let FunctionSignature {
+            pub_,
+            unsafe_,
+            name,
+            generic_params,
+            args,
+            return_type,
+            attributes: _,
+            doc: _,
+        } = sig;
+
+        let mut head = String::new();
+        if pub_.is_some() {
+            head += "pub ";
+        }
+        if unsafe_.is_some() {
+            head += "unsafe ";
+        }
+        head += &format!("fn {}{}", name.kind, generics(generic_params));
+        let tail = return_type
+            .as_ref()
+            .map_or(String::new(), |typ| format!(" -> {}", typ.kind));
+
+        let args_text = comma_joined(args.iter().map(|arg| &arg.kind));
+        let flat = format!("{head}({args_text}){tail}");
+        // Leave room for the ` {` of the function body.
+        if self.indent + flat.len() + 2 <= MAX_WIDTH || args.is_empty() {
+            return flat;
+        }
+
+        let arg_indent = spaces(self.indent + INDENT);
+        let args_text: String = args
+            .iter()
+            .map(|arg| format!("{arg_indent}{},\n", arg.kind))
+            .collect();
+        format!("{head}(\n{args_text}{}){tail}", spaces(self.indent))
+    }
+
+    fn stmts(&mut self, stmts: &[Node<FuncStmt>]) {
+        for stmt in stmts {
+            self.leading(stmt.span.start, false);
+            self.stmt(stmt);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Node<FuncStmt>) {
+        let indent = self.indent;
+        match &stmt.kind {
+            FuncStmt::For { target, iter, body } => {
+                let prefix = format!("for {} in ", target.kind);
+                let iter_text = self.expr(iter, indent, indent + prefix.len());
+                self.block(
+                    &format!("{prefix}{iter_text}"),
+                    iter.span.end,
+                    body.is_empty(),
+                    |f| f.stmts(body),
+                );
+            }
+            FuncStmt::While { test, body } => {
+                let test_text = self.expr(test, indent, indent + "while ".len());
+                self.block(
+                    &format!("while {test_text}"),
+                    test.span.end,
+                    body.is_empty(),
+                    |f| f.stmts(body),
+                );
+            }
+            FuncStmt::If {
+                test,
+                body,
+                or_else,
+            } => self.if_stmt("", test, body, or_else),
+            FuncStmt::Match { expr, arms } => {
+                let expr_text = self.expr(expr, indent, indent + "match ".len());
+                self.block(
+                    &format!("match {expr_text}"),
+                    expr.span.end,
+                    arms.is_empty(),
+                    |f| {
+                        for arm in arms {
+                            f.leading(arm.span.start, false);
+                            let header = format!("{} =>", pattern(&arm.kind.pat.kind));
+                            let body = &arm.kind.body;
+                            f.block(&header, arm.kind.pat.span.end, body.is_empty(), |f| {
+                                f.stmts(body)
+                            });
+                        }
+                    },
+                );
+            }
+            FuncStmt::Unsafe(body) => self.block("unsafe", stmt.span.start, body.is_empty(), |f| {
+                f.stmts(body)
+            }),
+            _ => {
+                let text = self.simple_stmt(&stmt.kind);
+                self.one_line(&text, stmt.span.end);
+            }
+        }
+    }
+
+    fn if_stmt(
+        &mut self,
+        prefix: &str,
+        test: &Node<Expr>,
+        body: &[Node<FuncStmt>],
+        or_else: &[Node<FuncStmt>],
+    ) {
+        let indent = self.indent;
+        let header = format!("{prefix}if ");
+        let test_text = self.expr(test, indent, indent + header.len());
+        let header = format!("{header}{test_text}");
+        // An empty `else {}` isn't in the AST, so look for the `else` token.
+        let (_, body_close) = self.braces(test.span.end);
+        let has_else = self.token_kinds_after(body_close + 1).next() == Some(TokenKind::Else);
+        if !has_else {
+            return self.block(&header, test.span.end, body.is_empty(), |f| f.stmts(body));
+        }
+
+        let close = self.open_block(&header, test.span.end, |f| f.stmts(body));
+        let is_else_if = self
+            .token_kinds_after(close + 1)
+            .take(2)
+            .eq([TokenKind::Else, TokenKind::If]);
+        match or_else {
+            [Node {
+                kind:
+                    FuncStmt::If {
+                        test,
+                        body,
+                        or_else,
+                    },
+                ..
+            }] if is_else_if => self.if_stmt("} else ", test, body, or_else),
+            _ => {
+                let else_close = self.open_block("} else", close + 1, |f| f.stmts(or_else));
+                self.line("}");
+                self.trailing(else_close + 1);
+            }
+        }
+    }
+
+    fn simple_stmt(&mut self, stmt: &FuncStmt) -> String {
+        let indent = self.indent;
+        let with_expr = |f: &mut Self, prefix: String, expr: &Node<Expr>| {
+            let expr = f.expr(expr, indent, indent + prefix.len());
+            format!("{prefix}{expr}")
+        };
+
+        match stmt {
+            FuncStmt::Return { value: None } => "return".into(),
+            FuncStmt::Return { value: Some(value) } => with_expr(self, "return ".into(), value),
+            FuncStmt::VarDecl {
+                mut_,
+                target,
+                typ,
+                value,
+            } => {
+                let decl = format!(
+                    "let {}{}: {}",
+                    if mut_.is_some() { "mut " } else { "" },
+                    target.kind,
+                    typ.kind
+                );
+                match value {
+                    Some(value) => with_expr(self, format!("{decl} = "), value),
+                    None => decl,
+                }
+            }
+            FuncStmt::ConstantDecl { name, typ, value } => {
+                with_expr(self, format!("const {}: {} = ", name.kind, typ.kind), value)
+            }
+            FuncStmt::Assign { target, value } => {
+                let target = self.expr(target, indent, indent);
+                with_expr(self, format!("{target} = "), value)
+            }
+            FuncStmt::AugAssign { target, op, value } => {
+                let target = self.expr(target, indent, indent);
+                with_expr(self, format!("{target} {}= ", op.kind), value)
+            }
+            FuncStmt::Assert { test, msg } => {
+                let test = with_expr(self, "assert ".into(), test);
+                match msg {
+                    Some(msg) => with_expr(self, format!("{test}, "), msg),
+                    None => test,
+                }
+            }
+            FuncStmt::Expr { value } => with_expr(self, String::new(), value),
+            FuncStmt::Break => "break".into(),
+            FuncStmt::Continue => "continue".into(),
+            FuncStmt::Revert { error: None } => "revert".into(),
+            FuncStmt::Revert { error: Some(error) } => with_expr(self, "revert ".into(), error),
+            FuncStmt::For { .. }
+            | FuncStmt::While { .. }
+            | FuncStmt::If { .. }
+            | FuncStmt::Match { .. }
+            | FuncStmt::Unsafe(_) => unreachable!("block statement"),
+        }
+    }
+
+    /// Formats an expression that starts at column `col` of a line indented
+    /// by `indent`. Calls are broken up if the expression doesn't fit on the
+    /// line, or if comments appear between their arguments.
+    fn expr(&mut self, expr: &Node<Expr>, indent: usize, col: usize) -> String {
+        let flat = self.expr_inner(expr, indent, col, false);
+        if col + flat.len() <= MAX_WIDTH && !self.has_comment_in(expr.span.start, expr.span.end) {
+            return flat;
+        }
+
+        let text = self.expr_inner(expr, indent, col, true);
+        // Any comment left inside the expression would end up after the
+        // statement.
+        while let Some(idx) =
+            self.find_comment(|span| (expr.span.start..expr.span.end).contains(&span.start))
+        {
+            self.emitted[idx] = true;
+            self.misplaced_comments.push(self.comments[idx].span);
+        }
+        text
+    }
+
+    fn expr_inner(&mut self, expr: &Node<Expr>, indent: usize, col: usize, wrap: bool) -> String {
+        let mut sub = |f: &mut Self, expr: &Node<Expr>, col: usize, parens: bool| {
+            let (col, open, close) = if parens {
+                (col + 1, "(", ")")
+            } else {
+                (col, "", "")
+            };
+            let text = if wrap {
+                f.expr(expr, indent, col)
+            } else {
+                f.expr_inner(expr, indent, col, false)
+            };
+            format!("{open}{text}{close}")
+        };
+
+        match &expr.kind {
+            Expr::Ternary {
+                if_expr,
+                test,
+                else_expr,
+            } => {
+                let if_text = sub(
+                    self,
+                    if_expr,
+                    col,
+                    matches!(if_expr.kind, Expr::Ternary { .. }),
+                );
+                let test_text = sub(self, test, end_col(col, &if_text) + 4, false);
+                let prefix = format!("{if_text} if {test_text} else ");
+                let else_text = sub(self, else_expr, end_col(col, &prefix), false);
+                format!("{prefix}{else_text}")
+            }
+            Expr::BoolOperation { left, op, right } => {
+                self.infix(left, &op.kind, &op.kind.to_string(), right, col, &mut sub)
+            }
+            Expr::BinOperation { left, op, right } => {
+                self.infix(left, &op.kind, &op.kind.to_string(), right, col, &mut sub)
+            }
+            Expr::CompOperation { left, op, right } => {
+                self.infix(left, &op.kind, &op.kind.to_string(), right, col, &mut sub)
+            }
+            Expr::UnaryOperation { op, operand } => {
+                let op_text = if op.kind == UnaryOperator::Not {
+                    "not ".to_string()
+                } else {
+                    op.kind.to_string()
+                };
+                let parens =
+                    op.kind.prefix_binding_power() > expr_left_binding_power(&operand.kind);
+                let operand = sub(self, operand, col + op_text.len(), parens);
+                format!("{op_text}{operand}")
+            }
+            Expr::Attribute { value, attr } => {
+                let value = sub(self, value, col, is_compound(&value.kind));
+                format!("{value}.{}", attr.kind)
+            }
+            Expr::Subscript { value, index } => {
+                let value = sub(self, value, col, is_compound(&value.kind));
+                let index = sub(self, index, end_col(col, &value) + 1, false);
+                format!("{value}[{index}]")
+            }
+            Expr::Try { value } => {
+                let value = sub(self, value, col, is_compound(&value.kind));
+                format!("{value}?")
+            }
+            Expr::Call {
+                func,
+                generic_args,
+                args,
+            } => {
+                let mut head = sub(self, func, col, is_compound(&func.kind));
+                if let Some(generic_args) = generic_args {
+                    head += &format!("<{}>", comma_joined(generic_args.kind.iter()));
+                }
+                if wrap && !args.kind.is_empty() {
+                    let args = self.wrapped_call_args(args, indent);
+                    format!("{head}({args})")
+                } else {
+                    let args = args
+                        .kind
+                        .iter()
+                        .map(|arg| self.call_arg(&arg.kind, indent, col, false))
+                        .collect::<Vec<_>>();
+                    format!("{head}({})", args.join(", "))
+                }
+            }
+            Expr::List { elts } => {
+                let elts = elts
+                    .iter()
+                    .map(|elt| sub(self, elt, col, false))
+                    .collect::<Vec<_>>();
+                format!("[{}]", elts.join(", "))
+            }
+            Expr::Repeat { value, len } => {
+                let value = sub(self, value, col + 1, false);
+                let len = match &len.kind {
+                    // The braces around the length are optional.
+                    GenericArg::ConstExpr(expr) if expr.span == len.span => {
+                        sub(self, expr, col, false)
+                    }
+                    len => len.to_string(),
+                };
+                format!("[{value}; {len}]")
+            }
+            Expr::Tuple { elts } => {
+                let elts = elts
+                    .iter()
+                    .map(|elt| sub(self, elt, col, false))
+                    .collect::<Vec<_>>();
+                if elts.len() == 1 {
+                    format!("({},)", elts[0])
+                } else {
+                    format!("({})", elts.join(", "))
+                }
+            }
+            // Keep string literals as written, so that escapes are kept.
+            Expr::Str(_) => self.src[expr.span.start..expr.span.end].to_string(),
+            Expr::Bool(_) | Expr::Name(_) | Expr::Path(_) | Expr::Num(_) | Expr::Unit => {
+                expr.kind.to_string()
+            }
+        }
+    }
+
+    fn infix<F>(
+        &mut self,
+        left: &Node<Expr>,
+        op: &impl InfixBindingPower,
+        op_text: &str,
+        right: &Node<Expr>,
+        col: usize,
+        sub: &mut F,
+    ) -> String
+    where
+        F: FnMut(&mut Self, &Node<Expr>, usize, bool) -> String,
+    {
+        let (left_bp, right_bp) = op.infix_binding_power();
+        let left_parens = expr_right_binding_power(&left.kind) < left_bp;
+        let right_parens = right_bp > expr_left_binding_power(&right.kind);
+        let left = sub(self, left, col, left_parens);
+        let prefix = format!("{left} {op_text} ");
+        let right = sub(self, right, end_col(col, &prefix), right_parens);
+        format!("{prefix}{right}")
+    }
+
+    fn call_arg(&mut self, arg: &CallArg, indent: usize, col: usize, wrap: bool) -> String {
+        let label = arg
+            .label
+            .as_ref()
+            .map_or(String::new(), |label| format!("{}: ", label.kind));
+        let col = col + label.len();
+        let value = if wrap {
+            self.expr(&arg.value, indent, col)
+        } else {
+            self.expr_inner(&arg.value, indent, col, false)
+        };
+        format!("{label}{value}")
+    }
+
+    /// Formats call arguments one per line, including the comments between
+    /// them.
+    fn wrapped_call_args(&mut self, args: &Node<Vec<Node<CallArg>>>, indent: usize) -> String {
+        let arg_indent = spaces(indent + INDENT);
+        let mut text = "\n".to_string();
+        for arg in &args.kind {
+            self.comment_lines(&mut text, &arg_indent, args.span.start, arg.span.start);
+
+            let arg_text = self.call_arg(&arg.kind, indent + INDENT, indent + INDENT, true);
+            text += &format!("{arg_indent}{arg_text},");
+            let next = self.find_comment(|span| span.start >= arg.span.end);
+            if let Some(idx) = next {
+                let span = self.comments[idx].span;
+                if span.start < args.span.end && !self.src[arg.span.end..span.start].contains('\n')
+                {
+                    text += " ";
+                    text += self.take_comment(idx);
+                }
+            }
+            text += "\n";
+        }
+        self.comment_lines(&mut text, &arg_indent, args.span.start, args.span.end);
+        text + &spaces(indent)
+    }
+
+    fn comment_lines(&mut self, text: &mut String, indent: &str, start: usize, end: usize) {
+        while let Some(idx) = self.find_comment(|span| (start..end).contains(&span.start)) {
+            *text += indent;
+            *text += self.take_comment(idx);
+            *text += "\n";
+        }
+    }
+}
+
+fn is_block_item(stmt: &ModuleStmt) -> bool {
+    matches!(
+        stmt,
+        ModuleStmt::Contract(_)
+            | ModuleStmt::Interface(_)
+            | ModuleStmt::Struct(_)
+            | ModuleStmt::Enum(_)
+            | ModuleStmt::Trait(_)
+            | ModuleStmt::Impl(_)
+            | ModuleStmt::Function(_)
+    )
+}
+
+/// The start of a function, including the attributes that precede it.
+fn function_start(function: &Node<Function>) -> usize {
+    function
+        .kind
+        .sig
+        .kind
+        .attributes
+        .first()
+        .map_or(function.span.start, |attr| attr.span.start)
+}
+
+/// Whether the expression has to be parenthesized when it's the value of an
+/// attribute, subscript or call expression.
+fn is_compound(expr: &Expr) -> bool {
+    expr_right_binding_power(expr) < u8::MAX
+}
+
+fn pattern(pat: &Pattern) -> String {
+    match pat {
+        Pattern::WildCard => "_".into(),
+        Pattern::Rest => "..".into(),
+        Pattern::Literal(lit) => lit.kind.to_string(),
+        Pattern::Path(path) => path.kind.to_string(),
+        Pattern::PathTuple(path, elts) => {
+            let elts = elts.iter().map(|elt| pattern(&elt.kind));
+            format!("{}({})", path.kind, comma_joined(elts))
+        }
+        Pattern::Tuple(elts) => {
+            let elts = elts.iter().map(|elt| pattern(&elt.kind));
+            format!("({})", comma_joined(elts))
+        }
+        Pattern::PathStruct {
+            path,
+            fields,
+            has_rest,
+        } => {
+            let mut fields = fields
+                .iter()
+                .map(|(name, pat)| format!("{}: {}", name.kind, pattern(&pat.kind)))
+                .collect::<Vec<_>>();
+            if *has_rest {
+                fields.push("..".into());
+            }
+            if fields.is_empty() {
+                format!("{} {{}}", path.kind)
+            } else {
+                format!("{} {{ {} }}", path.kind, fields.join(", "))
+            }
+        }
+        Pattern::Or(pats) => pats
+            .iter()
+            .map(|pat| pattern(&pat.kind))
+            .collect::<Vec<_>>()
+            .join(" | "),
+    }
+}
+
+/// The `<...>` generic parameter list of an item, if it has one.
+fn generics(params: &Node<Vec<GenericParameter>>) -> String {
+    if params.kind.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", comma_joined(params.kind.iter()))
+    }
+}
+
+fn comma_joined<T: std::fmt::Display>(items: impl Iterator<Item = T>) -> String {
+    items
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn spaces(count: usize) -> String {
+    " ".repeat(count)
+}
+
+/// The column at which a line ends after `text` has been written at `col`.
+fn end_col(col: usize, text: &str) -> usize {
+    match text.rfind('\n') {
+        Some(idx) => text.len() - idx - 1,
+        None => col + text.len(),
+    }
+}
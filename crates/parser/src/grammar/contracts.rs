@@ -1,9 +1,10 @@
-use super::functions::parse_fn_def;
+use super::functions::{parse_fn_def, parse_fn_sig};
 use super::types::{parse_field, parse_opt_qualifier};
 
-use crate::ast::{Contract, ContractStmt};
+use crate::ast::{Contract, ContractStmt, Interface};
 use crate::node::{Node, Span};
 use crate::{ParseFailed, ParseResult, Parser, TokenKind};
+use smol_str::SmolStr;
 
 // Rule: all "statement" level parse functions consume their trailing
 // newline(s), either directly or via a function they call.
@@ -33,6 +34,7 @@ pub fn parse_contract_def(
 
     loop {
         par.eat_newlines();
+        let attributes = parse_fn_attributes(par)?;
         let mut pub_qual = parse_opt_qualifier(par, TokenKind::Pub);
         let const_qual = parse_opt_qualifier(par, TokenKind::Const);
         if pub_qual.is_none() && const_qual.is_some() && par.peek() == Some(TokenKind::Pub) {
@@ -45,6 +47,9 @@ pub fn parse_contract_def(
 
         match par.peek_or_err()? {
             TokenKind::Name => {
+                if let Some(attr) = attributes.first() {
+                    par.error(attr.span, "attributes can't be applied to contract fields");
+                }
                 let field = parse_field(par, vec![], pub_qual, const_qual)?;
                 if !defs.is_empty() {
                     par.error(
@@ -61,9 +66,11 @@ pub fn parse_contract_def(
                         "`const` qualifier can't be used with function definitions",
                     );
                 }
-                defs.push(ContractStmt::Function(parse_fn_def(par, pub_qual)?));
+                let mut def = parse_fn_def(par, pub_qual)?;
+                def.kind.sig.kind.attributes = attributes;
+                defs.push(ContractStmt::Function(def));
             }
-            TokenKind::BraceClose => {
+            TokenKind::BraceClose if attributes.is_empty() => {
                 span += par.next()?.span;
                 break;
             }
@@ -85,6 +92,76 @@ pub fn parse_contract_def(
             fields,
             body: defs,
             pub_qual: contract_pub_qual,
+            doc: par.doc_comment(contract_tok.span + contract_pub_qual),
+        },
+        span,
+    ))
+}
+
+/// Parse the attributes that precede a contract function, one per line,
+/// like `#view`.
+fn parse_fn_attributes(par: &mut Parser) -> ParseResult<Vec<Node<SmolStr>>> {
+    let mut attributes = vec![];
+    while let Some(attr) = par.optional(TokenKind::Hash) {
+        let attr_name = par.expect_with_notes(TokenKind::Name, "failed to parse attribute definition", |_|
+            vec!["Note: an attribute name must start with a letter or underscore, and contain letters, numbers, or underscores".into()])?;
+        attributes.push(Node::new(attr_name.text.into(), attr.span + attr_name.span));
+        par.eat_newlines();
+    }
+    Ok(attributes)
+}
+
+/// Parse an interface definition.
+/// # Panics
+/// Panics if the next token isn't `interface`.
+pub fn parse_interface_def(
+    par: &mut Parser,
+    interface_pub_qual: Option<Span>,
+) -> ParseResult<Node<Interface>> {
+    let interface_tok = par.assert(TokenKind::Interface);
+    let interface_name = par.expect_with_notes(
+        TokenKind::Name,
+        "failed to parse interface definition",
+        |_| vec!["Note: `interface` must be followed by a name, which must start with a letter and contain only letters, numbers, or underscores".into()],
+    )?;
+
+    let mut span = interface_tok.span + interface_name.span;
+    par.enter_block(span, "interface definition")?;
+
+    let mut functions = vec![];
+    loop {
+        par.eat_newlines();
+        match par.peek_or_err()? {
+            TokenKind::Fn | TokenKind::Unsafe => {
+                functions.push(parse_fn_sig(par, None)?);
+                par.expect_with_notes(
+                    TokenKind::Semi,
+                    "failed to parse interface definition",
+                    |_| vec!["Note: interface functions must appear without body and followed by a semicolon.".into()],
+                )?;
+            }
+            TokenKind::BraceClose => {
+                span += par.next()?.span;
+                break;
+            }
+            _ => {
+                let tok = par.next()?;
+                par.unexpected_token_error(
+                    &tok,
+                    "failed to parse interface definition body",
+                    vec!["Note: an interface can only contain function signatures, like `fn balance(self) -> u256;`".into()],
+                );
+                return Err(ParseFailed);
+            }
+        };
+    }
+
+    Ok(Node::new(
+        Interface {
+            name: Node::new(interface_name.text.into(), interface_name.span),
+            functions,
+            pub_qual: interface_pub_qual,
+            doc: par.doc_comment(interface_tok.span + interface_pub_qual),
         },
         span,
     ))
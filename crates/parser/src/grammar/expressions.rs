@@ -55,6 +55,16 @@ pub fn parse_expr_with_min_bp(par: &mut Parser, min_bp: u8) -> ParseResult<Node<
                         span,
                     )
                 }
+                TokenKind::Question => {
+                    let question = par.next()?;
+                    let span = expr_head.span + question.span;
+                    Node::new(
+                        Expr::Try {
+                            value: Box::new(expr_head),
+                        },
+                        span,
+                    )
+                }
                 TokenKind::If => {
                     par.next()?;
                     let test = parse_expr(par)?;
@@ -285,14 +295,13 @@ fn infix_binding_power(op: TokenKind) -> Option<(u8, u8)> {
 }
 
 /// Specifies how tightly a postfix operator binds to its operand.
-/// We don't have any "real" postfix operators (like `?` in rust),
-/// but we treat `[`, `(`, and ternary `if` as though they're postfix
-/// operators.
+/// The only "real" postfix operator is `?`, but we treat `[`, `(`, and
+/// ternary `if` as though they're postfix operators.
 fn postfix_binding_power(op: TokenKind) -> Option<u8> {
     use TokenKind::*;
     match op {
         If => Some(35), // ternary
-        BracketOpen | ParenOpen => Some(150),
+        BracketOpen | ParenOpen | Question => Some(150),
         _ => None,
     }
 }
@@ -96,6 +96,8 @@ pub fn parse_fn_sig(
             args,
             generic_params,
             return_type,
+            attributes: vec![],
+            doc: par.doc_comment(span),
         },
         span,
     ))
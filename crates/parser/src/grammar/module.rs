@@ -1,11 +1,14 @@
-use super::expressions::parse_expr;
+use super::expressions::{parse_call_args, parse_expr};
 use super::functions::parse_fn_def;
 use super::types::{
     parse_impl_def, parse_path_tail, parse_struct_def, parse_trait_def, parse_type_alias,
     parse_type_desc,
 };
-use super::{contracts::parse_contract_def, types::parse_enum_def};
-use crate::ast::{ConstantDecl, Module, ModuleStmt, Pragma, Use, UseTree};
+use super::{
+    contracts::{parse_contract_def, parse_interface_def},
+    types::parse_enum_def,
+};
+use crate::ast::{Attribute, ConstantDecl, Module, ModuleStmt, Pragma, Use, UseTree};
 use crate::node::{Node, Span};
 use crate::{Label, ParseFailed, ParseResult, Parser, TokenKind};
 
@@ -42,6 +45,7 @@ pub fn parse_module_stmt(par: &mut Parser) -> ParseResult<ModuleStmt> {
         TokenKind::Pragma => ModuleStmt::Pragma(parse_pragma(par)?),
         TokenKind::Use => ModuleStmt::Use(parse_use(par)?),
         TokenKind::Contract => ModuleStmt::Contract(parse_contract_def(par, None)?),
+        TokenKind::Interface => ModuleStmt::Interface(parse_interface_def(par, None)?),
         TokenKind::Struct => ModuleStmt::Struct(parse_struct_def(par, None)?),
         TokenKind::Enum => ModuleStmt::Enum(parse_enum_def(par, None)?),
         TokenKind::Trait => ModuleStmt::Trait(parse_trait_def(par, None)?),
@@ -62,6 +66,9 @@ pub fn parse_module_stmt(par: &mut Parser) -> ParseResult<ModuleStmt> {
                 TokenKind::Contract => {
                     ModuleStmt::Contract(parse_contract_def(par, Some(pub_span))?)
                 }
+                TokenKind::Interface => {
+                    ModuleStmt::Interface(parse_interface_def(par, Some(pub_span))?)
+                }
                 _ => {
                     let tok = par.next()?;
                     par.unexpected_token_error(
@@ -78,7 +85,19 @@ pub fn parse_module_stmt(par: &mut Parser) -> ParseResult<ModuleStmt> {
             let attr = par.expect(TokenKind::Hash, "expected `#`")?;
             let attr_name = par.expect_with_notes(TokenKind::Name, "failed to parse attribute definition", |_|
                 vec!["Note: an attribute name must start with a letter or underscore, and contain letters, numbers, or underscores".into()])?;
-            ModuleStmt::Attribute(Node::new(attr_name.text.into(), attr.span + attr_name.span))
+            let args = if par.peek() == Some(TokenKind::ParenOpen) {
+                Some(parse_call_args(par)?)
+            } else {
+                None
+            };
+            let span = attr.span + attr_name.span + args.as_ref();
+            ModuleStmt::Attribute(Node::new(
+                Attribute {
+                    name: attr_name.text.into(),
+                    args,
+                },
+                span,
+            ))
         }
         _ => {
             let tok = par.next()?;
@@ -134,6 +153,7 @@ pub fn parse_constant(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult<N
             typ,
             value: exp,
             pub_qual,
+            doc: par.doc_comment(const_tok.span + pub_qual),
         },
         span,
     ))
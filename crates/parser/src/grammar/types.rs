@@ -2,7 +2,7 @@ use crate::ast::{
     self, Enum, Field, GenericArg, Impl, Path, Trait, TypeAlias, TypeDesc, Variant, VariantKind,
 };
 use crate::grammar::expressions::parse_expr;
-use crate::grammar::functions::{parse_fn_def, parse_fn_sig};
+use crate::grammar::functions::{parse_fn_def, parse_fn_sig, parse_generic_params};
 use crate::node::{Node, Span};
 use crate::Token;
 use crate::{ParseFailed, ParseResult, Parser, TokenKind};
@@ -23,7 +23,13 @@ pub fn parse_struct_def(
         vec!["Note: a struct name must start with a letter or underscore, and contain letters, numbers, or underscores".into()]
     })?;
 
-    let mut span = struct_tok.span + name.span;
+    let generic_params = if par.peek() == Some(TokenKind::Lt) {
+        parse_generic_params(par)?
+    } else {
+        Node::new(vec![], name.span)
+    };
+
+    let mut span = struct_tok.span + name.span + generic_params.span;
     let mut fields = vec![];
     let mut functions = vec![];
     par.enter_block(span, "struct body must start with `{`")?;
@@ -71,9 +77,11 @@ pub fn parse_struct_def(
     Ok(Node::new(
         ast::Struct {
             name: name.into(),
+            generic_params,
             fields,
             functions,
             pub_qual,
+            doc: par.doc_comment(struct_tok.span + pub_qual),
         },
         span,
     ))
@@ -90,7 +98,13 @@ pub fn parse_enum_def(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult<N
         |_| vec!["Note: `enum` must be followed by a name, which must start with a letter and contain only letters, numbers, or underscores".into()],
     )?;
 
-    let mut span = enum_tok.span + name.span;
+    let generic_params = if par.peek() == Some(TokenKind::Lt) {
+        parse_generic_params(par)?
+    } else {
+        Node::new(vec![], name.span)
+    };
+
+    let mut span = enum_tok.span + name.span + generic_params.span;
     let mut variants = vec![];
     let mut functions = vec![];
 
@@ -145,9 +159,11 @@ pub fn parse_enum_def(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult<N
     Ok(Node::new(
         ast::Enum {
             name: name.into(),
+            generic_params,
             variants,
             functions,
             pub_qual,
+            doc: par.doc_comment(enum_tok.span + pub_qual),
         },
         span,
     ))
@@ -200,6 +216,7 @@ pub fn parse_trait_def(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult<
             name: Node::new(trait_name.text.into(), trait_name.span),
             functions,
             pub_qual,
+            doc: par.doc_comment(span),
         },
         span,
     ))
@@ -255,6 +272,7 @@ pub fn parse_impl_def(par: &mut Parser) -> ParseResult<Node<Impl>> {
             impl_trait: Node::new(trait_name.text.into(), trait_name.span),
             receiver,
             functions,
+            doc: par.doc_comment(header_span),
         },
         header_span,
     ))
@@ -280,6 +298,7 @@ pub fn parse_type_alias(par: &mut Parser, pub_qual: Option<Span>) -> ParseResult
             name: name.into(),
             typ,
             pub_qual,
+            doc: par.doc_comment(span),
         },
         span,
     ))
@@ -336,6 +355,7 @@ pub fn parse_field(
             name: name.into(),
             typ,
             value,
+            doc: par.doc_comment(span),
         },
         span,
     ))
@@ -389,6 +409,7 @@ pub fn parse_variant(par: &mut Parser) -> ParseResult<Node<Variant>> {
         Variant {
             name: name.into(),
             kind,
+            doc: par.doc_comment(span),
         },
         span,
     ))
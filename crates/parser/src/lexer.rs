@@ -8,6 +8,9 @@ pub use token::{Token, TokenKind};
 pub struct Lexer<'a> {
     file_id: SourceFileId,
     inner: logos::Lexer<'a, TokenKind>,
+    keep_comments: bool,
+    /// The spans of the `///` doc comments that have been skipped so far.
+    doc_comments: Vec<Span>,
 }
 
 impl<'a> Lexer<'a> {
@@ -16,6 +19,16 @@ impl<'a> Lexer<'a> {
         Lexer {
             file_id,
             inner: TokenKind::lexer(src),
+            keep_comments: false,
+            doc_comments: vec![],
+        }
+    }
+
+    /// Create a new lexer that also returns `Comment` tokens.
+    pub fn with_comments(file_id: SourceFileId, src: &'a str) -> Lexer {
+        Lexer {
+            keep_comments: true,
+            ..Lexer::new(file_id, src)
         }
     }
 
@@ -23,13 +36,27 @@ impl<'a> Lexer<'a> {
     pub fn source(&self) -> &'a str {
         self.inner.source()
     }
+
+    /// The spans of the `///` doc comments that have been skipped so far, in
+    /// source order.
+    pub fn doc_comments(&self) -> &[Span] {
+        &self.doc_comments
+    }
 }
 
 impl<'a> Iterator for Lexer<'a> {
     type Item = Token<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let kind = self.inner.next()?;
+        let mut kind = self.inner.next()?;
+        while kind == TokenKind::Comment && !self.keep_comments {
+            if is_doc_comment(self.inner.slice()) {
+                let span = self.inner.span();
+                self.doc_comments
+                    .push(Span::new(self.file_id, span.start, span.end));
+            }
+            kind = self.inner.next()?;
+        }
         let text = self.inner.slice();
         let span = self.inner.span();
         Some(Token {
@@ -44,6 +71,11 @@ impl<'a> Iterator for Lexer<'a> {
     }
 }
 
+/// Returns `true` for `///` comments, but not for `////` separator lines.
+pub fn is_doc_comment(text: &str) -> bool {
+    text.starts_with("///") && !text.starts_with("////")
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lexer::{Lexer, TokenKind};
@@ -99,4 +131,24 @@ mod tests {
             &[Newline, Contract, Newline, Name, Newline],
         );
     }
+
+    #[test]
+    fn comments() {
+        let input = "// hi\nx = 1 // mom!\n/// doc";
+        let lex = Lexer::with_comments(SourceFileId::dummy_file(), input);
+        let actual = lex.map(|t| (t.kind, t.text)).collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            [
+                (Comment, "// hi"),
+                (Newline, "\n"),
+                (Name, "x"),
+                (Eq, "="),
+                (Int, "1"),
+                (Comment, "// mom!"),
+                (Newline, "\n"),
+                (Comment, "/// doc"),
+            ]
+        );
+    }
 }
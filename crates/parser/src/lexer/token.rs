@@ -27,13 +27,15 @@ impl<'a> Add<&Token<'a>> for Span {
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Logos)]
 pub enum TokenKind {
-    // Ignoring comments and spaces/tabs for now.
-    // If we implement an auto-formatting tool, we'll probably want to change this.
-    #[regex(r"//[^\n]*", logos::skip)]
     #[regex("[ \t]+", logos::skip)]
     #[error]
     Error,
 
+    // Comments are skipped by the `Lexer`, unless it was created with
+    // `Lexer::with_comments`.
+    #[regex(r"//[^\n]*")]
+    Comment,
+
     #[regex(r"\n[ \t]*")]
     Newline,
 
@@ -77,6 +79,8 @@ pub enum TokenKind {
     If,
     #[token("match")]
     Match,
+    #[token("interface")]
+    Interface,
     #[token("impl")]
     Impl,
     #[token("pragma")]
@@ -143,6 +147,8 @@ pub enum TokenKind {
     Comma,
     #[token("#")]
     Hash,
+    #[token("?")]
+    Question,
     #[token(";")]
     Semi,
     #[token("+")]
@@ -223,6 +229,7 @@ impl TokenKind {
         use TokenKind::*;
         match self {
             Newline => "a newline",
+            Comment => "a comment",
             Name => "a name",
             Int => "a number",
             Hex => "a hexadecimal number",
@@ -245,6 +252,7 @@ impl TokenKind {
             If => "keyword `if`",
             Match => "keyword `match`",
             Impl => "keyword `impl`",
+            Interface => "keyword `interface`",
             Pragma => "keyword `pragma`",
             For => "keyword `for`",
             Pub => "keyword `pub`",
@@ -274,6 +282,7 @@ impl TokenKind {
             ColonColon => "symbol `::`",
             Comma => "symbol `,`",
             Hash => "symbol `#`",
+            Question => "symbol `?`",
             Semi => "symbol `;`",
             Plus => "symbol `+`",
             Minus => "symbol `-`",
@@ -1,4 +1,6 @@
 pub mod ast;
+pub mod formatter;
+pub use formatter::format_file;
 pub mod grammar;
 pub mod lexer;
 pub use lexer::{Token, TokenKind};
@@ -4,6 +4,7 @@ use fe_common::files::SourceFileId;
 
 use crate::lexer::{Lexer, Token, TokenKind};
 use crate::node::Span;
+use smol_str::SmolStr;
 use std::{error, fmt};
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
@@ -306,6 +307,47 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Returns the text of the `///` doc comment on the lines directly above
+    /// the item that starts at `item_start`, without the leading slashes.
+    /// Attribute lines (e.g. `#test`) may appear between the doc comment and
+    /// the item.
+    pub fn doc_comment(&self, item_start: Span) -> Option<SmolStr> {
+        let src = self.lexer.source();
+        let docs = self.lexer.doc_comments();
+        let mut idx = docs.partition_point(|span| span.end <= item_start.start);
+        let mut next_start = item_start.start;
+        let mut lines = vec![];
+        while idx > 0 {
+            let doc = docs[idx - 1];
+            let gap = &src[doc.end..next_start];
+            let is_adjacent = if lines.is_empty() {
+                let mut gap_lines = gap.split('\n');
+                let last = gap_lines.next_back().unwrap_or_default();
+                gap.contains('\n')
+                    && last.trim().is_empty()
+                    && gap_lines
+                        .skip(1)
+                        .all(|line| line.trim_start().starts_with('#'))
+            } else {
+                gap.trim().is_empty() && gap.matches('\n').count() == 1
+            };
+            if !is_adjacent {
+                break;
+            }
+
+            let text = &src[doc.start + 3..doc.end];
+            lines.push(text.strip_prefix(' ').unwrap_or(text).trim_end());
+            next_start = doc.start;
+            idx -= 1;
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(lines.join("\n").into())
+    }
+
     /// Emit an error diagnostic, but don't stop parsing
     pub fn error<S: Into<String>>(&mut self, span: Span, message: S) {
         self.diagnostics.push(Diagnostic {
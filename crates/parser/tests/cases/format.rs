@@ -0,0 +1,236 @@
+use fe_common::db::TestDb;
+use fe_common::diagnostics::diagnostics_string;
+use fe_common::SourceFileId;
+use fe_parser::ast::Module;
+use fe_parser::lexer::Lexer;
+use fe_parser::{format_file, parse_file, TokenKind};
+use pretty_assertions::assert_eq;
+
+fn format(src: &str) -> String {
+    let mut db = TestDb::default();
+    let id = SourceFileId::new_local(&mut db, "test.fe", src.into());
+    format_file(id, src).expect("parse error")
+}
+
+/// The debug representation of the AST, without node ids and spans.
+fn ast_without_spans(module: &Module) -> String {
+    let debug = format!("{module:?}");
+    let mut out = String::with_capacity(debug.len());
+    let mut rest = debug.as_str();
+    while let Some(idx) = rest.find(|ch: char| ch.is_ascii_digit()) {
+        out.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        let number_len = |s: &str| s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
+        let start_len = number_len(rest);
+        let after = &rest[start_len..];
+        if let Some(end) = after.strip_prefix("..") {
+            let end_len = number_len(end);
+            if end_len > 0 {
+                out.push_str("_..");
+                rest = &end[end_len..];
+                continue;
+            }
+        }
+        if out.ends_with("NodeId(") {
+            rest = after;
+            continue;
+        }
+        out.push_str(&rest[..start_len]);
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+fn comments(src: &str) -> Vec<String> {
+    let mut db = TestDb::default();
+    let id = SourceFileId::new_local(&mut db, "test.fe", src.into());
+    Lexer::with_comments(id, src)
+        .filter(|tok| tok.kind == TokenKind::Comment)
+        .map(|tok| tok.text.trim_end().to_string())
+        .collect()
+}
+
+#[test]
+fn format_fixtures() {
+    let mut formatted_count = 0;
+    for (path, src) in fe_test_files::fixture_files() {
+        if !path.ends_with(".fe") {
+            continue;
+        }
+        let mut db = TestDb::default();
+        let id = SourceFileId::new_local(&mut db, path, src.into());
+        let (original, diags) = parse_file(id, src);
+        // Some fixtures contain syntax errors on purpose.
+        if diags.iter().any(|diag| diag.is_error()) {
+            continue;
+        }
+        let formatted = format_file(id, src).unwrap_or_else(|diags| {
+            panic!(
+                "{path}: formatting failed:\n{}",
+                diagnostics_string(&db, &diags)
+            )
+        });
+        formatted_count += 1;
+
+        let (reformatted, diags) = parse_file(id, &formatted);
+        assert!(
+            diags.is_empty(),
+            "{path}: formatted code doesn't parse:\n{formatted}"
+        );
+        assert_eq!(
+            ast_without_spans(&original),
+            ast_without_spans(&reformatted),
+            "{path}: formatting changed the code"
+        );
+        assert_eq!(
+            comments(src),
+            comments(&formatted),
+            "{path}: comments changed"
+        );
+        assert_eq!(
+            format_file(id, &formatted).unwrap(),
+            formatted,
+            "{path}: formatting isn't idempotent"
+        );
+    }
+    assert!(formatted_count > 0);
+}
+
+#[test]
+fn format_comments_and_blank_lines() {
+    let src = r#"
+use std::evm   // the evm
+
+
+// the answer
+const ANSWER: u256=42
+contract Foo {
+    x: u256
+    pub y:   Array<u8,    100>
+    // not a field
+    pub fn bar(self, ctx: Context, val: u256) -> u256 { // bar
+        // leading
+
+
+        let x: u256 = (1 + 2) * (val) // trailing
+        if x > 10 {
+            return x
+        } else {
+            if x < 5 { return 0 }
+        }
+        return baz(val: x,
+            // a comment
+            val2: ANSWER)
+    }
+    fn empty() {
+    }
+}
+"#;
+    let expected = r#"use std::evm // the evm
+
+// the answer
+const ANSWER: u256 = 42
+
+contract Foo {
+    x: u256
+    pub y: Array<u8, 100>
+
+    // not a field
+    pub fn bar(self, ctx: Context, val: u256) -> u256 { // bar
+        // leading
+
+        let x: u256 = (1 + 2) * val // trailing
+        if x > 10 {
+            return x
+        } else {
+            if x < 5 {
+                return 0
+            }
+        }
+        return baz(
+            val: x,
+            // a comment
+            val2: ANSWER,
+        )
+    }
+
+    fn empty() {}
+}
+"#;
+    assert_eq!(format(src), expected);
+    assert_eq!(format(expected), expected);
+}
+
+#[test]
+fn format_long_calls() {
+    let src = r#"fn f() {
+    let x: u256 = some_function(first_argument, second_argument, third_argument, fourth_argument, fifth_argument)
+    g(((b if a else c)), (x))
+}
+"#;
+    let expected = r#"fn f() {
+    let x: u256 = some_function(
+        first_argument,
+        second_argument,
+        third_argument,
+        fourth_argument,
+        fifth_argument,
+    )
+    g(b if a else c, x)
+}
+"#;
+    assert_eq!(format(src), expected);
+}
+
+#[test]
+fn format_empty_else() {
+    let src = r#"fn f(x: bool) {
+    if x { g() } else {}
+    if x {} else { }
+}
+"#;
+    let expected = r#"fn f(x: bool) {
+    if x {
+        g()
+    } else {
+    }
+    if x {
+    } else {
+    }
+}
+"#;
+    assert_eq!(format(src), expected);
+}
+
+#[test]
+fn format_rejects_comments_inside_expressions() {
+    let src = r#"fn f() -> Array<u256, 2> {
+    return [1, // one
+        2]
+}
+"#;
+    let mut db = TestDb::default();
+    let id = SourceFileId::new_local(&mut db, "test.fe", src.into());
+    let diags = format_file(id, src).unwrap_err();
+    assert_eq!(diags.len(), 1);
+    assert_eq!(
+        diags[0].message,
+        "can't format a comment inside this expression"
+    );
+    assert_eq!(
+        &src[diags[0].labels[0].span.start..diags[0].labels[0].span.end],
+        "// one"
+    );
+
+    // Comments between call arguments are kept in place.
+    let src = r#"fn f() {
+    g(
+        // first
+        1,
+        2, // second
+    )
+}
+"#;
+    assert_eq!(format(src), src);
+}
@@ -84,6 +84,7 @@ test_parse! { expr_list2, expressions::parse_expr, "[x, y, z,]" }
 test_parse! { expr_repeat, expressions::parse_expr, "[true; 42]" }
 test_parse! { expr_repeat2, expressions::parse_expr, "[5 + 4; 26]" }
 test_parse! { expr_ternary, expressions::parse_expr, "x + 1 if y + 2 else z + 3" }
+test_parse! { expr_try, expressions::parse_expr, "a.b(x)?.c? + 1" }
 test_parse! { expr_group, expressions::parse_expr, "(1 + 2) * 3" }
 test_parse! { expr_tuple1, expressions::parse_expr, "(1,)" }
 test_parse! { expr_tuple2, expressions::parse_expr, "(1, 2, \n 3)" }
@@ -217,6 +218,14 @@ test_parse! { struct_def, try_parse_module, r#"struct S {
   unsafe fn bar() {}
 }"# }
 test_parse! { empty_struct_def, try_parse_module, "struct S {}" }
+test_parse! { struct_def_generic, try_parse_module, r#"struct Pair<T: Compute, U> {
+  pub first: T
+  pub second: U
+
+  pub fn first(self) -> T {
+    return self.first
+  }
+}"# }
 
 test_parse! { enum_def, try_parse_module, r#"enum E {
     Unit1
@@ -225,6 +234,10 @@ test_parse! { enum_def, try_parse_module, r#"enum E {
     Tuple2(Array<u32, 10>, u256)
 }"# }
 test_parse! { enum_enum_def, try_parse_module, r#"enum E {}"# }
+test_parse! { enum_def_generic, try_parse_module, r#"enum Either<L, R> {
+    Left(L)
+    Right(R)
+}"# }
 
 test_parse! { contract_def, try_parse_module, r#"contract Foo {
   x: address
@@ -246,6 +259,20 @@ pub contract Foo {
     }
 }"# }
 
+test_parse! { contract_fn_attributes, try_parse_module, r#"contract Foo {
+  #view
+  pub fn bar(self) -> u256 {
+    return 1
+  }
+}
+"# }
+
+test_parse! { interface_def, try_parse_module, r#"pub interface Token {
+  fn balance_of(self, owner: address) -> u256;
+  fn transfer(mut self, to: address, value: u256) -> bool;
+}
+"# }
+
 test_parse! { module_stmts, try_parse_module, r#"
 pragma 0.5.0
 
@@ -305,3 +332,29 @@ contract Foo {
     }
 }
 "# }
+
+test_parse! { module_attribute_args, try_parse_module, r#"#test(should_revert: panic(0x01))
+fn foo() {}
+"# }
+
+test_parse! { doc_comments, try_parse_module, r#"
+/// A token.
+///
+/// Has an owner.
+#attr
+pub struct Token {
+    /// The owner.
+    #indexed
+    pub owner: address
+    // not a doc comment
+    value: u256
+
+    /// Transfers the token.
+    pub fn transfer(self) {}
+}
+
+/// Not attached to anything.
+
+//// not a doc comment
+contract Foo {}
+"# }
@@ -31,3 +31,8 @@ pub fn fixture_dir_files(path: &str) -> Vec<(&'static str, &'static str)> {
 
     fe_library::static_dir_files(dir)
 }
+
+/// Returns `(file_path, file_content)` of every fixture file.
+pub fn fixture_files() -> Vec<(&'static str, &'static str)> {
+    fe_library::static_dir_files(&FIXTURES)
+}
@@ -0,0 +1,89 @@
+use bytes::Bytes;
+use revm::interpreter::{CallInputs, Gas, InstructionResult};
+use revm::primitives::{B160, U256};
+use revm::{Database, EVMData, Inspector};
+
+/// The address that the functions of `std::test` call. It's the same as the
+/// address of Foundry's cheatcodes.
+const CHEATCODE_ADDRESS: B160 = B160([
+    0x71, 0x09, 0x70, 0x9e, 0xcf, 0xa9, 0x1a, 0x80, 0x62, 0x6f, 0xf3, 0x98, 0x9d, 0x68, 0xf6, 0x7f,
+    0x5b, 0x1d, 0xd1, 0x2d,
+]);
+
+/// `warp(uint256)`
+const WARP_SELECTOR: [u8; 4] = [0xe5, 0xd6, 0xbf, 0x02];
+/// `roll(uint256)`
+const ROLL_SELECTOR: [u8; 4] = [0x1f, 0x7b, 0x4f, 0x30];
+/// `prank(address)`
+const PRANK_SELECTOR: [u8; 4] = [0xca, 0x66, 0x9f, 0xa7];
+/// `deal(address,uint256)`
+const DEAL_SELECTOR: [u8; 4] = [0xc8, 0x8a, 0x5e, 0x6d];
+
+/// Intercepts the calls that a test makes to the cheatcode address, and
+/// changes the state of the EVM instead.
+pub struct Cheatcodes {
+    test_address: B160,
+    /// The caller of the next call that the test makes, set by `prank`.
+    prank: Option<B160>,
+}
+
+impl Cheatcodes {
+    pub fn new(test_address: B160) -> Self {
+        Self {
+            test_address,
+            prank: None,
+        }
+    }
+
+    /// Applies the cheatcode encoded in `input`. Returns `None` if the
+    /// cheatcode is unknown or its arguments are malformed.
+    fn apply<DB: Database>(&mut self, data: &mut EVMData<'_, DB>, input: &[u8]) -> Option<()> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        let args = &input[4..];
+        let word = |index: usize| -> Option<[u8; 32]> {
+            args.get(index * 32..(index + 1) * 32)?.try_into().ok()
+        };
+        let address = |index: usize| word(index).map(|word| B160::from_slice(&word[12..]));
+
+        match selector {
+            WARP_SELECTOR => data.env.block.timestamp = U256::from_be_bytes(word(0)?),
+            ROLL_SELECTOR => data.env.block.number = U256::from_be_bytes(word(0)?),
+            PRANK_SELECTOR => self.prank = Some(address(0)?),
+            DEAL_SELECTOR => {
+                let amount = U256::from_be_bytes(word(1)?);
+                let (account, _) = data
+                    .journaled_state
+                    .load_account(address(0)?, data.db)
+                    .ok()?;
+                account.info.balance = amount;
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+impl<DB: Database> Inspector<DB> for Cheatcodes {
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+        _is_static: bool,
+    ) -> (InstructionResult, Gas, Bytes) {
+        if inputs.contract == CHEATCODE_ADDRESS {
+            let result = match self.apply(data, &inputs.input) {
+                Some(()) => InstructionResult::Return,
+                None => InstructionResult::Revert,
+            };
+            return (result, Gas::new(inputs.gas_limit), Bytes::new());
+        }
+
+        if inputs.context.caller == self.test_address {
+            if let Some(caller) = self.prank.take() {
+                inputs.context.caller = caller;
+                inputs.transfer.source = caller;
+            }
+        }
+        (InstructionResult::Continue, Gas::new(0), Bytes::new())
+    }
+}
@@ -1,41 +1,206 @@
 use bytes::Bytes;
+use cheatcodes::Cheatcodes;
 use colored::Colorize;
 use revm::primitives::{AccountInfo, Bytecode, Env, ExecutionResult, TransactTo, B160, U256};
+use serde_json::json;
 use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+mod cheatcodes;
+
+/// Selects tests by their qualified names, like `module::test_name`.
+#[derive(Debug, Clone, Default)]
+pub struct TestFilter {
+    pattern: Option<String>,
+    exact: bool,
+}
+
+impl TestFilter {
+    /// A filter that matches the names that contain `pattern`, or that match it
+    /// if it contains `*` or `?` wildcards. If `exact` is set, names only match
+    /// if they're equal to `pattern`.
+    pub fn new(pattern: Option<String>, exact: bool) -> Self {
+        Self { pattern, exact }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match &self.pattern {
+            None => true,
+            Some(pattern) if self.exact => name == pattern,
+            Some(pattern) if pattern.contains(['*', '?']) => {
+                glob_matches(pattern.as_bytes(), name.as_bytes())
+            }
+            Some(pattern) => name.contains(pattern.as_str()),
+        }
+    }
+}
+
+fn glob_matches(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| glob_matches(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && glob_matches(rest, &name[1..]),
+        Some((byte, rest)) => name.first() == Some(byte) && glob_matches(rest, &name[1..]),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    Failed { reason: String },
+}
+
+/// The result of executing a single test.
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub name: String,
+    pub outcome: TestOutcome,
+    pub gas_used: u64,
+    /// The data that the test reverted with, if it reverted.
+    pub revert_data: Option<Vec<u8>>,
+    pub duration: Duration,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.outcome == TestOutcome::Passed
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct TestSink {
-    success_count: usize,
-    failure_details: Vec<String>,
+    results: Vec<TestResult>,
 }
 
 impl TestSink {
     pub fn test_count(&self) -> usize {
-        self.failure_count() + self.success_count()
+        self.results.len()
     }
 
     pub fn failure_count(&self) -> usize {
-        self.failure_details.len()
+        self.test_count() - self.success_count()
     }
 
     pub fn success_count(&self) -> usize {
-        self.success_count
+        self.results.iter().filter(|result| result.passed()).count()
+    }
+
+    pub fn insert_result(&mut self, result: TestResult) {
+        self.results.push(result)
     }
 
-    pub fn insert_failure(&mut self, name: &str, reason: &str) {
-        self.failure_details
-            .push(format!("{} ({})", name, reason.red()))
+    /// Appends the results of `other`, like those of tests that were executed
+    /// on another thread.
+    pub fn merge(&mut self, other: TestSink) {
+        self.results.extend(other.results)
     }
 
-    pub fn inc_success_count(&mut self) {
-        self.success_count += 1
+    pub fn results(&self) -> &[TestResult] {
+        &self.results
     }
 
     pub fn failure_details(&self) -> String {
-        self.failure_details.join("\n")
+        self.results
+            .iter()
+            .filter_map(|result| match &result.outcome {
+                TestOutcome::Passed => None,
+                TestOutcome::Failed { reason } => {
+                    Some(format!("{} ({})", result.name, reason.red()))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Reports the results as a JSON object.
+    pub fn to_json(&self) -> String {
+        let tests: Vec<_> = self
+            .results
+            .iter()
+            .map(|result| {
+                let (status, failure) = match &result.outcome {
+                    TestOutcome::Passed => ("passed", None),
+                    TestOutcome::Failed { reason } => ("failed", Some(reason)),
+                };
+                json!({
+                    "name": result.name,
+                    "status": status,
+                    "failure": failure,
+                    "gas_used": result.gas_used,
+                    "revert_data": result.revert_data.as_ref().map(|data| format!("0x{}", hex::encode(data))),
+                    "duration_secs": result.duration.as_secs_f64(),
+                })
+            })
+            .collect();
+
+        let report = json!({
+            "passed": self.success_count(),
+            "failed": self.failure_count(),
+            "tests": tests,
+        });
+        serde_json::to_string_pretty(&report).expect("failed to serialize test report")
+    }
+
+    /// Reports the results as a JUnit XML document, with the module part of
+    /// each test's name as its class name.
+    pub fn to_junit(&self) -> String {
+        let total_time: Duration = self.results.iter().map(|result| result.duration).sum();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml += &format!(
+            "<testsuites name=\"fe test\" tests=\"{}\" failures=\"{}\" time=\"{:.6}\">\n",
+            self.test_count(),
+            self.failure_count(),
+            total_time.as_secs_f64()
+        );
+        xml += &format!(
+            "  <testsuite name=\"fe test\" tests=\"{}\" failures=\"{}\" time=\"{:.6}\">\n",
+            self.test_count(),
+            self.failure_count(),
+            total_time.as_secs_f64()
+        );
+
+        for result in &self.results {
+            let (class_name, name) = result
+                .name
+                .rsplit_once("::")
+                .unwrap_or(("", result.name.as_str()));
+            xml += &format!(
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\">\n",
+                xml_escape(class_name),
+                xml_escape(name),
+                result.duration.as_secs_f64()
+            );
+            if let TestOutcome::Failed { reason } = &result.outcome {
+                xml += &format!("      <failure message=\"{}\"/>\n", xml_escape(reason));
+            }
+            let mut output = format!("gas used: {}", result.gas_used);
+            if let Some(data) = &result.revert_data {
+                output += &format!("\nrevert data: 0x{}", hex::encode(data));
+            }
+            xml += &format!("      <system-out>{}</system-out>\n", xml_escape(&output));
+            xml += "    </testcase>\n";
+        }
+
+        xml += "  </testsuite>\n</testsuites>\n";
+        xml
     }
 }
 
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
 impl Display for TestSink {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.failure_count() != 0 {
@@ -65,36 +230,252 @@ impl Display for TestSink {
     }
 }
 
-pub fn execute(name: &str, bytecode: &str, sink: &mut TestSink) -> bool {
+/// The revert that a test is expected to end with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectedRevert {
+    /// Any revert, with any data.
+    Any,
+    /// A revert with the named error struct, whatever the values of its fields.
+    Error { name: String, selector: [u8; 4] },
+    /// A `Panic(uint256)` revert with the given code.
+    Panic(u64),
+}
+
+impl ExpectedRevert {
+    fn matches(&self, output: &[u8]) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Error { selector, .. } => output.starts_with(selector),
+            Self::Panic(code) => output == encode_panic(*code),
+        }
+    }
+}
+
+impl Display for ExpectedRevert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => write!(f, "a revert"),
+            Self::Error { name, .. } => write!(f, "a revert with `{name}`"),
+            Self::Panic(code) => write!(f, "a revert with `Panic(0x{code:02x})`"),
+        }
+    }
+}
+
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+fn encode_panic(code: u64) -> Vec<u8> {
+    let mut data = PANIC_SELECTOR.to_vec();
+    data.extend_from_slice(&[0; 24]);
+    data.extend_from_slice(&code.to_be_bytes());
+    data
+}
+
+/// Describes revert data, decoding the `Panic(uint256)` and `Error(string)`
+/// errors.
+fn decode_revert(output: &[u8]) -> String {
+    if output.is_empty() {
+        return "no data".into();
+    }
+
+    if output.len() == 36 && output.starts_with(&PANIC_SELECTOR) {
+        let code = output[4..].iter().skip_while(|byte| **byte == 0);
+        let code = hex::encode(code.copied().collect::<Vec<_>>());
+        return format!("`Panic(0x{code:0>2})`");
+    }
+
+    if output.starts_with(&ERROR_SELECTOR) && output.len() >= 68 {
+        // The string is the only field, so its length comes right after its offset.
+        let (high, low) = output[36..68].split_at(24);
+        let len = u64::from_be_bytes(low.try_into().unwrap());
+        let reason = usize::try_from(len)
+            .ok()
+            .and_then(|len| len.checked_add(68))
+            .and_then(|end| output.get(68..end));
+        if let (true, Some(reason)) = (high.iter().all(|byte| *byte == 0), reason) {
+            return format!("`Error({:?})`", String::from_utf8_lossy(reason));
+        }
+    }
+
+    format!("0x{}", hex::encode(output))
+}
+
+pub fn execute(
+    name: &str,
+    bytecode: &str,
+    expected_revert: Option<&ExpectedRevert>,
+    sink: &mut TestSink,
+) -> bool {
     let bytecode = Bytecode::new_raw(Bytes::copy_from_slice(&hex::decode(bytecode).unwrap()));
 
+    // Both the sender of the test transaction and the test itself start with
+    // 1000 ether, so that tests can send value to the contracts they create.
+    let initial_balance = U256::from(1_000_000_000_000_000_000_000u128);
+
     let mut database = revm::InMemoryDB::default();
     let test_address = B160::from(42);
-    let test_info = AccountInfo::new(U256::ZERO, 0, bytecode);
+    let test_info = AccountInfo::new(initial_balance, 0, bytecode);
     database.insert_account_info(test_address, test_info);
 
+    let sender_address = B160::from(0x5e4de5);
+    let sender_info = AccountInfo::new(initial_balance, 0, Bytecode::new());
+    database.insert_account_info(sender_address, sender_info);
+
     let mut env = Env::default();
+    env.tx.caller = sender_address;
     env.tx.transact_to = TransactTo::Call(test_address);
 
     let mut evm = revm::new();
     evm.env = env;
     evm.database(&mut database);
-    let result = evm.transact_commit().expect("evm failure");
-
-    if result.is_success() {
-        sink.inc_success_count();
-        true
-    } else if let ExecutionResult::Revert { gas_used, output } = result {
-        sink.insert_failure(
-            name,
-            &format!(
-                "Reverted gas used: {} output: {}",
-                gas_used,
-                hex::encode(output)
-            ),
-        );
-        false
-    } else {
-        panic!("test halted")
+    let start = Instant::now();
+    let result = evm
+        .inspect_commit(Cheatcodes::new(test_address))
+        .expect("evm failure");
+    let duration = start.elapsed();
+
+    let (gas_used, revert_data) = match &result {
+        ExecutionResult::Success { gas_used, .. } | ExecutionResult::Halt { gas_used, .. } => {
+            (*gas_used, None)
+        }
+        ExecutionResult::Revert { gas_used, output } => (*gas_used, Some(output.to_vec())),
+    };
+
+    let failure = match (result, expected_revert) {
+        (ExecutionResult::Success { .. }, None) => None,
+        (ExecutionResult::Success { .. }, Some(expected)) => {
+            Some(format!("expected {expected}, but the test passed"))
+        }
+        (ExecutionResult::Revert { gas_used, output }, None) => Some(format!(
+            "Reverted gas used: {} output: {}",
+            gas_used,
+            decode_revert(&output)
+        )),
+        (ExecutionResult::Revert { output, .. }, Some(expected)) => {
+            if expected.matches(&output) {
+                None
+            } else {
+                Some(format!(
+                    "expected {expected}, but it reverted with {}",
+                    decode_revert(&output)
+                ))
+            }
+        }
+        (ExecutionResult::Halt { reason, gas_used }, _) => {
+            Some(format!("Halted gas used: {gas_used} reason: {reason:?}"))
+        }
+    };
+
+    let outcome = match failure {
+        Some(reason) => TestOutcome::Failed { reason },
+        None => TestOutcome::Passed,
+    };
+    let passed = outcome == TestOutcome::Passed;
+    sink.insert_result(TestResult {
+        name: name.to_string(),
+        outcome,
+        gas_used,
+        revert_data,
+        duration,
+    });
+    passed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(pattern: &str, exact: bool) -> TestFilter {
+        TestFilter::new(Some(pattern.to_string()), exact)
+    }
+
+    #[test]
+    fn filter_without_pattern_matches_everything() {
+        assert!(TestFilter::default().matches("math::test_sqrt"));
+        assert!(TestFilter::new(None, false).matches(""));
+    }
+
+    #[test]
+    fn filter_matches_substrings() {
+        let filter = filter("sqrt", false);
+        assert!(filter.matches("math::test_sqrt"));
+        assert!(filter.matches("sqrt"));
+        assert!(!filter.matches("math::test_min"));
+    }
+
+    #[test]
+    fn filter_matches_globs() {
+        assert!(filter("math::*", false).matches("math::test_sqrt"));
+        assert!(filter("*::test_*", false).matches("math::test_min"));
+        assert!(filter("math::test_m?n", false).matches("math::test_min"));
+        assert!(filter("*", false).matches(""));
+
+        // Globs have to match the whole name, unlike substrings.
+        assert!(!filter("test_*", false).matches("math::test_min"));
+        assert!(!filter("math::test_m?n", false).matches("math::test_mn"));
+        assert!(!filter("math::test_m?n", false).matches("math::test_mins"));
+    }
+
+    #[test]
+    fn filter_matches_exact_names() {
+        let exact = filter("math::test_min", true);
+        assert!(exact.matches("math::test_min"));
+        assert!(!exact.matches("math::test_min_zero"));
+        assert!(!exact.matches("other::math::test_min"));
+
+        // Wildcards are taken literally.
+        let exact_glob = filter("math::*", true);
+        assert!(!exact_glob.matches("math::test_min"));
+        assert!(exact_glob.matches("math::*"));
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_matches(b"", b""));
+        assert!(!glob_matches(b"", b"a"));
+        assert!(glob_matches(b"a*b*c", b"abbbc"));
+        assert!(glob_matches(b"a*b*c", b"abc"));
+        assert!(!glob_matches(b"a*b*c", b"acb"));
+        assert!(glob_matches(b"??", b"ab"));
+        assert!(!glob_matches(b"??", b"a"));
+        assert!(glob_matches(b"**?", b"a"));
+    }
+
+    fn sink() -> TestSink {
+        let mut sink = TestSink::default();
+        sink.insert_result(TestResult {
+            name: "math::test_sqrt".to_string(),
+            outcome: TestOutcome::Passed,
+            gas_used: 21_000,
+            revert_data: None,
+            duration: Duration::from_millis(2),
+        });
+        sink.insert_result(TestResult {
+            name: "math::test_min".to_string(),
+            outcome: TestOutcome::Failed {
+                reason: "expected `a < b` & \"b > a\"".to_string(),
+            },
+            gas_used: 30_500,
+            revert_data: Some(vec![0x4e, 0x48, 0x7b, 0x71]),
+            duration: Duration::from_micros(1500),
+        });
+        sink.insert_result(TestResult {
+            name: "test_<top>".to_string(),
+            outcome: TestOutcome::Passed,
+            gas_used: 0,
+            revert_data: None,
+            duration: Duration::ZERO,
+        });
+        sink
+    }
+
+    #[test]
+    fn json_report() {
+        insta::assert_snapshot!(sink().to_json());
+    }
+
+    #[test]
+    fn junit_report() {
+        insta::assert_snapshot!(sink().to_junit());
     }
 }
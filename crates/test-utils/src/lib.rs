@@ -186,6 +186,23 @@ impl ContractHarness {
         validate_revert(self.capture_call(executor, name, input), revert_data)
     }
 
+    /// Checks that the call reverts with the error of the contract ABI named
+    /// `error`, encoded with `args`.
+    pub fn test_function_reverts_with_error(
+        &self,
+        executor: &mut Executor,
+        name: &str,
+        input: &[ethabi::Token],
+        error: &str,
+        args: &[ethabi::Token],
+    ) {
+        let error = &self.abi.errors[error][0];
+        let revert_data = error
+            .encode(args)
+            .unwrap_or_else(|reason| panic!("Unable to encode error {}: {reason:?}", error.name));
+        self.test_function_reverts(executor, name, input, &revert_data)
+    }
+
     pub fn test_call_reverts(&self, executor: &mut Executor, input: Vec<u8>, revert_data: &[u8]) {
         validate_revert(self.capture_call_raw_bytes(executor, input), revert_data)
     }
@@ -319,6 +336,10 @@ pub fn encoded_panic_out_of_bounds() -> Vec<u8> {
     encode_revert("Panic(uint256)", &[uint_token(0x32)])
 }
 
+pub fn encoded_panic_empty_pop() -> Vec<u8> {
+    encode_revert("Panic(uint256)", &[uint_token(0x31)])
+}
+
 pub fn encoded_div_or_mod_by_zero() -> Vec<u8> {
     encode_revert("Panic(uint256)", &[uint_token(0x12)])
 }
@@ -394,6 +415,46 @@ pub fn deploy_contract_from_ingot(
     )
 }
 
+#[allow(dead_code)]
+#[cfg(feature = "solc-backend")]
+pub fn deploy_contract_from_ingot_dir(
+    executor: &mut Executor,
+    path: &str,
+    contract_name: &str,
+    init_params: &[ethabi::Token],
+) -> ContractHarness {
+    let dir = fixture_dir_path(path);
+    let mut db = driver::Db::default();
+    let compiled_module = match driver::compile_ingot_dir(&mut db, &dir, true, true, true) {
+        Ok(module) => module,
+        Err(error) => {
+            fe_common::diagnostics::print_diagnostics(&db, &error.0);
+            panic!("failed to compile ingot: {path}")
+        }
+    };
+
+    let compiled_contract = compiled_module
+        .contracts
+        .get(contract_name)
+        .expect("could not find contract in fixture");
+
+    _deploy_contract(
+        executor,
+        &compiled_contract.bytecode,
+        &compiled_contract.json_abi,
+        init_params,
+    )
+}
+
+/// Returns the on-disk path of a directory in `test-files/fixtures`, for
+/// driver functions that read ingots (and their dependencies) from disk.
+#[allow(dead_code)]
+pub fn fixture_dir_path(path: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../test-files/fixtures")
+        .join(path)
+}
+
 #[allow(dead_code)]
 #[cfg(feature = "solc-backend")]
 pub fn deploy_solidity_contract(
@@ -774,6 +835,11 @@ pub fn uint_array_token(v: &[u64]) -> ethabi::Token {
     ethabi::Token::FixedArray(v.iter().map(|n| uint_token(*n)).collect())
 }
 
+#[allow(dead_code)]
+pub fn uint_vec_token(v: &[u64]) -> ethabi::Token {
+    ethabi::Token::Array(v.iter().map(|n| uint_token(*n)).collect())
+}
+
 #[allow(dead_code)]
 pub fn int_array_token(v: &[i64]) -> ethabi::Token {
     ethabi::Token::FixedArray(v.iter().map(|n| int_token(*n)).collect())
@@ -4,7 +4,7 @@
 use evm::{Capture, ExitReason};
 use evm_runtime::Handler;
 use insta::assert_snapshot;
-use primitive_types::{H160, U256};
+use primitive_types::{H160, H256, U256};
 use rstest::rstest;
 use std::collections::BTreeMap;
 
@@ -139,6 +139,25 @@ fn test_revert() {
                 &[uint_token(1), bool_token(true)],
             ),
         );
+
+        // The revert data can be decoded with the errors of the contract ABI.
+        harness.test_function_reverts_with_error(
+            &mut executor,
+            "revert_custom_error",
+            &[],
+            "Error",
+            &[uint_token(0x100)],
+        );
+        harness.test_function_reverts_with_error(
+            &mut executor,
+            "revert_other_error",
+            &[],
+            "OtherError",
+            &[uint_token(1), bool_token(true)],
+        );
+        assert_eq!(harness.abi.errors.len(), 2);
+        // The error structs aren't events.
+        assert!(harness.abi.events.is_empty());
     })
 }
 
@@ -1430,6 +1449,46 @@ fn external_contract() {
     })
 }
 
+#[test]
+fn interface() {
+    with_executor(&|mut executor| {
+        let token_harness = deploy_contract(&mut executor, "interface.fe", "Token", &[]);
+        let wallet_harness = deploy_contract(&mut executor, "interface.fe", "Wallet", &[]);
+
+        let token = ethabi::Token::Address(token_harness.address);
+        let wallet = ethabi::Token::Address(wallet_harness.address);
+        let recipient = address_token("1234");
+
+        token_harness.test_function(
+            &mut executor,
+            "mint",
+            &[wallet.clone(), uint_token(1000)],
+            None,
+        );
+
+        wallet_harness.test_function(
+            &mut executor,
+            "send",
+            &[token.clone(), recipient.clone(), uint_token(100)],
+            Some(&bool_token(true)),
+        );
+
+        wallet_harness.test_function(
+            &mut executor,
+            "balance",
+            &[token.clone(), recipient],
+            Some(&uint_token(100)),
+        );
+
+        wallet_harness.test_function(
+            &mut executor,
+            "balance",
+            &[token, wallet],
+            Some(&uint_token(900)),
+        );
+    })
+}
+
 #[test]
 fn create2_contract() {
     with_executor(&|mut executor| {
@@ -1966,6 +2025,9 @@ fn ctx_init_in_call() {
     case::trait_associated_functions("trait_associated_functions.fe"),
     case::generic_functions("generic_functions.fe"),
     case::generic_functions_primitves("generic_functions_primitves.fe"),
+    case::generic_structs("generic_structs.fe"),
+    case::option_result("option_result.fe"),
+    case::vec("vec.fe"),
     case::contract_pure_fns("contract_pure_fns.fe")
 )]
 fn execution_tests(fixture_file: &str) {
@@ -1975,3 +2037,535 @@ fn execution_tests(fixture_file: &str) {
         assert_harness_gas_report!(harness, fixture_file);
     })
 }
+
+#[test]
+fn generic_struct_abi() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "generic_structs.fe", "Example", &[]);
+        let wrapper = |n| tuple_token(&[uint_token(n)]);
+
+        harness.test_function(&mut executor, "wrap", &[uint_token(42)], Some(&wrapper(42)));
+        harness.test_function(
+            &mut executor,
+            "unwrap",
+            &[wrapper(42)],
+            Some(&uint_token(42)),
+        );
+        harness.test_function(&mut executor, "rewrap", &[wrapper(42)], Some(&wrapper(43)));
+    })
+}
+
+#[test]
+fn vec_abi() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "vec.fe", "Example", &[]);
+
+        harness.test_function(
+            &mut executor,
+            "total",
+            &[uint_vec_token(&[1, 2, 3, 4])],
+            Some(&uint_token(10)),
+        );
+        harness.test_function(
+            &mut executor,
+            "total",
+            &[uint_vec_token(&[])],
+            Some(&uint_token(0)),
+        );
+        harness.test_function(
+            &mut executor,
+            "squares",
+            &[uint_token(5)],
+            Some(&uint_vec_token(&[0, 1, 4, 9, 16])),
+        );
+        harness.test_function(&mut executor, "get", &[uint_token(0)], Some(&uint_token(1)));
+        harness.test_function_reverts(
+            &mut executor,
+            "get",
+            &[uint_token(1)],
+            &encoded_panic_out_of_bounds(),
+        );
+        harness.test_function_reverts(&mut executor, "pop_empty", &[], &encoded_panic_empty_pop());
+    })
+}
+
+#[test]
+fn storage_packing() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "storage_packing.fe", "Foo", &[]);
+        harness.test_function(&mut executor, "set", &[], None);
+        harness.test_function(&mut executor, "check", &[], None);
+
+        let slot = |executor: &Executor, slot| {
+            executor
+                .storage(harness.address, H256::from_low_u64_be(slot))
+                .to_fixed_bytes()
+        };
+        let mut packed = [0; 32];
+        packed[..5].copy_from_slice(&[1, 2, 3, 4, 1]);
+        packed[5] = 0x20;
+        packed[24] = 0x02;
+        assert_eq!(slot(&executor, 0), packed);
+        assert_eq!(
+            slot(&executor, 1),
+            H256::from_low_u64_be(5).to_fixed_bytes()
+        );
+        let mut point = [0; 32];
+        point[..4].copy_from_slice(&[6, 7, 8, 9]);
+        assert_eq!(slot(&executor, 2), point);
+
+        harness.test_function(&mut executor, "bump", &[], None);
+        packed[2] = 30;
+        point[1] = 70;
+        assert_eq!(slot(&executor, 0), packed);
+        assert_eq!(slot(&executor, 2), point);
+
+        let mut db = fe_driver::Db::default();
+        let path = "features/storage_packing.fe";
+        let module =
+            fe_driver::compile_single_file(&mut db, path, test_files::fixture(path), false, true)
+                .unwrap_or_else(|_| panic!("failed to compile {path}"));
+        let layout: serde_json::Value =
+            serde_json::from_str(&module.contracts["Foo"].storage_layout).unwrap();
+        let fields: Vec<_> = layout["storage"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| {
+                (
+                    field["name"].as_str().unwrap(),
+                    field["slot"].as_u64().unwrap(),
+                    field["offset"].as_u64().unwrap(),
+                    field["size"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("a", 0, 0, 1),
+                ("b", 0, 1, 1),
+                ("c", 0, 2, 1),
+                ("d", 0, 3, 1),
+                ("flag", 0, 4, 1),
+                ("owner", 0, 5, 20),
+                ("total", 1, 0, 32),
+                ("point", 2, 0, 2),
+                ("small", 2, 2, 2),
+            ]
+        );
+    })
+}
+
+#[test]
+fn storage_vec() {
+    with_executor(&|mut executor| {
+        let harness = deploy_contract(&mut executor, "storage_vec.fe", "Foo", &[]);
+
+        harness.test_function_reverts(&mut executor, "pop", &[], &encoded_panic_empty_pop());
+        for value in [10, 20, 30] {
+            harness.test_function(&mut executor, "push", &[uint_token(value)], None);
+        }
+        harness.test_function(&mut executor, "len", &[], Some(&uint_token(3)));
+        harness.test_function(
+            &mut executor,
+            "get",
+            &[uint_token(1)],
+            Some(&uint_token(20)),
+        );
+        harness.test_function_reverts(
+            &mut executor,
+            "get",
+            &[uint_token(3)],
+            &encoded_panic_out_of_bounds(),
+        );
+        harness.test_function(&mut executor, "set", &[uint_token(1), uint_token(21)], None);
+        harness.test_function(
+            &mut executor,
+            "items",
+            &[],
+            Some(&uint_vec_token(&[10, 21, 30])),
+        );
+
+        // The length lives in the field's slot and the elements start at the slot
+        // `keccak256(abi.encode(slot))`.
+        let len_slot = H256::zero();
+        let data_slot = U256::from(keccak::full_as_bytes(len_slot.as_bytes()));
+        assert_eq!(
+            executor.storage(harness.address, len_slot),
+            H256::from_low_u64_be(3)
+        );
+        let mut first_elem_slot = [0; 32];
+        data_slot.to_big_endian(&mut first_elem_slot);
+        assert_eq!(
+            executor.storage(harness.address, H256(first_elem_slot)),
+            H256::from_low_u64_be(10)
+        );
+
+        harness.test_function(&mut executor, "pop", &[], Some(&uint_token(30)));
+        harness.test_function(&mut executor, "len", &[], Some(&uint_token(2)));
+        harness.test_function(
+            &mut executor,
+            "replace",
+            &[uint_vec_token(&[7, 8, 9, 10])],
+            None,
+        );
+        harness.test_function(
+            &mut executor,
+            "items",
+            &[],
+            Some(&uint_vec_token(&[7, 8, 9, 10])),
+        );
+
+        // Shrinking the `Vec` clears the elements beyond its new length.
+        harness.test_function(&mut executor, "replace", &[uint_vec_token(&[6])], None);
+        harness.test_function(&mut executor, "items", &[], Some(&uint_vec_token(&[6])));
+        for idx in 0..4 {
+            let mut elem_slot = [0; 32];
+            (data_slot + idx).to_big_endian(&mut elem_slot);
+            let expected = if idx == 0 { 6 } else { 0 };
+            assert_eq!(
+                executor.storage(harness.address, H256(elem_slot)),
+                H256::from_low_u64_be(expected)
+            );
+        }
+        harness.test_function(&mut executor, "push", &[uint_token(5)], None);
+        harness.test_function(&mut executor, "items", &[], Some(&uint_vec_token(&[6, 5])));
+
+        harness.test_function(&mut executor, "push_flag", &[uint_token(255)], None);
+        harness.test_function(&mut executor, "push_flag", &[uint_token(1)], None);
+        harness.test_function(
+            &mut executor,
+            "flag",
+            &[uint_token(0)],
+            Some(&uint_token(255)),
+        );
+        harness.test_function(
+            &mut executor,
+            "flag",
+            &[uint_token(1)],
+            Some(&uint_token(1)),
+        );
+
+        // Shrinking a `Vec` of packed elements keeps the live elements that share
+        // the last slot with the cleared ones.
+        harness.test_function(
+            &mut executor,
+            "replace_flags",
+            &[uint_vec_token(&[1, 2, 3, 4, 5])],
+            None,
+        );
+        harness.test_function(
+            &mut executor,
+            "replace_flags",
+            &[uint_vec_token(&[6, 7, 8])],
+            None,
+        );
+        harness.test_function(
+            &mut executor,
+            "flags",
+            &[],
+            Some(&uint_vec_token(&[6, 7, 8])),
+        );
+        let flags_slot = keccak::full_as_bytes(H256::from_low_u64_be(1).as_bytes());
+        let mut packed_flags = [0; 32];
+        packed_flags[..3].copy_from_slice(&[6, 7, 8]);
+        assert_eq!(
+            executor.storage(harness.address, H256(flags_slot)),
+            H256(packed_flags)
+        );
+        harness.test_function(&mut executor, "push_flag", &[uint_token(9)], None);
+        harness.test_function(
+            &mut executor,
+            "flags",
+            &[],
+            Some(&uint_vec_token(&[6, 7, 8, 9])),
+        );
+
+        harness.test_function(
+            &mut executor,
+            "push_point",
+            &[uint_token(1), uint_token(2)],
+            None,
+        );
+        harness.test_function(
+            &mut executor,
+            "push_point",
+            &[uint_token(3), uint_token(4)],
+            None,
+        );
+        harness.test_function(
+            &mut executor,
+            "point_x",
+            &[uint_token(1)],
+            Some(&uint_token(3)),
+        );
+        harness.test_function(&mut executor, "pop_point", &[], Some(&uint_token(7)));
+        harness.test_function(
+            &mut executor,
+            "point_x",
+            &[uint_token(0)],
+            Some(&uint_token(1)),
+        );
+
+        harness.test_function(&mut executor, "push_to_list", &[uint_token(5)], None);
+        harness.test_function(&mut executor, "list", &[], Some(&uint_vec_token(&[5])));
+    })
+}
+
+#[test]
+fn storage_layout_json() {
+    let mut db = fe_driver::Db::default();
+    let path = "features/storage_vec.fe";
+    let module =
+        fe_driver::compile_single_file(&mut db, path, test_files::fixture(path), false, true)
+            .unwrap_or_else(|_| panic!("failed to compile {path}"));
+    let layout = &module.contracts["Foo"].storage_layout;
+    for (name, ty, slot, encoding) in [
+        ("items", "Vec<u256>", 0, "dynamic"),
+        ("flags", "Vec<u8>", 1, "dynamic"),
+        ("points", "Vec<Point>", 2, "dynamic"),
+        ("lists", "Map<address, Vec<u256>>", 3, "mapping"),
+    ] {
+        let entry = format!(
+            r#"{{
+      "encoding": "{encoding}",
+      "name": "{name}",
+      "offset": 0,
+      "size": 32,
+      "slot": {slot},
+      "type": "{ty}"
+    }}"#
+        );
+        assert!(layout.contains(&entry), "missing `{name}` in {layout}");
+    }
+}
+
+#[test]
+fn abi_import() {
+    let path = "abi_import/token.json";
+    let imported = fe_driver::import_abi("IToken", test_files::fixture(path), 100)
+        .unwrap_or_else(|err| panic!("failed to import {path}: {err}"));
+    assert_eq!(
+        imported.skipped,
+        [
+            "event `Log`: anonymous events aren't supported",
+            "function `transfer`: overloaded functions aren't supported",
+            "function `checkpoint`: `uint24` has no equivalent in Fe",
+            "function `type`: the name is a Fe keyword",
+        ]
+    );
+
+    let mut db = fe_driver::Db::default();
+    let diags = fe_driver::check_single_file(&mut db, "token.fe", &imported.src);
+    assert!(diags.is_empty(), "{}", imported.src);
+    assert_snapshot!(imported.src);
+}
+
+#[test]
+fn abi_import_unsupported_types() {
+    let path = "abi_import/registry.json";
+    let imported = fe_driver::import_abi("IRegistry", test_files::fixture(path), 100)
+        .unwrap_or_else(|err| panic!("failed to import {path}: {err}"));
+    assert_eq!(
+        imported.skipped,
+        [
+            "event `Registered`: `uint256[]` can't be used in a struct field",
+            "error `UnknownNode`: `bytes32` has no equivalent in Fe",
+            "function `resolve`: `bytes32` has no equivalent in Fe",
+            "function `register`: `uint256[]` can't be used in a struct field",
+            "function `rate`: `ufixed128x18` has no equivalent in Fe",
+        ]
+    );
+
+    let mut db = fe_driver::Db::default();
+    let diags = fe_driver::check_single_file(&mut db, "registry.fe", &imported.src);
+    assert!(diags.is_empty(), "{}", imported.src);
+    assert_snapshot!(imported.src);
+}
+
+#[test]
+fn abi_round_trip() {
+    let mut db = fe_driver::Db::default();
+    let path = "features/interface.fe";
+    let module =
+        fe_driver::compile_single_file(&mut db, path, test_files::fixture(path), false, true)
+            .unwrap_or_else(|_| panic!("failed to compile {path}"));
+    for (name, contract) in &module.contracts {
+        let abi: fe_abi::contract::AbiContract = serde_json::from_str(&contract.json_abi)
+            .unwrap_or_else(|err| panic!("failed to read the ABI of `{name}`: {err}"));
+        assert_eq!(
+            serde_json::to_string_pretty(&abi).unwrap(),
+            contract.json_abi
+        );
+    }
+}
+
+#[test]
+fn state_mutability() {
+    use fe_abi::function::StateMutability::*;
+
+    let mut db = fe_driver::Db::default();
+    let path = "features/state_mutability.fe";
+    let module =
+        fe_driver::compile_single_file(&mut db, path, test_files::fixture(path), false, true)
+            .unwrap_or_else(|_| panic!("failed to compile {path}"));
+    let abi: fe_abi::contract::AbiContract =
+        serde_json::from_str(&module.contracts["Bank"].json_abi).unwrap();
+    let mutabilities = abi
+        .funcs
+        .into_iter()
+        .map(|func| (func.name, func.state_mutability))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        mutabilities,
+        [
+            ("add".to_string(), Pure),
+            ("total".to_string(), View),
+            ("sender".to_string(), View),
+            ("reset".to_string(), Nonpayable),
+            ("record".to_string(), Nonpayable),
+            ("deposit".to_string(), Payable),
+            ("doubled".to_string(), View),
+            ("tip".to_string(), Payable),
+            // `mut self` and `mut ctx` promise to modify the state.
+            ("prepare".to_string(), Nonpayable),
+            ("prepare_ctx".to_string(), Nonpayable),
+        ]
+    );
+}
+
+#[test]
+fn payable() {
+    with_executor(&|mut executor| {
+        let mut vault = deploy_contract(&mut executor, "payable.fe", "Vault", &[]);
+        vault.value = U256::from(10);
+        vault.test_function(&mut executor, "deposit", &[], None);
+        vault.test_function(&mut executor, "value", &[], Some(&uint_token(10)));
+        vault.test_function_reverts(&mut executor, "balance", &[], &[]);
+        vault.value = U256::zero();
+        vault.test_function(&mut executor, "balance", &[], Some(&uint_token(0)));
+
+        // The constructor of `Vault` isn't payable, so creating it with value fails.
+        let mut factory = deploy_contract(&mut executor, "payable.fe", "Factory", &[]);
+        executor
+            .state_mut()
+            .deposit(factory.address, U256::from(100));
+        factory.value = U256::from(10);
+        factory.test_function(
+            &mut executor,
+            "create_vault",
+            &[],
+            Some(&address_token("0")),
+        );
+        let created = factory.call_function(&mut executor, "create_payable_vault", &[]);
+        assert_ne!(created, Some(address_token("0")));
+        factory.value = U256::zero();
+        let created = factory.call_function(&mut executor, "create_vault", &[]);
+        assert_ne!(created, Some(address_token("0")));
+    });
+
+    let mut db = fe_driver::Db::default();
+    let path = "features/payable.fe";
+    let module =
+        fe_driver::compile_single_file(&mut db, path, test_files::fixture(path), false, true)
+            .unwrap_or_else(|_| panic!("failed to compile {path}"));
+    for (name, mutability) in [
+        ("Vault", fe_abi::function::StateMutability::Nonpayable),
+        ("PayableVault", fe_abi::function::StateMutability::Payable),
+    ] {
+        let abi: fe_abi::contract::AbiContract =
+            serde_json::from_str(&module.contracts[name].json_abi).unwrap();
+        let constructor = abi
+            .funcs
+            .iter()
+            .find(|func| func.func_type == fe_abi::function::AbiFunctionType::Constructor)
+            .unwrap();
+        assert_eq!(constructor.state_mutability, mutability, "{name}");
+    }
+}
+
+#[test]
+fn receive_fallback() {
+    with_executor(&|mut executor| {
+        let unknown_selector = vec![0xde, 0xad, 0xbe, 0xef];
+
+        // Empty calldata goes to `receive`, and an unknown selector to `fallback`.
+        let mut wallet = deploy_contract(&mut executor, "receive_fallback.fe", "Wallet", &[]);
+        wallet.value = U256::from(10);
+        wallet.test_call_returns(&mut executor, vec![], &[]);
+        wallet.test_call_reverts(&mut executor, unknown_selector.clone(), &[]);
+        wallet.value = U256::zero();
+        wallet.test_call_returns(&mut executor, unknown_selector.clone(), &[]);
+        wallet.test_function(&mut executor, "received", &[], Some(&uint_token(10)));
+        wallet.test_function(&mut executor, "fallback_calls", &[], Some(&uint_token(1)));
+
+        // Without `receive`, empty calldata goes to `fallback`.
+        let mut forwarder = deploy_contract(&mut executor, "receive_fallback.fe", "Forwarder", &[]);
+        forwarder.value = U256::from(10);
+        forwarder.test_call_returns(&mut executor, vec![], &[]);
+        forwarder.test_call_returns(&mut executor, unknown_selector, &[]);
+        forwarder.value = U256::zero();
+        forwarder.test_function(&mut executor, "calls", &[], Some(&uint_token(2)));
+    });
+
+    let mut db = fe_driver::Db::default();
+    let path = "features/receive_fallback.fe";
+    let module =
+        fe_driver::compile_single_file(&mut db, path, test_files::fixture(path), false, true)
+            .unwrap_or_else(|_| panic!("failed to compile {path}"));
+    let abi: fe_abi::contract::AbiContract =
+        serde_json::from_str(&module.contracts["Wallet"].json_abi).unwrap();
+    let special_funcs: Vec<_> = abi
+        .funcs
+        .iter()
+        .filter(|func| func.func_type != fe_abi::function::AbiFunctionType::Function)
+        .map(|func| (func.func_type, func.state_mutability.clone()))
+        .collect();
+    assert_eq!(
+        special_funcs,
+        [
+            (
+                fe_abi::function::AbiFunctionType::Receive,
+                fe_abi::function::StateMutability::Payable
+            ),
+            (
+                fe_abi::function::AbiFunctionType::Fallback,
+                fe_abi::function::StateMutability::Nonpayable
+            ),
+        ]
+    );
+}
+
+#[rstest(
+    fixture_file,
+    case("features/events.fe"),
+    case("features/revert.fe"),
+    case("features/interface.fe"),
+    case("stress/abi_encoding_stress.fe")
+)]
+fn solidity_interface(fixture_file: &str) {
+    let mut db = fe_driver::Db::default();
+    let module = fe_driver::compile_single_file(
+        &mut db,
+        fixture_file,
+        test_files::fixture(fixture_file),
+        false,
+        true,
+    )
+    .unwrap_or_else(|_| panic!("failed to compile {fixture_file}"));
+    for (name, contract) in &module.contracts {
+        let src = contract
+            .solidity_interface
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        test_utils::compile_solidity_contract(&format!("I{name}"), &src, false).unwrap_or_else(
+            |err| {
+                panic!(
+                    "solc rejected the interface of `{name}`: {err}\n{}",
+                    contract.solidity_interface
+                )
+            },
+        );
+    }
+}
@@ -43,6 +43,26 @@ fn test_ingot_pub_contract() {
         let _harness = deploy_ingot(&mut executor, "visibility_ingot", "Foo", &[]);
     })
 }
+#[test]
+fn test_ingot_with_path_dependency() {
+    with_executor(&|mut executor| {
+        let harness = test_utils::deploy_contract_from_ingot_dir(
+            &mut executor,
+            "ingots/manifest_ingot",
+            "Foo",
+            &[],
+        );
+
+        harness.test_function(&mut executor, "get_43", &[], Some(&uint_token(43)));
+        harness.test_function(
+            &mut executor,
+            "get_counter_value",
+            &[],
+            Some(&uint_token(27)),
+        );
+    })
+}
+
 #[test]
 fn test_basic_ingot() {
     with_executor(&|mut executor| {
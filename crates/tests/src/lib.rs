@@ -2,7 +2,7 @@
 #![allow(dead_code)]
 use dir_test::{dir_test, Fixture};
 use fe_common::diagnostics::print_diagnostics;
-use fe_test_runner::TestSink;
+use fe_test_runner::{TestFilter, TestSink};
 
 #[dir_test(dir: "$CARGO_MANIFEST_DIR/fixtures/files", glob: "*.fe")]
 fn single_file_test_run(fixture: Fixture<&str>) {
@@ -13,6 +13,7 @@ fn single_file_test_run(fixture: Fixture<&str>) {
         fixture.path(),
         fixture.content(),
         true,
+        &TestFilter::default(),
     ) {
         Ok((_, tests)) => tests,
         Err(error) => {